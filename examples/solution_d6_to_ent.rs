@@ -4,7 +4,6 @@ use rand::Rng;
 
 /// Here we want to devise a reliable solution to get a uniformly distributed set of random
 /// bits from a preset number of 6-sided dice rolls.
-
 fn main() {
     let mut rng = rand::thread_rng();
 
@@ -0,0 +1,304 @@
+use bip39::Mnemonic;
+use getset::Getters;
+use ring::digest::{digest, SHA256};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::{
+    process::{bit_order::BitOrder, error::ProcessError},
+    tychentropy::Tychentropy,
+};
+
+/// Label that keeps hash combined outputs apart from any other use of SHA-256.
+pub const COMBINATION_DOMAIN: &str = "tychentropy/combination/v1";
+/// Label that keeps fingerprints apart from hash combined outputs.
+pub const FINGERPRINT_DOMAIN: &str = "tychentropy/fingerprint/v1";
+/// Bytes of SHA-256 kept in a participant fingerprint.
+pub const FINGERPRINT_BYTES: usize = 4;
+
+/// How the entropies of several sessions are merged into one.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, Default, PartialEq, Eq)]
+pub enum CombinationMethod {
+    /// XOR of the entropies. Every bit stays uniform as long as one contribution is uniform
+    /// and independent of the others.
+    #[default]
+    Xor,
+    /// SHA-256 of the domain label, the target and every entropy in order, with a block counter
+    /// for targets longer than 256 bits. Also hides how the contributions relate to each other,
+    /// but changing the order of the sessions changes the result.
+    Sha256,
+}
+
+impl CombinationMethod {
+    pub fn get_name(&self) -> String {
+        match self {
+            CombinationMethod::Xor => "XOR".to_string(),
+            CombinationMethod::Sha256 => "SHA-256".to_string(),
+        }
+    }
+}
+
+/// Entropy merged from the sessions of several participants, for example for shared custody.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct CombinedEntropy {
+    method: CombinationMethod,
+    target_entropy_bits: u64,
+    bit_order: BitOrder,
+    /// One fingerprint for each session, in the order they were combined. Lets participants
+    /// check that their session was used without showing its entropy.
+    fingerprints: Vec<Vec<u8>>,
+    entropy_bytes: Vec<u8>,
+}
+
+impl CombinedEntropy {
+    /// Combines the final entropy of `sessions`, mixes included. The sessions must all be
+    /// ready, have the same target and bit order, and give different entropies.
+    pub fn combine(
+        sessions: &[&Tychentropy],
+        method: CombinationMethod,
+    ) -> Result<Self, ProcessError> {
+        if sessions.len() < 2 {
+            return Err(ProcessError::NotEnoughSessionsToCombine {
+                given: sessions.len() as u64,
+            });
+        }
+        let target_entropy_bits = *sessions[0].get_target_entropy_bits();
+        let bit_order = sessions[0].get_bit_order().clone();
+        for (index, session) in sessions.iter().enumerate() {
+            if !*session.get_is_entropy_ready() {
+                return Err(ProcessError::SessionToCombineDoesNotMatch {
+                    index: index as u64,
+                    requirement: "entropy that is ready".to_string(),
+                });
+            }
+            if *session.get_target_entropy_bits() != target_entropy_bits {
+                return Err(ProcessError::SessionToCombineDoesNotMatch {
+                    index: index as u64,
+                    requirement: format!("a target of {} bits", target_entropy_bits),
+                });
+            }
+            if *session.get_bit_order() != bit_order {
+                return Err(ProcessError::SessionToCombineDoesNotMatch {
+                    index: index as u64,
+                    requirement: format!("bytes packed {}", bit_order.get_name()),
+                });
+            }
+            if let Some(first_index) = sessions[..index].iter().position(|other| {
+                other.get_final_entropy_bytes_vector() == session.get_final_entropy_bytes_vector()
+            }) {
+                return Err(ProcessError::SessionsToCombineAreRepeated {
+                    first_index: first_index as u64,
+                    second_index: index as u64,
+                });
+            }
+        }
+        let entropies = sessions
+            .iter()
+            .map(|session| session.get_final_entropy_bytes_vector().as_slice())
+            .collect::<Vec<&[u8]>>();
+        let mut entropy_bytes = match method {
+            CombinationMethod::Xor => xor_combine(&entropies),
+            CombinationMethod::Sha256 => hash_combine(&entropies, target_entropy_bits),
+        };
+        let used_bits_in_last_byte = (target_entropy_bits % 8) as u32;
+        if used_bits_in_last_byte != 0 {
+            if let Some(last_byte) = entropy_bytes.last_mut() {
+                *last_byte &= bit_order.get_used_bits_mask(used_bits_in_last_byte);
+            }
+        }
+        Ok(CombinedEntropy {
+            method,
+            target_entropy_bits,
+            bit_order,
+            fingerprints: entropies
+                .iter()
+                .map(|entropy| fingerprint(entropy))
+                .collect(),
+            entropy_bytes,
+        })
+    }
+
+    /// Mnemonic of the combined entropy, when the target is a mnemonic length.
+    pub fn get_mnemonic(&self) -> Option<Mnemonic> {
+        match self.target_entropy_bits {
+            128 | 160 | 192 | 224 | 256 => Mnemonic::from_entropy(&self.entropy_bytes).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// First bytes of the SHA-256 of the domain label and a session's final entropy.
+pub fn fingerprint(entropy_bytes: &[u8]) -> Vec<u8> {
+    let mut input = labelled(FINGERPRINT_DOMAIN);
+    input.extend(entropy_bytes);
+    let fingerprint = digest(&SHA256, &input).as_ref()[..FINGERPRINT_BYTES].to_vec();
+    input.zeroize();
+    fingerprint
+}
+
+fn xor_combine(entropies: &[&[u8]]) -> Vec<u8> {
+    let mut combined = vec![0u8; entropies[0].len()];
+    entropies.iter().for_each(|entropy| {
+        combined
+            .iter_mut()
+            .zip(entropy.iter())
+            .for_each(|(combined_byte, byte)| *combined_byte ^= byte)
+    });
+    combined
+}
+
+fn hash_combine(entropies: &[&[u8]], target_entropy_bits: u64) -> Vec<u8> {
+    let output_len = target_entropy_bits.div_ceil(8) as usize;
+    let mut combined = vec![];
+    let mut counter = 1u32;
+    while combined.len() < output_len {
+        let mut input = labelled(COMBINATION_DOMAIN);
+        input.extend(target_entropy_bits.to_be_bytes());
+        input.extend(counter.to_be_bytes());
+        entropies.iter().for_each(|entropy| {
+            input.extend((entropy.len() as u64).to_be_bytes());
+            input.extend(entropy.iter());
+        });
+        combined.extend(digest(&SHA256, &input).as_ref());
+        input.zeroize();
+        counter += 1;
+    }
+    combined.truncate(output_len);
+    combined
+}
+
+/// The label prefixed with its length as an 8 byte big endian integer.
+fn labelled(label: &str) -> Vec<u8> {
+    let mut labelled = (label.len() as u64).to_be_bytes().to_vec();
+    labelled.extend(label.as_bytes());
+    labelled
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::process::input::new::TychentropyNewInput;
+
+    fn ready_session(rolls: Vec<u64>, target_entropy_bytes: u64) -> Tychentropy {
+        let input = TychentropyNewInput::new(8, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(rolls).unwrap();
+        tychentropy
+    }
+
+    #[test]
+    fn combine_works_with_xor_01() {
+        // d8 rolls give 3 bits each: 8 rolls of 1 and 8 of 8 are the bytes 00.. and ff..
+        let first = ready_session(vec![1; 8], 3);
+        let second = ready_session(vec![8; 8], 3);
+        let third = ready_session(vec![1, 8, 1, 8, 1, 8, 1, 8], 3);
+        let test =
+            CombinedEntropy::combine(&[&first, &second, &third], CombinationMethod::Xor).unwrap();
+        let expected = third
+            .get_final_entropy_bytes_vector()
+            .iter()
+            .map(|byte| byte ^ 0xff)
+            .collect::<Vec<u8>>();
+        assert_eq!(*test.get_entropy_bytes(), expected);
+        assert_eq!(test.get_fingerprints().len(), 3);
+        assert_eq!(test.get_fingerprints()[1], fingerprint(&[0xff, 0xff, 0xff]));
+        assert_eq!(test.get_fingerprints()[1].len(), FINGERPRINT_BYTES);
+    }
+
+    #[test]
+    fn combine_works_with_hash_01() {
+        let first = ready_session(vec![1; 8], 3);
+        let second = ready_session(vec![8; 8], 3);
+        let test = CombinedEntropy::combine(&[&first, &second], CombinationMethod::Sha256).unwrap();
+        let mut expected_input = labelled(COMBINATION_DOMAIN);
+        expected_input.extend(24u64.to_be_bytes());
+        expected_input.extend(1u32.to_be_bytes());
+        for entropy in [[0x00; 3], [0xff; 3]] {
+            expected_input.extend(3u64.to_be_bytes());
+            expected_input.extend(entropy);
+        }
+        assert_eq!(
+            *test.get_entropy_bytes(),
+            digest(&SHA256, &expected_input).as_ref()[..3].to_vec()
+        );
+        let reversed =
+            CombinedEntropy::combine(&[&second, &first], CombinationMethod::Sha256).unwrap();
+        assert_ne!(test.get_entropy_bytes(), reversed.get_entropy_bytes());
+    }
+
+    #[test]
+    fn combine_emits_error_for_incompatible_sessions_01() {
+        let first = ready_session(vec![1; 8], 3);
+        let second = ready_session(vec![8; 16], 6);
+        let unfinished = ready_session(vec![8; 7], 3);
+        assert_eq!(
+            CombinedEntropy::combine(&[&first], CombinationMethod::Xor)
+                .err()
+                .unwrap(),
+            ProcessError::NotEnoughSessionsToCombine { given: 1 }
+        );
+        assert_eq!(
+            CombinedEntropy::combine(&[&first, &second], CombinationMethod::Xor)
+                .err()
+                .unwrap(),
+            ProcessError::SessionToCombineDoesNotMatch {
+                index: 1,
+                requirement: "a target of 24 bits".to_string()
+            }
+        );
+        assert_eq!(
+            CombinedEntropy::combine(&[&first, &unfinished], CombinationMethod::Xor)
+                .err()
+                .unwrap(),
+            ProcessError::SessionToCombineDoesNotMatch {
+                index: 1,
+                requirement: "entropy that is ready".to_string()
+            }
+        );
+        let mut lsb_first_input = TychentropyNewInput::new(8, 3).unwrap();
+        lsb_first_input.set_bit_order(BitOrder::LsbFirst);
+        let mut lsb_first = Tychentropy::new(lsb_first_input);
+        lsb_first.add_rolls(vec![8; 8]).unwrap();
+        let test = CombinedEntropy::combine(&[&first, &lsb_first], CombinationMethod::Xor)
+            .err()
+            .unwrap();
+        assert_eq!(
+            test,
+            ProcessError::SessionToCombineDoesNotMatch {
+                index: 1,
+                requirement: "bytes packed most significant bit first".to_string()
+            }
+        );
+        assert_eq!(
+            test.to_string(),
+            "Session 1 cannot be combined, as combined sessions need bytes packed most \
+             significant bit first."
+        );
+        let copy = ready_session(vec![1; 8], 3);
+        assert_eq!(
+            CombinedEntropy::combine(&[&first, &copy], CombinationMethod::Xor)
+                .err()
+                .unwrap(),
+            ProcessError::SessionsToCombineAreRepeated {
+                first_index: 0,
+                second_index: 1
+            }
+        );
+    }
+
+    #[test]
+    fn get_mnemonic_works_01() {
+        let first = ready_session((0..43).map(|index| index % 8 + 1).collect(), 16);
+        let second = ready_session((0..43).map(|index| 8 - index % 8).collect(), 16);
+        let test = CombinedEntropy::combine(&[&first, &second], CombinationMethod::Xor).unwrap();
+        let mnemonic = test.get_mnemonic().unwrap();
+        assert_eq!(mnemonic.word_count(), 12);
+        assert_eq!(mnemonic.to_entropy(), *test.get_entropy_bytes());
+        let short = CombinedEntropy::combine(
+            &[&ready_session(vec![1; 8], 3), &ready_session(vec![8; 8], 3)],
+            CombinationMethod::Xor,
+        )
+        .unwrap();
+        assert!(short.get_mnemonic().is_none());
+    }
+}
@@ -0,0 +1,237 @@
+use getset::Getters;
+use ring::{
+    digest::{digest, Algorithm, SHA256, SHA512},
+    hmac,
+};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::domain::process::error::ProcessError;
+
+/// Label that keeps conditioned outputs apart from any other use of the same hash function.
+pub const CONDITIONING_DOMAIN: &str = "tychentropy/conditioning/v1";
+
+/// Vetted function that compresses the whole roll sequence into the entropy.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, Default, PartialEq, Eq)]
+pub enum ConditioningFunction {
+    #[default]
+    Sha256,
+    Sha512,
+    /// HMAC-SHA-512 keyed with the domain label.
+    HmacSha512,
+}
+
+impl ConditioningFunction {
+    /// Length of the function's output, the most entropy it can give.
+    pub fn get_output_bits(&self) -> u64 {
+        match self {
+            ConditioningFunction::Sha256 => 256,
+            ConditioningFunction::Sha512 | ConditioningFunction::HmacSha512 => 512,
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            ConditioningFunction::Sha256 => "SHA-256".to_string(),
+            ConditioningFunction::Sha512 => "SHA-512".to_string(),
+            ConditioningFunction::HmacSha512 => "HMAC-SHA-512".to_string(),
+        }
+    }
+}
+
+/// Optional step that credits every roll, rejected ones included, by hashing the whole sequence
+/// once it holds `safety_factor` times the target entropy.
+///
+/// Each roll of a fair die with `range` faces is credited with `log2(range)` bits of
+/// min-entropy. The safety factor covers dice that are less fair than that.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct Conditioning {
+    function: ConditioningFunction,
+    safety_factor: f64,
+}
+
+impl Default for Conditioning {
+    fn default() -> Self {
+        Conditioning {
+            function: ConditioningFunction::default(),
+            safety_factor: 2.0,
+        }
+    }
+}
+
+impl Conditioning {
+    pub fn new(function: ConditioningFunction, safety_factor: f64) -> Result<Self, ProcessError> {
+        if safety_factor.is_nan() || safety_factor < 1.0 {
+            return Err(ProcessError::ConditioningSafetyFactorIsLessThanOne);
+        }
+        Ok(Conditioning {
+            function,
+            safety_factor,
+        })
+    }
+
+    /// Checks that the function can give `target_entropy_bits`.
+    pub fn check_target_bits(&self, target_entropy_bits: u64) -> Result<(), ProcessError> {
+        let output_bits = self.function.get_output_bits();
+        if target_entropy_bits > output_bits {
+            Err(ProcessError::ConditioningOutputIsShorterThanTarget {
+                output_bits,
+                target_bits: target_entropy_bits,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Entropy that must be credited to the rolls before they are conditioned.
+    pub fn get_required_entropy_bits(&self, target_entropy_bits: u64) -> f64 {
+        target_entropy_bits as f64 * self.safety_factor
+    }
+
+    /// Conditions the rolls, given as values from 1 to their range, into at least
+    /// `target_entropy_bits` bits, packed most significant bit first.
+    ///
+    /// The input is the domain label, the target and each roll's range and zero indexed value,
+    /// all numbers as 8 byte big endian integers, so two different sessions never hash the same
+    /// bytes. For plain hashes the label is prefixed with its length; HMAC uses it as the key.
+    pub fn condition(&self, sequence: &[u64], ranges: &[u64], target_entropy_bits: u64) -> Vec<u8> {
+        // The buffers are sized up front so that no copy of the rolls is left behind when they
+        // grow, and are zeroized once hashed.
+        let mut message = Vec::with_capacity(8 + 16 * sequence.len());
+        message.extend(target_entropy_bits.to_be_bytes());
+        sequence
+            .iter()
+            .zip(ranges.iter())
+            .for_each(|(value, range)| {
+                message.extend(range.to_be_bytes());
+                message.extend((value - 1).to_be_bytes());
+            });
+        let mut output = match self.function {
+            ConditioningFunction::Sha256 => digest_domain_separated(&SHA256, &message),
+            ConditioningFunction::Sha512 => digest_domain_separated(&SHA512, &message),
+            ConditioningFunction::HmacSha512 => {
+                let key = hmac::Key::new(hmac::HMAC_SHA512, CONDITIONING_DOMAIN.as_bytes());
+                hmac::sign(&key, &message).as_ref().to_vec()
+            }
+        };
+        message.zeroize();
+        let output_len = target_entropy_bits.div_ceil(8) as usize;
+        if output.len() > output_len {
+            output[output_len..].zeroize();
+        }
+        output.truncate(output_len);
+        output
+    }
+}
+
+fn digest_domain_separated(algorithm: &'static Algorithm, message: &[u8]) -> Vec<u8> {
+    let mut input = Vec::with_capacity(8 + CONDITIONING_DOMAIN.len() + message.len());
+    input.extend((CONDITIONING_DOMAIN.len() as u64).to_be_bytes());
+    input.extend(CONDITIONING_DOMAIN.as_bytes());
+    input.extend(message);
+    let output = digest(algorithm, &input).as_ref().to_vec();
+    input.zeroize();
+    output
+}
+
+/// What was conditioned, kept by the session once its entropy is made.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct ConditioningRecord {
+    function: ConditioningFunction,
+    safety_factor: f64,
+    domain: String,
+    /// Number of rolls that went into the conditioning function.
+    conditioned_data: u64,
+    /// Min-entropy credited to those rolls.
+    credited_entropy_bits: f64,
+}
+
+impl ConditioningRecord {
+    pub fn new(
+        conditioning: &Conditioning,
+        conditioned_data: u64,
+        credited_entropy_bits: f64,
+    ) -> Self {
+        ConditioningRecord {
+            function: conditioning.function.clone(),
+            safety_factor: conditioning.safety_factor,
+            domain: CONDITIONING_DOMAIN.to_string(),
+            conditioned_data,
+            credited_entropy_bits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new_emits_error_for_small_safety_factor_01() {
+        let test = Conditioning::new(ConditioningFunction::Sha256, 0.5);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::ConditioningSafetyFactorIsLessThanOne
+        );
+        assert!(Conditioning::new(ConditioningFunction::Sha256, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn check_target_bits_works_01() {
+        let conditioning = Conditioning::new(ConditioningFunction::Sha256, 2.0).unwrap();
+        assert!(conditioning.check_target_bits(256).is_ok());
+        assert_eq!(
+            conditioning.check_target_bits(264).err().unwrap(),
+            ProcessError::ConditioningOutputIsShorterThanTarget {
+                output_bits: 256,
+                target_bits: 264
+            }
+        );
+        let conditioning = Conditioning::new(ConditioningFunction::HmacSha512, 2.0).unwrap();
+        assert!(conditioning.check_target_bits(512).is_ok());
+    }
+
+    #[test]
+    fn condition_works_01() {
+        let conditioning = Conditioning::new(ConditioningFunction::Sha256, 2.0).unwrap();
+        let output = conditioning.condition(&[1, 4, 6], &[6, 6, 6], 8);
+        let mut expected_input = (CONDITIONING_DOMAIN.len() as u64).to_be_bytes().to_vec();
+        expected_input.extend(b"tychentropy/conditioning/v1");
+        expected_input.extend(8u64.to_be_bytes());
+        for value in [0u64, 3, 5] {
+            expected_input.extend(6u64.to_be_bytes());
+            expected_input.extend(value.to_be_bytes());
+        }
+        assert_eq!(
+            output,
+            digest(&SHA256, &expected_input).as_ref()[..1].to_vec()
+        );
+    }
+
+    #[test]
+    fn condition_separates_functions_and_targets_01() {
+        let sequence = vec![2, 3, 5, 1];
+        let ranges = vec![6; 4];
+        let outputs = [
+            ConditioningFunction::Sha256,
+            ConditioningFunction::Sha512,
+            ConditioningFunction::HmacSha512,
+        ]
+        .iter()
+        .map(|function| {
+            Conditioning::new(function.clone(), 1.0)
+                .unwrap()
+                .condition(&sequence, &ranges, 128)
+        })
+        .collect::<Vec<Vec<u8>>>();
+        assert!(outputs.iter().all(|output| output.len() == 16));
+        assert_ne!(outputs[0], outputs[1]);
+        assert_ne!(outputs[1], outputs[2]);
+        let conditioning = Conditioning::new(ConditioningFunction::Sha512, 1.0).unwrap();
+        assert_ne!(
+            conditioning.condition(&sequence, &ranges, 256)[..16],
+            outputs[1][..]
+        );
+    }
+}
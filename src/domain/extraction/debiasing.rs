@@ -0,0 +1,202 @@
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Number of data the iterated Peres extractor works on at once. Blocks are independent, so
+/// the bits of one block are unbiased no matter how many blocks the session ends up needing.
+pub const PERES_BLOCK_LEN: usize = 16;
+
+/// Von Neumann pair extraction. Two data `a` and `b` from the same source give one bit, `1`
+/// when `a > b` and `0` when `a < b`, and nothing when they are equal. Both orders are equally
+/// likely for any fixed bias, so the bit is unbiased. For coin flips this is the classic
+/// `10 -> 1`, `01 -> 0` rule. Data are only paired with data of the same range.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, Default, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct VonNeumannState {
+    pending_data: Vec<PendingDatum>,
+}
+
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct PendingDatum {
+    range: u64,
+    value: u64,
+}
+
+impl VonNeumannState {
+    pub fn new() -> Self {
+        VonNeumannState::default()
+    }
+
+    /// Absorbs a zero indexed datum and returns the productive pair and its bit, if any.
+    pub fn absorb(&mut self, zero_indexed_datum_value: u64, range: u64) -> Option<([u64; 2], u8)> {
+        match self
+            .pending_data
+            .iter()
+            .position(|pending| pending.range == range)
+        {
+            None => {
+                self.pending_data.push(PendingDatum {
+                    range,
+                    value: zero_indexed_datum_value,
+                });
+                None
+            }
+            Some(index) => {
+                let first = self.pending_data.remove(index).value;
+                if first == zero_indexed_datum_value {
+                    None
+                } else {
+                    Some((
+                        [first, zero_indexed_datum_value],
+                        (first > zero_indexed_datum_value) as u8,
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Iterated Peres extraction over blocks of [`PERES_BLOCK_LEN`] data of the same range.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, Default, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct PeresState {
+    blocks: Vec<PeresBlock>,
+}
+
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct PeresBlock {
+    range: u64,
+    data: Vec<u64>,
+}
+
+impl PeresState {
+    pub fn new() -> Self {
+        PeresState::default()
+    }
+
+    /// Absorbs a zero indexed datum. Once a block is complete, returns the block and its bits.
+    pub fn absorb(
+        &mut self,
+        zero_indexed_datum_value: u64,
+        range: u64,
+    ) -> Option<(Vec<u64>, Vec<u8>)> {
+        let index = match self.blocks.iter().position(|block| block.range == range) {
+            Some(index) => index,
+            None => {
+                self.blocks.push(PeresBlock {
+                    range,
+                    data: vec![],
+                });
+                self.blocks.len() - 1
+            }
+        };
+        self.blocks[index].data.push(zero_indexed_datum_value);
+        if self.blocks[index].data.len() < PERES_BLOCK_LEN {
+            None
+        } else {
+            let block = std::mem::take(&mut self.blocks[index].data);
+            let bits = peres_extract(&block);
+            Some((block, bits))
+        }
+    }
+}
+
+/// The iterated Peres extractor. Emits the Von Neumann bits of the pairs, then recurses on the
+/// sequence telling which pairs were equal and on the values of the equal pairs. Works for any
+/// alphabet, with binary input giving Peres' original construction.
+pub fn peres_extract(data: &[u64]) -> Vec<u8> {
+    if data.len() < 2 {
+        return vec![];
+    }
+    let mut bits = vec![];
+    let mut equality_indicators = vec![];
+    let mut equal_pair_values = vec![];
+    data.chunks_exact(2).for_each(|pair| {
+        if pair[0] == pair[1] {
+            equality_indicators.push(1);
+            equal_pair_values.push(pair[0]);
+        } else {
+            equality_indicators.push(0);
+            bits.push((pair[0] > pair[1]) as u8);
+        }
+    });
+    bits.extend(peres_extract(&equality_indicators));
+    bits.extend(peres_extract(&equal_pair_values));
+    bits
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn von_neumann_absorb_works_01() {
+        let mut state = VonNeumannState::new();
+        assert_eq!(state.absorb(1, 2), None);
+        assert_eq!(state.absorb(0, 2), Some(([1, 0], 1)));
+        assert_eq!(state.absorb(0, 2), None);
+        assert_eq!(state.absorb(1, 2), Some(([0, 1], 0)));
+        assert_eq!(state.absorb(1, 2), None);
+        assert_eq!(state.absorb(1, 2), None);
+        assert!(state.pending_data.is_empty());
+    }
+
+    #[test]
+    fn von_neumann_absorb_pairs_same_range_only_01() {
+        let mut state = VonNeumannState::new();
+        assert_eq!(state.absorb(5, 6), None);
+        assert_eq!(state.absorb(19, 20), None);
+        assert_eq!(state.absorb(2, 6), Some(([5, 2], 1)));
+        assert_eq!(state.absorb(0, 20), Some(([19, 0], 1)));
+        assert!(state.pending_data.is_empty());
+    }
+
+    #[test]
+    fn peres_extract_works_01() {
+        // Pairs 10, 11, 01, 00: Von Neumann gives 1, 0. Equality indicators 0, 1, 0, 1 give 0, 0
+        // and then nothing. Equal pair values 1, 0 give 1.
+        let result = peres_extract(&[1, 0, 1, 1, 0, 1, 0, 0]);
+        assert_eq!(result, vec![1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn peres_extract_is_unbiased_01() {
+        // For a biased coin the chance of a block only depends on its number of ones, so within
+        // every weight class each output of a given length must appear equally often.
+        let block_len = 8;
+        let mut counts: HashMap<(u32, Vec<u8>), u64> = HashMap::new();
+        for block in 0u64..(1 << block_len) {
+            let data = (0..block_len)
+                .map(|index| (block >> index) & 1)
+                .collect::<Vec<u64>>();
+            let bits = peres_extract(&data);
+            *counts.entry((block.count_ones(), bits)).or_default() += 1;
+        }
+        for ((weight, bits), count) in counts.iter() {
+            for other in 0u64..(1 << bits.len()) {
+                let other_bits = (0..bits.len())
+                    .rev()
+                    .map(|index| ((other >> index) & 1) as u8)
+                    .collect::<Vec<u8>>();
+                assert_eq!(counts.get(&(*weight, other_bits)), Some(count));
+            }
+        }
+    }
+
+    #[test]
+    fn peres_absorb_works_01() {
+        let mut state = PeresState::new();
+        for _ in 0..PERES_BLOCK_LEN - 1 {
+            assert_eq!(state.absorb(1, 2), None);
+            assert_eq!(state.absorb(3, 4), None);
+        }
+        let (block, bits) = state.absorb(0, 2).unwrap();
+        assert_eq!(block.len(), PERES_BLOCK_LEN);
+        assert_eq!(bits, peres_extract(&block));
+        assert!(state.blocks[0].data.is_empty());
+        assert_eq!(state.blocks[1].data.len(), PERES_BLOCK_LEN - 1);
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Write;
+
+use ring::digest::{digest, SHA256};
+use zeroize::Zeroize;
+
+/// Least number of d6 rolls a Coldcard accepts for a 24 word seed without warning. 99 rolls
+/// carry about 256 bits of entropy.
+pub const COLDCARD_MIN_ROLLS: u64 = 99;
+
+/// Exact number of d6 rolls a SeedSigner takes for a 12 word seed. Only the first 16 bytes of
+/// the hash are used.
+pub const SEEDSIGNER_12_WORD_ROLLS: u64 = 50;
+/// Exact number of d6 rolls a SeedSigner takes for a 24 word seed.
+pub const SEEDSIGNER_24_WORD_ROLLS: u64 = 99;
+
+/// SHA-256 of the rolls written as one ASCII string of decimal digits, such as `"14623"` for
+/// the rolls 1, 4, 6, 2 and 3. This is how hardware wallets such as the Coldcard turn d6 rolls
+/// into a seed. The digit string is zeroized once hashed.
+pub fn sha256_of_roll_digits(rolls: &[u64]) -> Vec<u8> {
+    let mut roll_digits = String::with_capacity(rolls.len());
+    for roll in rolls {
+        write!(roll_digits, "{}", roll).unwrap();
+    }
+    let hash = digest(&SHA256, roll_digits.as_bytes()).as_ref().to_vec();
+    roll_digits.zeroize();
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    // These digests were computed with Python's hashlib from the same digit strings, so they
+    // only check the encoding against another SHA-256 implementation. Vectors from the
+    // Coldcard firmware tests could not be fetched when these were written and still have to
+    // be added here.
+    #[test]
+    fn sha256_of_roll_digits_works_01() {
+        assert_eq!(
+            to_hex(&sha256_of_roll_digits(&[1, 2, 3])),
+            "a665a45920422f9d417e4867efdc4fb8a04a1f3fff1fa07e998e86f7f7a27ae3"
+        );
+    }
+
+    #[test]
+    fn sha256_of_roll_digits_works_02() {
+        let rolls = (0..99).map(|index| index % 6 + 1).collect::<Vec<u64>>();
+        assert_eq!(
+            to_hex(&sha256_of_roll_digits(&rolls)),
+            "5588d3630bd19f6375b7bd922457af34ea9c74f00807566a1cf808e445dc8c20"
+        );
+        assert_eq!(
+            to_hex(&sha256_of_roll_digits(&[1; 99])),
+            "fa098eb852b2660348b21bb00ad03a49cc177ea07ebe34f46b40baa85313525e"
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Raw data consumed by the extraction method to emit a group of entropy bits. Data that were
+/// discarded before the emission are counted as part of its cost. Each digit of the Lehmer code
+/// of a deck counts as one datum.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct ExtractionCost {
+    consumed_data: u64,
+    emitted_bits: u64,
+}
+
+impl ExtractionCost {
+    pub fn new(consumed_data: u64, emitted_bits: u64) -> Self {
+        ExtractionCost {
+            consumed_data,
+            emitted_bits,
+        }
+    }
+}
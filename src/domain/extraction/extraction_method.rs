@@ -0,0 +1,66 @@
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// How natural data are turned into entropy bits.
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop, Default, PartialEq, Eq)]
+pub enum ExtractionMethod {
+    /// Keeps only the data below the largest power of two that fits in the range and uses
+    /// their binary representation. Simple to follow by hand, but a d6 wastes a third of its rolls.
+    #[default]
+    Rejection,
+    /// Treats the whole sequence as one mixed-radix number and carries the leftover fractional
+    /// entropy of each datum into the next one. Every datum contributes and the bits stay unbiased.
+    RangeCoding,
+    /// Von Neumann pair extraction. Removes any constant bias of the source at the cost of
+    /// many discarded data. Meant for coins and other sources with a small range.
+    VonNeumann,
+    /// Iterated Peres extraction over fixed blocks. As unbiased as [`ExtractionMethod::VonNeumann`]
+    /// but recovers much of the entropy that pair extraction throws away.
+    Peres,
+    /// SHA-256 of the ASCII digits of all d6 rolls, byte for byte what a Coldcard makes from
+    /// the same rolls. No bits come out while rolls are added; the entropy is made by
+    /// [`crate::Tychentropy::finalize_hashed_entropy`] once at least 99 rolls are in. Needs a
+    /// d6 session with a 32 byte target.
+    Coldcard,
+    /// SHA-256 of the ASCII digits of exactly 50 or 99 d6 rolls, as a SeedSigner makes a 12 or
+    /// 24 word seed. For 12 words only the first 16 bytes of the hash are kept. The entropy is
+    /// made as soon as the last roll is added. Needs a d6 session with a 16 or 32 byte target.
+    SeedSigner,
+    /// The digits typed into the Ian Coleman BIP39 tool read as one number in the base the
+    /// tool guesses, with the "Use Raw Entropy" option. Faces must be labelled with single
+    /// digits, such as d6 rolls or d10 rolls labelled from 0. The entropy is made by
+    /// [`crate::Tychentropy::finalize_hashed_entropy`] and must come to exactly the target,
+    /// which is 128 to 256 bits in steps of 32. The tool's card entropy is not reproduced, so
+    /// deck permutations are rejected in this mode.
+    IanColemanRaw,
+    /// SHA-256 of the digits typed into the Ian Coleman BIP39 tool, truncated to the target,
+    /// as the tool does when a mnemonic length is selected. Same faces and targets as
+    /// [`ExtractionMethod::IanColemanRaw`].
+    IanColemanHashed,
+}
+
+impl ExtractionMethod {
+    /// Whether the entropy is made from all the data at once, by hashing or by base conversion,
+    /// instead of bit by bit as data are added.
+    pub fn is_hashed(&self) -> bool {
+        matches!(
+            self,
+            ExtractionMethod::Coldcard
+                | ExtractionMethod::SeedSigner
+                | ExtractionMethod::IanColemanRaw
+                | ExtractionMethod::IanColemanHashed
+        )
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            ExtractionMethod::Rejection => "rejection".to_string(),
+            ExtractionMethod::RangeCoding => "range coding".to_string(),
+            ExtractionMethod::VonNeumann => "Von Neumann".to_string(),
+            ExtractionMethod::Peres => "Peres".to_string(),
+            ExtractionMethod::Coldcard => "Coldcard".to_string(),
+            ExtractionMethod::SeedSigner => "SeedSigner".to_string(),
+            ExtractionMethod::IanColemanRaw => "Ian Coleman raw".to_string(),
+            ExtractionMethod::IanColemanHashed => "Ian Coleman hashed".to_string(),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::utils::domain_utils::u128_to_bit_vec;
+
+use super::{
+    debiasing::{PeresState, VonNeumannState},
+    extraction_method::ExtractionMethod,
+    extractor::Extractor,
+    range_coding::RangeCodingState,
+};
+
+/// Outcome of feeding one zero indexed datum into an extraction state.
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop, Default, PartialEq, Eq)]
+pub struct ExtractionStep {
+    /// Data that were used to generate entropy in this step.
+    pub entropy_generating_data: Vec<u64>,
+    /// Entropy bits extracted in this step, most significant bit first.
+    pub bits: Vec<u8>,
+}
+
+/// Running state of the selected extraction method. Data of different ranges may be absorbed
+/// into the same state; methods that compare data only compare data of the same range.
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop, Default)]
+pub enum ExtractionState {
+    #[default]
+    Rejection,
+    RangeCoding(RangeCodingState),
+    VonNeumann(VonNeumannState),
+    Peres(PeresState),
+    /// Keeps nothing itself. Hashed methods, and other methods that make the entropy at once,
+    /// work on the whole sequence of the session.
+    Hashed,
+}
+
+impl From<&ExtractionMethod> for ExtractionState {
+    fn from(value: &ExtractionMethod) -> Self {
+        match value {
+            ExtractionMethod::Rejection => ExtractionState::Rejection,
+            ExtractionMethod::RangeCoding => ExtractionState::RangeCoding(RangeCodingState::new()),
+            ExtractionMethod::VonNeumann => ExtractionState::VonNeumann(VonNeumannState::new()),
+            ExtractionMethod::Peres => ExtractionState::Peres(PeresState::new()),
+            ExtractionMethod::Coldcard
+            | ExtractionMethod::SeedSigner
+            | ExtractionMethod::IanColemanRaw
+            | ExtractionMethod::IanColemanHashed => ExtractionState::Hashed,
+        }
+    }
+}
+
+impl Extractor for ExtractionState {
+    /// At most `remaining_bits` are returned by methods that can hold entropy back.
+    fn absorb(
+        &mut self,
+        zero_indexed_datum_value: u64,
+        range: u64,
+        remaining_bits: u64,
+    ) -> ExtractionStep {
+        match self {
+            ExtractionState::Rejection => {
+                let full_bits_in_each_datum = range.ilog2();
+                let cutoff_value = 2u64.pow(full_bits_in_each_datum);
+                if zero_indexed_datum_value < cutoff_value {
+                    ExtractionStep {
+                        entropy_generating_data: vec![zero_indexed_datum_value],
+                        bits: u128_to_bit_vec(
+                            zero_indexed_datum_value as u128,
+                            full_bits_in_each_datum,
+                        ),
+                    }
+                } else {
+                    ExtractionStep::default()
+                }
+            }
+            ExtractionState::RangeCoding(state) => ExtractionStep {
+                entropy_generating_data: vec![zero_indexed_datum_value],
+                bits: state.absorb(zero_indexed_datum_value, range, remaining_bits),
+            },
+            ExtractionState::VonNeumann(state) => {
+                match state.absorb(zero_indexed_datum_value, range) {
+                    Some((pair, bit)) => ExtractionStep {
+                        entropy_generating_data: pair.to_vec(),
+                        bits: vec![bit],
+                    },
+                    None => ExtractionStep::default(),
+                }
+            }
+            ExtractionState::Peres(state) => match state.absorb(zero_indexed_datum_value, range) {
+                Some((block, bits)) if !bits.is_empty() => ExtractionStep {
+                    entropy_generating_data: block,
+                    bits,
+                },
+                _ => ExtractionStep::default(),
+            },
+            ExtractionState::Hashed => ExtractionStep {
+                entropy_generating_data: vec![zero_indexed_datum_value],
+                bits: vec![],
+            },
+        }
+    }
+
+    fn get_name(&self) -> String {
+        match self {
+            ExtractionState::Rejection => ExtractionMethod::Rejection.get_name(),
+            ExtractionState::RangeCoding(_) => ExtractionMethod::RangeCoding.get_name(),
+            ExtractionState::VonNeumann(_) => ExtractionMethod::VonNeumann.get_name(),
+            ExtractionState::Peres(_) => ExtractionMethod::Peres.get_name(),
+            ExtractionState::Hashed => "hashed".to_string(),
+        }
+    }
+
+    fn splits_datum_groups(&self) -> bool {
+        matches!(self, ExtractionState::Rejection)
+    }
+
+    fn new_empty(&self) -> Box<dyn Extractor> {
+        Box::new(match self {
+            ExtractionState::Rejection => ExtractionState::Rejection,
+            ExtractionState::RangeCoding(_) => {
+                ExtractionState::RangeCoding(RangeCodingState::new())
+            }
+            ExtractionState::VonNeumann(_) => ExtractionState::VonNeumann(VonNeumannState::new()),
+            ExtractionState::Peres(_) => ExtractionState::Peres(PeresState::new()),
+            ExtractionState::Hashed => ExtractionState::Hashed,
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn Extractor> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rejection_absorb_works_01() {
+        let mut state = ExtractionState::from(&ExtractionMethod::Rejection);
+        let step = state.absorb(3, 6, 8);
+        assert_eq!(step.entropy_generating_data, vec![3]);
+        assert_eq!(step.bits, vec![1, 1]);
+    }
+
+    #[test]
+    fn rejection_absorb_discards_datum_above_cutoff_01() {
+        let mut state = ExtractionState::from(&ExtractionMethod::Rejection);
+        let step = state.absorb(4, 6, 8);
+        assert_eq!(step, ExtractionStep::default());
+    }
+
+    #[test]
+    fn range_coding_absorb_uses_every_datum_01() {
+        let mut state = ExtractionState::from(&ExtractionMethod::RangeCoding);
+        let step = state.absorb(5, 6, 2);
+        assert_eq!(step.entropy_generating_data, vec![5]);
+    }
+
+    #[test]
+    fn von_neumann_absorb_works_01() {
+        let mut state = ExtractionState::from(&ExtractionMethod::VonNeumann);
+        assert_eq!(state.absorb(0, 2, 8), ExtractionStep::default());
+        let step = state.absorb(1, 2, 8);
+        assert_eq!(step.entropy_generating_data, vec![0, 1]);
+        assert_eq!(step.bits, vec![0]);
+    }
+}
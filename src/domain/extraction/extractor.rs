@@ -0,0 +1,80 @@
+use std::fmt::Debug;
+
+use zeroize::Zeroize;
+
+use super::extraction_state::{ExtractionState, ExtractionStep};
+
+/// Turns natural data into entropy bits, one datum at a time. The session keeps all the
+/// bookkeeping, such as the generated bits and whether the entropy is ready, from the steps
+/// the extractor returns.
+///
+/// The built in methods are implemented by [`ExtractionState`], with rejection as the default.
+/// Any other extractor can be given to a session with
+/// [`crate::domain::process::input::TychentropyNewInput::set_extractor`]. Extractors are `Send`
+/// and `Sync` so that a session can be moved to, or shared with, another thread.
+pub trait Extractor: Debug + Zeroize + Send + Sync {
+    /// Feeds a zero indexed datum drawn uniformly from `0..range` and returns the bits it made
+    /// available, most significant bit first. No more than `remaining_bits` should be returned.
+    fn absorb(
+        &mut self,
+        zero_indexed_datum_value: u64,
+        range: u64,
+        remaining_bits: u64,
+    ) -> ExtractionStep;
+
+    fn get_name(&self) -> String;
+
+    /// Whether the faces of a group of data rolled together are fed one at a time rather than
+    /// as one combined datum. Methods that throw whole data away, such as rejection, would
+    /// waste every face of a group they reject.
+    fn splits_datum_groups(&self) -> bool {
+        false
+    }
+
+    /// An extractor with the same settings that has absorbed nothing yet. Sessions use it when
+    /// they are rebuilt from their history.
+    fn new_empty(&self) -> Box<dyn Extractor>;
+
+    fn clone_box(&self) -> Box<dyn Extractor>;
+}
+
+impl Clone for Box<dyn Extractor> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl Zeroize for Box<dyn Extractor> {
+    fn zeroize(&mut self) {
+        (**self).zeroize()
+    }
+}
+
+impl Default for Box<dyn Extractor> {
+    fn default() -> Self {
+        Box::new(ExtractionState::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::extraction::ExtractionMethod;
+
+    #[test]
+    fn default_extractor_is_rejection_01() {
+        let mut extractor = Box::<dyn Extractor>::default();
+        assert_eq!(extractor.get_name(), ExtractionMethod::Rejection.get_name());
+        assert_eq!(extractor.absorb(2, 6, 8).bits, vec![1, 0]);
+    }
+
+    #[test]
+    fn new_empty_forgets_absorbed_data_01() {
+        let mut extractor: Box<dyn Extractor> =
+            Box::new(ExtractionState::from(&ExtractionMethod::VonNeumann));
+        extractor.absorb(0, 2, 8);
+        let mut empty = extractor.new_empty();
+        assert!(empty.absorb(1, 2, 8).bits.is_empty());
+        assert_eq!(extractor.absorb(1, 2, 8).bits, vec![0]);
+    }
+}
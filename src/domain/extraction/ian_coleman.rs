@@ -0,0 +1,170 @@
+use ring::digest::{digest, SHA256};
+
+/// Entropy typed into the Ian Coleman BIP39 tool, read the way the tool reads it.
+///
+/// The tool guesses the base from the characters used, trying binary (`0-1`), base 6 (`0-5`),
+/// dice (`1-6`, where a 6 counts as 0) and base 10 (`0-9`) in that order. A d6 sequence
+/// without a 6 is therefore read as base 6, and one with only 1s as binary, which is what
+/// the tool does as well.
+///
+/// Card entropy, which the tool reads in base 52 with its own correction for drawing without
+/// replacement, is out of scope. Only digit strings are read, and sessions using the tool's
+/// methods reject deck permutations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IanColemanEntropy {
+    base: u64,
+    digits: Vec<u64>,
+    clean_string: String,
+}
+
+impl IanColemanEntropy {
+    /// Reads a string of decimal digits. Returns `None` if any character is not a digit.
+    pub fn from_digit_string(text: &str) -> Option<Self> {
+        if !text.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let is_within = |first: char, last: char| text.chars().all(|c| (first..=last).contains(&c));
+        let (base, is_dice) = if is_within('0', '1') {
+            (2, false)
+        } else if is_within('0', '5') {
+            (6, false)
+        } else if is_within('1', '6') {
+            (6, true)
+        } else {
+            (10, false)
+        };
+        let digits = text
+            .chars()
+            .map(|c| {
+                let digit = c.to_digit(10).unwrap() as u64;
+                if is_dice {
+                    digit % 6
+                } else {
+                    digit
+                }
+            })
+            .collect();
+        Some(IanColemanEntropy {
+            base,
+            digits,
+            clean_string: text.to_string(),
+        })
+    }
+
+    pub fn get_base(&self) -> u64 {
+        self.base
+    }
+
+    /// Bits the tool credits the entropy with, `floor(digits * log2(base))`.
+    pub fn get_expected_bits(&self) -> u64 {
+        (self.digits.len() as f64 * (self.base as f64).log2()).floor() as u64
+    }
+
+    /// Binary form of the digits read as one number, most significant bit first, padded with
+    /// leading zeros to the expected bits. It can be longer than the expected bits.
+    pub fn get_raw_bits(&self) -> Vec<u8> {
+        let mut bits = digits_to_bits(&self.digits, self.base);
+        let expected_bits = self.get_expected_bits() as usize;
+        if bits.len() < expected_bits {
+            let mut padded_bits = vec![0; expected_bits - bits.len()];
+            padded_bits.append(&mut bits);
+            bits = padded_bits;
+        }
+        bits
+    }
+
+    /// What the tool gives with "Use Raw Entropy": the last multiple of 32 raw bits, most
+    /// significant bit first. Leading bits that do not fill 32 are dropped.
+    pub fn get_raw_mnemonic_bits(&self) -> Vec<u8> {
+        let raw_bits = self.get_raw_bits();
+        let bits_to_use = raw_bits.len() / 32 * 32;
+        raw_bits[raw_bits.len() - bits_to_use..].to_vec()
+    }
+
+    /// What the tool gives with a mnemonic length selected: the first `bit_len` bits of the
+    /// SHA-256 of the typed digits, packed most significant bit first.
+    pub fn get_hashed_bytes(&self, bit_len: u64) -> Vec<u8> {
+        let hash = digest(&SHA256, self.clean_string.as_bytes());
+        hash.as_ref()[..(bit_len.div_ceil(8) as usize).min(32)].to_vec()
+    }
+}
+
+/// Digits of a number in `base`, most significant first, as a bit vector with no leading
+/// zeros. Zero is a single `0` bit.
+pub fn digits_to_bits(digits: &[u64], base: u64) -> Vec<u8> {
+    // Little endian 32 bit limbs, so each step fits a u64.
+    let mut limbs: Vec<u64> = vec![0];
+    for digit in digits {
+        let mut carry = *digit;
+        for limb in limbs.iter_mut() {
+            let value = *limb * base + carry;
+            *limb = value & 0xffff_ffff;
+            carry = value >> 32;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+    let bits = limbs
+        .iter()
+        .rev()
+        .flat_map(|limb| (0..32).rev().map(move |index| ((limb >> index) & 1) as u8))
+        .skip_while(|bit| *bit == 0)
+        .collect::<Vec<u8>>();
+    if bits.is_empty() {
+        vec![0]
+    } else {
+        bits
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_bit_string(bits: &[u8]) -> String {
+        bits.iter().map(|bit| bit.to_string()).collect()
+    }
+
+    #[test]
+    fn from_digit_string_works_01() {
+        assert_eq!(
+            IanColemanEntropy::from_digit_string("1010").unwrap().base,
+            2
+        );
+        assert_eq!(
+            IanColemanEntropy::from_digit_string("0125").unwrap().base,
+            6
+        );
+        let dice = IanColemanEntropy::from_digit_string("1462").unwrap();
+        assert_eq!(dice.base, 6);
+        assert_eq!(dice.digits, vec![1, 4, 0, 2]);
+        assert_eq!(
+            IanColemanEntropy::from_digit_string("0962").unwrap().base,
+            10
+        );
+        assert!(IanColemanEntropy::from_digit_string("14a2").is_none());
+    }
+
+    #[test]
+    fn digits_to_bits_works_01() {
+        assert_eq!(
+            to_bit_string(&digits_to_bits(&[1, 4, 0, 2], 6)),
+            "101101010"
+        );
+        assert_eq!(to_bit_string(&digits_to_bits(&[0, 0], 6)), "0");
+        let digits = vec![5; 30];
+        // 6^30 - 1 has 78 bits.
+        assert_eq!(digits_to_bits(&digits, 6).len(), 78);
+        assert!(digits_to_bits(&digits, 6).iter().all(|bit| *bit <= 1));
+    }
+
+    #[test]
+    fn get_raw_bits_works_01() {
+        // 1, 4, 6, 2 is 1*216 + 4*36 + 0*6 + 2 = 362, and 4 digits of base 6 are worth 10 bits.
+        let test = IanColemanEntropy::from_digit_string("1462").unwrap();
+        assert_eq!(test.get_expected_bits(), 10);
+        assert_eq!(to_bit_string(&test.get_raw_bits()), "0101101010");
+        assert_eq!(test.get_raw_mnemonic_bits(), vec![]);
+    }
+}
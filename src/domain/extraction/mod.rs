@@ -0,0 +1,19 @@
+pub mod conditioning;
+pub mod debiasing;
+pub mod dice_hashing;
+pub mod extraction_cost;
+pub mod extraction_method;
+pub mod extraction_state;
+pub mod extractor;
+pub mod ian_coleman;
+pub mod range_coding;
+pub mod toeplitz;
+
+pub use conditioning::{Conditioning, ConditioningFunction, ConditioningRecord};
+pub use debiasing::{PeresState, VonNeumannState};
+pub use extraction_cost::ExtractionCost;
+pub use extraction_method::ExtractionMethod;
+pub use extraction_state::{ExtractionState, ExtractionStep};
+pub use extractor::Extractor;
+pub use range_coding::RangeCodingState;
+pub use toeplitz::{ToeplitzExtraction, ToeplitzExtractor};
@@ -0,0 +1,168 @@
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::utils::domain_utils::u128_to_bit_vec;
+
+/// Once the modulus grows past this limit, bits are emitted even if the target does not need
+/// them yet. It keeps `modulus * range` inside a `u128` for any `u64` range.
+const ACCUMULATION_LIMIT: u128 = 1 << 64;
+/// Bits kept back when emitting before the target is in reach. The chance of a rejected split
+/// is then at most 2^-16, so almost no entropy is lost on the way.
+const SLACK_BITS: u64 = 16;
+
+/// Incremental interval extraction over a mixed-radix number.
+///
+/// `value` is always uniformly distributed in `0..modulus`. Absorbing a datum from `0..range`
+/// multiplies the interval by `range`. Emitting `n` bits splits the interval into a part that
+/// is a multiple of `2^n`, which yields `n` exactly unbiased bits, and a remainder which is kept
+/// as a smaller interval instead of being thrown away.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct RangeCodingState {
+    value: u128,
+    modulus: u128,
+}
+
+impl Default for RangeCodingState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RangeCodingState {
+    pub fn new() -> Self {
+        RangeCodingState {
+            value: 0,
+            modulus: 1,
+        }
+    }
+
+    /// Absorbs a zero indexed datum from `0..range` and returns at most `remaining_bits` bits.
+    /// Nothing more should be absorbed once `remaining_bits` have been returned.
+    pub fn absorb(
+        &mut self,
+        zero_indexed_datum_value: u64,
+        range: u64,
+        remaining_bits: u64,
+    ) -> Vec<u8> {
+        self.value = self.value * range as u128 + zero_indexed_datum_value as u128;
+        self.modulus *= range as u128;
+        let mut bits = vec![];
+        let mut remaining_bits = remaining_bits;
+        while remaining_bits > 0 {
+            let available_bits = self.modulus.ilog2() as u64;
+            let chunk_len = if available_bits >= remaining_bits {
+                remaining_bits
+            } else if self.modulus >= ACCUMULATION_LIMIT {
+                available_bits - SLACK_BITS
+            } else {
+                break;
+            };
+            if chunk_len == 0 {
+                break;
+            }
+            if let Some(chunk) = self.split(chunk_len as u32) {
+                bits.extend(u128_to_bit_vec(chunk, chunk_len as u32));
+                remaining_bits -= chunk_len;
+            }
+        }
+        bits
+    }
+
+    /// Emits every whole bit left in the interval. Used when no more data will follow.
+    pub fn flush(&mut self) -> Vec<u8> {
+        let mut bits = vec![];
+        while self.modulus > 1 {
+            let chunk_len = self.modulus.ilog2();
+            if let Some(chunk) = self.split(chunk_len) {
+                bits.extend(u128_to_bit_vec(chunk, chunk_len));
+            }
+        }
+        bits
+    }
+
+    fn split(&mut self, chunk_len: u32) -> Option<u128> {
+        let cutoff = (self.modulus >> chunk_len) << chunk_len;
+        if self.value < cutoff {
+            let chunk = self.value & ((1u128 << chunk_len) - 1);
+            self.value >>= chunk_len;
+            self.modulus >>= chunk_len;
+            Some(chunk)
+        } else {
+            self.value -= cutoff;
+            self.modulus -= cutoff;
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Runs every equally likely sequence of `len` data through a fresh state and counts how
+    /// often each `target_bits` long output appears among the sequences that completed.
+    fn output_counts(range: u64, len: u32, target_bits: u64) -> Vec<u64> {
+        let mut counts = vec![0u64; 1 << target_bits];
+        for index in 0..range.pow(len) {
+            let mut state = RangeCodingState::new();
+            let mut bits = vec![];
+            let mut rest = index;
+            for _ in 0..len {
+                let datum = rest % range;
+                rest /= range;
+                bits.extend(state.absorb(datum, range, target_bits - bits.len() as u64));
+                if bits.len() as u64 == target_bits {
+                    let value = bits.iter().fold(0usize, |acc, bit| acc * 2 + *bit as usize);
+                    counts[value] += 1;
+                    break;
+                }
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn absorb_is_unbiased_01() {
+        let counts = output_counts(6, 6, 3);
+        assert!(counts.iter().all(|count| *count == counts[0] && *count > 0));
+    }
+
+    #[test]
+    fn absorb_is_unbiased_02() {
+        let counts = output_counts(3, 8, 4);
+        assert!(counts.iter().all(|count| *count == counts[0] && *count > 0));
+    }
+
+    #[test]
+    fn absorb_works_01() {
+        let mut state = RangeCodingState::new();
+        assert_eq!(state.absorb(5, 6, 8), vec![]);
+        assert_eq!(state.absorb(2, 6, 8), vec![]);
+        assert_eq!(state.absorb(3, 6, 8), vec![]);
+        assert_eq!(state.value, 195);
+        assert_eq!(state.modulus, 216);
+        // 195 * 6 + 1 = 1171 lies in the 0..1280 part of 0..1296, so 8 bits come out at once.
+        assert_eq!(state.absorb(1, 6, 8), vec![1, 0, 0, 1, 0, 0, 1, 1]);
+        assert_eq!(state.value, 4);
+        assert_eq!(state.modulus, 5);
+    }
+
+    #[test]
+    fn absorb_keeps_modulus_bounded_01() {
+        let mut state = RangeCodingState::new();
+        for _ in 0..1000 {
+            state.absorb(u64::MAX - 1, u64::MAX, u64::MAX);
+            assert!(state.modulus < ACCUMULATION_LIMIT);
+        }
+    }
+
+    #[test]
+    fn flush_works_01() {
+        let mut state = RangeCodingState::new();
+        state.absorb(1, 4, 8);
+        state.absorb(3, 4, 8);
+        assert_eq!(state.flush(), vec![0, 1, 1, 1]);
+        assert_eq!(state.modulus, 1);
+    }
+}
@@ -0,0 +1,176 @@
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::domain::process::error::ProcessError;
+
+/// Seeded extractor that multiplies the input bits by a Toeplitz matrix over GF(2).
+///
+/// Toeplitz matrices form a universal family of hash functions, so by the leftover hash lemma
+/// an input with `k` bits of min-entropy gives `m` output bits whose statistical distance from
+/// uniform is at most `2^-((k - m) / 2)`, whatever the bias of the source. The seed picks the
+/// matrix. It must be uniform and independent of the input, but it may be public.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct ToeplitzExtractor {
+    seed: Vec<u8>,
+    /// The output is shortened so that its distance from uniform is at most
+    /// `2^-security_parameter`.
+    security_parameter: u32,
+}
+
+/// Output of a Toeplitz extraction and the bound it comes with.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct ToeplitzExtraction {
+    /// Extracted bits, most significant bit first.
+    bits: Vec<u8>,
+    input_bits: u64,
+    min_entropy_bits: f64,
+    security_parameter: u32,
+    /// Seed bits used for the matrix, `input_bits + output_bits - 1`.
+    used_seed_bits: u64,
+    /// Leftover hash lemma bound on the statistical distance of `bits` from uniform.
+    statistical_distance_bound: f64,
+}
+
+impl ToeplitzExtractor {
+    pub fn new(seed: Vec<u8>, security_parameter: u32) -> Self {
+        ToeplitzExtractor {
+            seed,
+            security_parameter,
+        }
+    }
+
+    /// Number of bits that can be extracted, `floor(k - 2 * security_parameter)` for an input
+    /// of `input_bits` bits with `min_entropy_bits` bits of min-entropy.
+    pub fn get_output_len(
+        &self,
+        input_bits: u64,
+        min_entropy_bits: f64,
+    ) -> Result<u64, ProcessError> {
+        if min_entropy_bits.is_nan() || min_entropy_bits > input_bits as f64 {
+            return Err(ProcessError::MinEntropyExceedsInputLength {
+                min_entropy_bits: format!("{:.2}", min_entropy_bits),
+                input_bits,
+            });
+        }
+        let output_len = (min_entropy_bits - 2.0 * self.security_parameter as f64).floor();
+        if output_len < 1.0 {
+            Err(ProcessError::MinEntropyIsTooLowForExtraction {
+                min_entropy_bits: format!("{:.2}", min_entropy_bits),
+                security_parameter: self.security_parameter,
+            })
+        } else {
+            Ok(output_len as u64)
+        }
+    }
+
+    /// Extracts from `input_bits`, a vector of 0 and 1, credited with `min_entropy_bits` bits
+    /// of min-entropy in total.
+    pub fn extract(
+        &self,
+        input_bits: &[u8],
+        min_entropy_bits: f64,
+    ) -> Result<ToeplitzExtraction, ProcessError> {
+        let input_len = input_bits.len() as u64;
+        let output_len = self.get_output_len(input_len, min_entropy_bits)?;
+        let used_seed_bits = input_len + output_len - 1;
+        let seed_bits = self.seed.len() as u64 * 8;
+        if seed_bits < used_seed_bits {
+            return Err(ProcessError::ToeplitzSeedIsTooShort {
+                seed_bits,
+                required_seed_bits: used_seed_bits,
+            });
+        }
+        let seed_bit = |index: u64| (self.seed[(index / 8) as usize] >> (7 - index % 8)) & 1;
+        // Entry (i, j) of the matrix is seed bit i - j + input_len - 1, so each diagonal is
+        // constant.
+        let bits = (0..output_len)
+            .map(|row| {
+                input_bits
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, bit)| **bit == 1)
+                    .fold(0, |acc, (column, _)| {
+                        acc ^ seed_bit(row + input_len - 1 - column as u64)
+                    })
+            })
+            .collect();
+        let statistical_distance_bound = 2f64.powf(-(min_entropy_bits - output_len as f64) / 2.0);
+        Ok(ToeplitzExtraction {
+            bits,
+            input_bits: input_len,
+            min_entropy_bits,
+            security_parameter: self.security_parameter,
+            used_seed_bits,
+            statistical_distance_bound,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract_works_01() {
+        // Seed bits 1 0 1 1 0 give the rows 1 1 0 1 and 0 1 1 0.
+        let extractor = ToeplitzExtractor::new(vec![0b1011_0000], 1);
+        let test = extractor.extract(&[1, 0, 1, 1], 4.0).unwrap();
+        assert_eq!(test.bits, vec![0, 1]);
+        assert_eq!(test.used_seed_bits, 5);
+        assert!((test.statistical_distance_bound - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn extract_is_linear_01() {
+        let extractor = ToeplitzExtractor::new(vec![0x5a, 0xc3, 0x99, 0x0f, 0x71], 4);
+        let x = vec![1, 0, 0, 1, 1, 1, 0, 1, 0, 0, 1, 0, 1, 1, 0, 1, 0, 1, 1, 0];
+        let y = vec![0, 1, 1, 1, 0, 0, 0, 1, 1, 0, 1, 1, 0, 1, 1, 1, 0, 0, 1, 0];
+        let x_xor_y = x
+            .iter()
+            .zip(y.iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<u8>>();
+        let extract = |bits: &[u8]| extractor.extract(bits, 18.5).unwrap().bits.clone();
+        let expected = extract(&x)
+            .iter()
+            .zip(extract(&y).iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<u8>>();
+        assert_eq!(extract(&x).len(), 10);
+        assert_eq!(extract(&x_xor_y), expected);
+    }
+
+    #[test]
+    fn get_output_len_emits_error_for_low_min_entropy_01() {
+        let extractor = ToeplitzExtractor::new(vec![0; 64], 64);
+        assert_eq!(extractor.get_output_len(300, 200.9).unwrap(), 72);
+        assert_eq!(
+            extractor.get_output_len(300, 128.0).err().unwrap(),
+            ProcessError::MinEntropyIsTooLowForExtraction {
+                min_entropy_bits: "128.00".to_string(),
+                security_parameter: 64
+            }
+        );
+        assert_eq!(
+            extractor.get_output_len(100, 128.0).err().unwrap(),
+            ProcessError::MinEntropyExceedsInputLength {
+                min_entropy_bits: "128.00".to_string(),
+                input_bits: 100
+            }
+        );
+    }
+
+    #[test]
+    fn extract_emits_error_for_short_seed_01() {
+        let extractor = ToeplitzExtractor::new(vec![0; 2], 1);
+        assert_eq!(
+            extractor.extract(&[1; 12], 10.0).err().unwrap(),
+            ProcessError::ToeplitzSeedIsTooShort {
+                seed_bits: 16,
+                required_seed_bits: 19
+            }
+        );
+    }
+}
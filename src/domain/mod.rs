@@ -1,3 +1,5 @@
+pub mod combination;
+pub mod extraction;
 pub mod process;
 pub mod tychentropy;
 pub mod statistics;
@@ -0,0 +1,91 @@
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Order in which entropy bits are packed into bytes.
+///
+/// With `MsbFirst` the first bit of the entropy bit string is the most significant bit of the
+/// first byte, so the bytes read in binary are the bit string itself. This is what BIP-39 and
+/// common dice to seed guides expect. `LsbFirst` puts the first bit in the least significant
+/// position of each byte, which is how sessions packed their bytes before the order could be
+/// chosen.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    #[default]
+    MsbFirst,
+    LsbFirst,
+}
+
+impl BitOrder {
+    /// Packs bits into bytes. The unused positions of a partial last byte are zero bits.
+    pub fn pack(&self, bits: &[u8]) -> Vec<u8> {
+        bits.chunks(8)
+            .map(|byte_sized_chunk| {
+                byte_sized_chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0, |acc, (index, bit)| acc | (bit << self.get_shift(index)))
+            })
+            .collect()
+    }
+
+    /// Unpacks the first `bit_len` bits of the bytes.
+    pub fn unpack(&self, bytes: &[u8], bit_len: u64) -> Vec<u8> {
+        bytes
+            .iter()
+            .flat_map(|byte| (0..8).map(move |index| (byte >> self.get_shift(index)) & 1))
+            .take(bit_len as usize)
+            .collect()
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            BitOrder::MsbFirst => "most significant bit first".to_string(),
+            BitOrder::LsbFirst => "least significant bit first".to_string(),
+        }
+    }
+
+    /// Mask of the positions used by the first `used_bits` bits of a byte.
+    pub fn get_used_bits_mask(&self, used_bits: u32) -> u8 {
+        (0..used_bits.min(8) as usize).fold(0, |acc, index| acc | (1 << self.get_shift(index)))
+    }
+
+    fn get_shift(&self, index: usize) -> usize {
+        match self {
+            BitOrder::MsbFirst => 7 - index,
+            BitOrder::LsbFirst => index,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pack_works_01() {
+        let bits = vec![0, 0, 1, 1, 0, 1, 1, 0, 1, 1, 0];
+        assert_eq!(
+            BitOrder::MsbFirst.pack(&bits),
+            vec![0b0011_0110, 0b1100_0000]
+        );
+        assert_eq!(
+            BitOrder::LsbFirst.pack(&bits),
+            vec![0b0110_1100, 0b0000_0011]
+        );
+    }
+
+    #[test]
+    fn unpack_works_01() {
+        let bits = vec![1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 1, 0, 1];
+        for bit_order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+            let bytes = bit_order.pack(&bits);
+            assert_eq!(bit_order.unpack(&bytes, bits.len() as u64), bits);
+        }
+    }
+
+    #[test]
+    fn get_used_bits_mask_works_01() {
+        assert_eq!(BitOrder::MsbFirst.get_used_bits_mask(3), 0b1110_0000);
+        assert_eq!(BitOrder::LsbFirst.get_used_bits_mask(3), 0b0000_0111);
+        assert_eq!(BitOrder::MsbFirst.get_used_bits_mask(8), 0b1111_1111);
+    }
+}
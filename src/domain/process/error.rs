@@ -2,6 +2,8 @@ use std::fmt::Display;
 
 use crate::error::Error;
 
+use super::ingestion_report::IngestionReport;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum ProcessError {
     EntropyAlreadyCreated,
@@ -22,6 +24,7 @@ pub enum ProcessError {
     NaturalDatumOutOfRange {
         value: u64,
         range: u64,
+        faces: String,
     },
     EntropyBitVecLenDoesNotMatchTarget {
         provided_bit_len: u64,
@@ -29,6 +32,7 @@ pub enum ProcessError {
     },
     RangeCountIsLessThanTwo,
     TargetEntropyBytesAreLessThanOne,
+    TargetEntropyBitsAreLessThanOne,
     RngEntropyBytesHaveNotBeenGeneratedYet,
     EntropyGeneratingAppendageIsNoneWhileItsDerivativesAreSome,
     EntropyGeneratingAppendageIsSomeWhileItsDerivativesAreNone,
@@ -37,6 +41,140 @@ pub enum ProcessError {
         entropy_generating_sequence_appendage: u64,
     },
     MixedEntropyAlreadyCreated,
+    NaturalDatumGroupIsEmpty,
+    NaturalDatumGroupRangeOverflow {
+        range: u64,
+        group_len: u64,
+    },
+    DatumRangeIsNotAcceptedInSession {
+        range: u64,
+    },
+    RollsNeedRangeInMixedRangeSession,
+    DeckHasLessThanTwoCards,
+    DeckHasTooManyCards {
+        deck_len: u64,
+        max_deck_len: u64,
+    },
+    CardIsNotInDeck {
+        card: u64,
+        deck_len: u64,
+    },
+    CardIsRepeatedInDeckPermutation {
+        card: u64,
+    },
+    CardIsMissingInDeckPermutation {
+        card: u64,
+    },
+    UnknownCardNotation {
+        token: String,
+    },
+    NothingToRedo,
+    InvalidDatumAtPosition {
+        index: u64,
+        error: Box<ProcessError>,
+    },
+    IngestionStoppedAtPosition {
+        index: u64,
+        report: IngestionReport,
+        error: Box<ProcessError>,
+    },
+    InvalidRollToken {
+        index: u64,
+        offset: u64,
+        token: String,
+    },
+    FaceAlphabetDoesNotFitRange {
+        range: u64,
+        alphabet: String,
+    },
+    FaceLabelIsNotValid {
+        label: String,
+    },
+    FaceLabelIsRepeated {
+        label: String,
+    },
+    FaceIsNotInAlphabet {
+        face: String,
+        faces: String,
+    },
+    ExtractionMethodIsNotHashed {
+        method: String,
+    },
+    ExtractionMethodDoesNotFitSession {
+        method: String,
+        requirement: String,
+    },
+    NotEnoughDataForHashedEntropy {
+        data_len: u64,
+        required_data_len: u64,
+    },
+    ConditioningSafetyFactorIsLessThanOne,
+    ConditioningOutputIsShorterThanTarget {
+        output_bits: u64,
+        target_bits: u64,
+    },
+    MinEntropyExceedsInputLength {
+        min_entropy_bits: String,
+        input_bits: u64,
+    },
+    MinEntropyIsTooLowForExtraction {
+        min_entropy_bits: String,
+        security_parameter: u32,
+    },
+    ToeplitzSeedIsTooShort {
+        seed_bits: u64,
+        required_seed_bits: u64,
+    },
+    MixingOutputIsTooLong {
+        strategy: String,
+        output_bytes: u64,
+        max_output_bytes: u64,
+    },
+    NotEnoughSessionsToCombine {
+        given: u64,
+    },
+    SessionToCombineDoesNotMatch {
+        index: u64,
+        requirement: String,
+    },
+    SessionsToCombineAreRepeated {
+        first_index: u64,
+        second_index: u64,
+    },
+    NoDieBiasWarningFound,
+    RangeIsTooLargeForDieBiasMonitor {
+        range: u64,
+        max_range: u64,
+    },
+    HealthTestFailureIsNotAcknowledged {
+        test: String,
+        range: u64,
+    },
+    NaturalDatumFailedHealthTest {
+        test: String,
+        range: u64,
+        face: String,
+    },
+    NoHealthTestFailureFound,
+    RngCommitmentMustPrecedeData,
+    RngIsAlreadyCommitted,
+    NoRngCommitmentFound,
+    RngRevealDoesNotMatchCommitment,
+    RngIsCommittedAndMustBeRevealed,
+    RngSourceFailed {
+        source: String,
+        error: String,
+    },
+    NoRngSourceGiven,
+    ExternalEntropyCouldNotBeRead {
+        error: String,
+    },
+    ExternalEntropyFailedStatisticalTests {
+        failed_tests: Vec<String>,
+    },
+    ExternalEntropyStatisticalTestsDidNotRun {
+        missing_tests: Vec<String>,
+    },
 }
 
 impl Display for ProcessError {
@@ -45,7 +183,7 @@ impl Display for ProcessError {
             ProcessError::EntropyAlreadyCreated => {
                 write!(f, "Entropy has been generated. No need to add more data.")
             }
-            ProcessError::NoSequenceAppendixFound => write!(f, "No sequence appendix was found."),
+            ProcessError::NoSequenceAppendixFound => write!(f, "No sequence appendix was found. There is no datum to undo since the session was created or reset."),
             ProcessError::EntropyBitsAreNotReady {
                 required_entropy_bits,
                 current_entropy_bits,
@@ -68,7 +206,7 @@ impl Display for ProcessError {
                 provided_len, target_len
             ),
 
-            ProcessError::NaturalDatumOutOfRange { value: datum, range: max_inclusive } => write!(f, "Datum must be between 1 and {} (inclusive). The number entered was {}, which is out of range.", max_inclusive, datum),
+            ProcessError::NaturalDatumOutOfRange { value: datum, range: max_inclusive, faces } => write!(f, "Datum must be between 1 and {} (inclusive), for the faces {}. The number entered was {}, which is out of range.", max_inclusive, faces, datum),
             ProcessError::MaxInclusiveLessThanTwo => write!(f, "Max inclusive limit must be more than 2."),
             ProcessError::EntropyBitVecLenDoesNotMatchTarget { provided_bit_len: provided_len, target_bit_len: target_len } => write!(f, "Bit vector provided contains {} bits, while the target entropy requires {} bits.", provided_len, target_len),
             ProcessError::RangeCountIsLessThanTwo => write!(
@@ -78,11 +216,57 @@ impl Display for ProcessError {
             ProcessError::TargetEntropyBytesAreLessThanOne => {
                 write!(f, "At least 1 byte is required to generate entropy.")
             }
+            ProcessError::TargetEntropyBitsAreLessThanOne => write!(f, "At least 1 bit is required to generate entropy."),
             ProcessError::RngEntropyBytesHaveNotBeenGeneratedYet => write!(f, "Rng generated entropy has not been created yet. To mix, you have to generate the rng entropy first."),
             ProcessError::EntropyGeneratingAppendageIsNoneWhileItsDerivativesAreSome => write!(f, "Data is not used to create entropy. But there are Options with Some() value that suggest otherwise."),
             ProcessError::EntropyGeneratingAppendageIsSomeWhileItsDerivativesAreNone => write!(f, "Data is used to create entropy. But there are Options with None value that suggest otherwise."),
             ProcessError::EntropyGeneratingAppendageIsNotRightComparedToSequenceAppendix { sequence_appendage: sequence_appendix, entropy_generating_sequence_appendage: entropy_generating_sequence_appendix } => write!(f, "Sequence appendix is {}. Entropy generating appendix must be {}, but is {}.", sequence_appendix, sequence_appendix - 1, entropy_generating_sequence_appendix),
             ProcessError::MixedEntropyAlreadyCreated => write!(f, "Mixed entropy has been generated. No need to remix."),
+            ProcessError::NaturalDatumGroupIsEmpty => write!(f, "A group of data must contain at least one datum."),
+            ProcessError::NaturalDatumGroupRangeOverflow { range, group_len } => write!(f, "A group of {} data with range {} has {}^{} possible values, which does not fit in 64 bits.", group_len, range, range, group_len),
+            ProcessError::DatumRangeIsNotAcceptedInSession { range } => write!(f, "Data with range {} are not accepted in this session.", range),
+            ProcessError::RollsNeedRangeInMixedRangeSession => write!(f, "This session accepts dice of several ranges. Add data with their ranges instead of bare rolls."),
+            ProcessError::DeckHasLessThanTwoCards => write!(f, "A deck must have at least two cards to generate entropy."),
+            ProcessError::DeckHasTooManyCards { deck_len, max_deck_len } => write!(f, "A deck of {} cards is too large. Decks may have at most {} cards.", deck_len, max_deck_len),
+            ProcessError::CardIsNotInDeck { card, deck_len } => write!(f, "Card {} is not in a deck of {} cards.", card, deck_len),
+            ProcessError::CardIsRepeatedInDeckPermutation { card } => write!(f, "Card {} appears more than once in the deck permutation.", card),
+            ProcessError::CardIsMissingInDeckPermutation { card } => write!(f, "Card {} is missing from the deck permutation.", card),
+            ProcessError::UnknownCardNotation { token } => write!(f, "{} is not a known card. Use a rank of A, 2-9, T, J, Q or K followed by a suit of C, D, H or S.", token),
+            ProcessError::NothingToRedo => write!(f, "There is nothing to redo."),
+            ProcessError::InvalidDatumAtPosition { index, error } => write!(f, "Datum at position {} is invalid: {}", index, error),
+            ProcessError::IngestionStoppedAtPosition { index, report, error } => write!(f, "Adding data stopped at position {} after {} data were added: {}", index, report.get_accepted_data(), error),
+            ProcessError::InvalidRollToken { index, offset, token } => write!(f, "Token {} at position {} (byte offset {}) is not a valid roll.", token, index, offset),
+            ProcessError::FaceAlphabetDoesNotFitRange { range, alphabet } => write!(f, "The {} face alphabet cannot label the faces of a die with {} faces.", alphabet, range),
+            ProcessError::FaceLabelIsNotValid { label } => write!(f, "Face label \"{}\" is not valid. Labels must not be empty or contain whitespace, commas or semicolons.", label),
+            ProcessError::FaceLabelIsRepeated { label } => write!(f, "Face label {} is used for more than one face.", label),
+            ProcessError::FaceIsNotInAlphabet { face, faces } => write!(f, "Face {} is not on the die. Faces are {}.", face, faces),
+            ProcessError::ExtractionMethodIsNotHashed { method } => write!(f, "The {} extraction method makes entropy as data are added. There is nothing to finalize.", method),
+            ProcessError::ExtractionMethodDoesNotFitSession { method, requirement } => write!(f, "The {} extraction method needs {}.", method, requirement),
+            ProcessError::NotEnoughDataForHashedEntropy { data_len, required_data_len } => write!(f, "{} data have been added, while at least {} are required.", data_len, required_data_len),
+            ProcessError::ConditioningSafetyFactorIsLessThanOne => write!(f, "The conditioning safety factor must be at least 1."),
+            ProcessError::ConditioningOutputIsShorterThanTarget { output_bits, target_bits } => write!(f, "The conditioning function gives {} bits, while the target is {} bits.", output_bits, target_bits),
+            ProcessError::MinEntropyExceedsInputLength { min_entropy_bits, input_bits } => write!(f, "A min-entropy of {} bits cannot be credited to an input of {} bits.", min_entropy_bits, input_bits),
+            ProcessError::MinEntropyIsTooLowForExtraction { min_entropy_bits, security_parameter } => write!(f, "A min-entropy of {} bits is not enough to extract any bit with a security parameter of {}. At least {} bits are needed.", min_entropy_bits, security_parameter, 2 * *security_parameter as u64 + 1),
+            ProcessError::ToeplitzSeedIsTooShort { seed_bits, required_seed_bits } => write!(f, "The Toeplitz seed has {} bits, while {} bits are required.", seed_bits, required_seed_bits),
+            ProcessError::NotEnoughSessionsToCombine { given } => write!(f, "At least 2 sessions are needed to combine entropy, while {} were given.", given),
+            ProcessError::SessionToCombineDoesNotMatch { index, requirement } => write!(f, "Session {} cannot be combined, as combined sessions need {}.", index, requirement),
+            ProcessError::SessionsToCombineAreRepeated { first_index, second_index } => write!(f, "Sessions {} and {} have the same entropy, which would cancel out or add nothing.", first_index, second_index),
+            ProcessError::NoDieBiasWarningFound => write!(f, "There is no die bias warning to acknowledge."),
+            ProcessError::RangeIsTooLargeForDieBiasMonitor { range, max_range } => write!(f, "The die bias monitor cannot watch dice of range {}. It watches ranges of at most {}.", range, max_range),
+            ProcessError::HealthTestFailureIsNotAcknowledged { test, range } => write!(f, "The dice of range {} failed the {} health test, which must be acknowledged before more data are added.", range, test),
+            ProcessError::NaturalDatumFailedHealthTest { test, range, face } => write!(f, "The datum was not added, as face {} of the dice of range {} failed the {} health test. Acknowledge the failure before adding more data.", face, range, test),
+            ProcessError::NoHealthTestFailureFound => write!(f, "There is no health test failure to acknowledge."),
+            ProcessError::RngCommitmentMustPrecedeData => write!(f, "The rng contribution must be committed to before any data are added."),
+            ProcessError::RngIsAlreadyCommitted => write!(f, "The session is already committed to an rng contribution."),
+            ProcessError::NoRngCommitmentFound => write!(f, "The session is not committed to any rng contribution."),
+            ProcessError::RngRevealDoesNotMatchCommitment => write!(f, "The revealed rng bytes do not match the commitment made before the data were added."),
+            ProcessError::RngIsCommittedAndMustBeRevealed => write!(f, "The session is committed to an rng contribution, which must be revealed to mix the entropy."),
+            ProcessError::RngSourceFailed { source, error } => write!(f, "The rng source {} failed: {}", source, error),
+            ProcessError::NoRngSourceGiven => write!(f, "At least one rng source is needed to mix the entropy."),
+            ProcessError::ExternalEntropyCouldNotBeRead { error } => write!(f, "External entropy could not be read: {}", error),
+            ProcessError::ExternalEntropyFailedStatisticalTests { failed_tests } => write!(f, "External entropy failed these statistical tests: {}.", failed_tests.join(", ")),
+            ProcessError::ExternalEntropyStatisticalTestsDidNotRun { missing_tests } => write!(f, "These statistical tests could not be run on the external entropy: {}.", missing_tests.join(", ")),
+            ProcessError::MixingOutputIsTooLong { strategy, output_bytes, max_output_bytes } => write!(f, "{} mixing cannot give {} bytes. It gives at most {} bytes.", strategy, output_bytes, max_output_bytes),
         }
     }
 }
@@ -91,4 +275,4 @@ impl From<ProcessError> for Error {
     fn from(value: ProcessError) -> Self {
         Error::ProcessError(value)
     }
-}
\ No newline at end of file
+}
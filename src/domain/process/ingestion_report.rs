@@ -0,0 +1,29 @@
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Outcome of adding several data at once.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, Default, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct IngestionReport {
+    /// Data added to the session.
+    accepted_data: u64,
+    /// Data left over after the target entropy was reached. They were not added.
+    ignored_data: u64,
+}
+
+impl IngestionReport {
+    pub fn new(accepted_data: u64, ignored_data: u64) -> Self {
+        IngestionReport {
+            accepted_data,
+            ignored_data,
+        }
+    }
+
+    pub fn record_accepted(&mut self) {
+        self.accepted_data += 1;
+    }
+
+    pub fn record_ignored(&mut self) {
+        self.ignored_data += 1;
+    }
+}
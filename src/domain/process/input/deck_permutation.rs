@@ -0,0 +1,254 @@
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::domain::process::error::ProcessError;
+
+/// Ranks of a standard deck in the order used to number the cards.
+pub const CARD_RANKS: [char; 13] = [
+    'A', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K',
+];
+/// Suits of a standard deck in the order used to number the cards.
+pub const CARD_SUITS: [char; 4] = ['C', 'D', 'H', 'S'];
+pub const STANDARD_DECK_LEN: u64 = 52;
+/// Largest deck accepted, which bounds the memory used to check a permutation.
+pub const MAX_DECK_LEN: u64 = 1024;
+
+/// The order of a shuffled deck of `deck_len` distinct cards, numbered from 1 to `deck_len`.
+///
+/// Drawing cards without replacement cannot be written as independent data, but the Lehmer
+/// code of the permutation can. Its digit at position `i` is uniformly distributed in
+/// `0..deck_len - i` for a well shuffled deck, so a deck gives `log2(deck_len!)` bits, which is
+/// about 225.58 bits for 52 cards.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct DeckPermutation {
+    deck_len: u64,
+    cards: Vec<u64>,
+}
+
+/// One digit of a Lehmer code, in `0..range`.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct LehmerDigit {
+    value: u64,
+    range: u64,
+}
+
+impl DeckPermutation {
+    /// Creates a permutation from card numbers in `1..=deck_len`. Every card must appear exactly
+    /// once.
+    pub fn new(deck_len: u64, cards: Vec<u64>) -> Result<Self, ProcessError> {
+        if deck_len < 2 {
+            return Err(ProcessError::DeckHasLessThanTwoCards);
+        }
+        if deck_len > MAX_DECK_LEN {
+            return Err(ProcessError::DeckHasTooManyCards {
+                deck_len,
+                max_deck_len: MAX_DECK_LEN,
+            });
+        }
+        let mut seen = vec![false; deck_len as usize];
+        for card in cards.iter() {
+            if !(1..=deck_len).contains(card) {
+                return Err(ProcessError::CardIsNotInDeck {
+                    card: *card,
+                    deck_len,
+                });
+            }
+            if seen[(card - 1) as usize] {
+                return Err(ProcessError::CardIsRepeatedInDeckPermutation { card: *card });
+            }
+            seen[(card - 1) as usize] = true;
+        }
+        if let Some(index) = seen.iter().position(|is_seen| !is_seen) {
+            return Err(ProcessError::CardIsMissingInDeckPermutation {
+                card: index as u64 + 1,
+            });
+        }
+        Ok(DeckPermutation { deck_len, cards })
+    }
+
+    /// Parses a full 52 card deck written as rank and suit pairs, such as `AS 7H TD QC`. Ranks
+    /// are `A 2-9 T J Q K` (`10` is accepted for `T`) and suits are `C D H S`. Cards may be
+    /// separated by whitespace or commas and are case insensitive.
+    pub fn from_card_notation(text: &str) -> Result<Self, ProcessError> {
+        let cards = text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+            .map(card_number_from_notation)
+            .collect::<Result<Vec<u64>, ProcessError>>()?;
+        DeckPermutation::new(STANDARD_DECK_LEN, cards)
+    }
+
+    /// Lehmer code of the permutation. The digit at position `i` counts the cards after
+    /// position `i` that have a smaller number, and lies in `0..deck_len - i`. The last digit,
+    /// which is always zero, is left out.
+    pub fn get_lehmer_code(&self) -> Vec<LehmerDigit> {
+        let mut remaining_cards = (1..=self.deck_len).collect::<Vec<u64>>();
+        self.cards
+            .iter()
+            .take(self.cards.len() - 1)
+            .map(|card| {
+                let position = remaining_cards.binary_search(card).unwrap();
+                let range = remaining_cards.len() as u64;
+                remaining_cards.remove(position);
+                LehmerDigit {
+                    value: position as u64,
+                    range,
+                }
+            })
+            .collect()
+    }
+
+    /// Entropy of a uniformly shuffled deck, `log2(deck_len!)`.
+    pub fn get_entropy_bits(&self) -> f64 {
+        (2..=self.deck_len).map(|n| (n as f64).log2()).sum()
+    }
+}
+
+/// Card number in `1..=52` for a rank and suit pair, counting ranks first within each suit.
+pub fn card_number_from_notation(token: &str) -> Result<u64, ProcessError> {
+    let unknown_card = || ProcessError::UnknownCardNotation {
+        token: token.to_string(),
+    };
+    let chars = token.to_uppercase().chars().collect::<Vec<char>>();
+    let (rank, suit) = match chars[..] {
+        [rank, suit] => (rank, suit),
+        ['1', '0', suit] => ('T', suit),
+        _ => return Err(unknown_card()),
+    };
+    let rank_index = CARD_RANKS
+        .iter()
+        .position(|r| *r == rank)
+        .ok_or_else(unknown_card)?;
+    let suit_index = CARD_SUITS
+        .iter()
+        .position(|s| *s == suit)
+        .ok_or_else(unknown_card)?;
+    Ok((suit_index * CARD_RANKS.len() + rank_index) as u64 + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ordered_deck_notation() -> String {
+        CARD_SUITS
+            .iter()
+            .flat_map(|suit| {
+                CARD_RANKS
+                    .iter()
+                    .map(move |rank| format!("{}{}", rank, suit))
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn new_works_in_normal_conditions_01() {
+        let test = DeckPermutation::new(3, vec![3, 1, 2]).unwrap();
+        assert_eq!(
+            test.get_lehmer_code(),
+            vec![
+                LehmerDigit { value: 2, range: 3 },
+                LehmerDigit { value: 0, range: 2 }
+            ]
+        );
+    }
+
+    #[test]
+    fn new_works_in_normal_conditions_02() {
+        let test = DeckPermutation::new(4, vec![2, 4, 3, 1]).unwrap();
+        let values = test
+            .get_lehmer_code()
+            .iter()
+            .map(|digit| digit.value)
+            .collect::<Vec<u64>>();
+        assert_eq!(values, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn new_emits_error_for_repeated_card_01() {
+        let test = DeckPermutation::new(4, vec![2, 4, 2, 1]);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::CardIsRepeatedInDeckPermutation { card: 2 }
+        );
+    }
+
+    #[test]
+    fn new_emits_error_for_missing_card_01() {
+        let test = DeckPermutation::new(4, vec![2, 4, 1]);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::CardIsMissingInDeckPermutation { card: 3 }
+        );
+    }
+
+    #[test]
+    fn new_emits_error_for_card_out_of_deck_01() {
+        let test = DeckPermutation::new(4, vec![2, 5, 1, 3]);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::CardIsNotInDeck {
+                card: 5,
+                deck_len: 4
+            }
+        );
+    }
+
+    #[test]
+    fn new_emits_error_for_too_large_deck_01() {
+        let test = DeckPermutation::new(u64::MAX, vec![1, 2]);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::DeckHasTooManyCards {
+                deck_len: u64::MAX,
+                max_deck_len: MAX_DECK_LEN
+            }
+        );
+        let test = DeckPermutation::new(MAX_DECK_LEN, (1..=MAX_DECK_LEN).rev().collect());
+        assert!(test.is_ok());
+    }
+
+    #[test]
+    fn from_card_notation_works_01() {
+        let test = DeckPermutation::from_card_notation(&ordered_deck_notation()).unwrap();
+        assert_eq!(test.cards, (1..=52).collect::<Vec<u64>>());
+        assert!(test.get_lehmer_code().iter().all(|digit| digit.value == 0));
+        assert_eq!(test.get_lehmer_code().len(), 51);
+        assert!((test.get_entropy_bits() - 225.58).abs() < 0.01);
+    }
+
+    #[test]
+    fn from_card_notation_emits_error_for_unknown_card_01() {
+        let text = ordered_deck_notation().replace("KS", "KX");
+        let test = DeckPermutation::from_card_notation(&text);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::UnknownCardNotation {
+                token: "KX".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn card_number_from_notation_works_01() {
+        assert_eq!(card_number_from_notation("AC").unwrap(), 1);
+        assert_eq!(card_number_from_notation("10d").unwrap(), 23);
+        assert_eq!(card_number_from_notation("td").unwrap(), 23);
+        assert_eq!(card_number_from_notation("KS").unwrap(), 52);
+    }
+
+    #[test]
+    fn card_number_from_notation_emits_error_for_non_ascii_token_01() {
+        for token in ["é", "Aé", "é♠", "10é", "♠", "1é0"] {
+            assert_eq!(
+                card_number_from_notation(token).err().unwrap(),
+                ProcessError::UnknownCardNotation {
+                    token: token.to_string()
+                }
+            );
+        }
+    }
+}
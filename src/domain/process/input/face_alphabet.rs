@@ -0,0 +1,297 @@
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::domain::process::{error::ProcessError, input::roll_text::ROLL_SEPARATORS};
+
+const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// How the faces of a die are labelled, mapping each label to a zero indexed value in
+/// `0..range` and back.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, Default, PartialEq, Eq)]
+pub enum FaceAlphabet {
+    /// Faces `1` to `range`, as on most dice.
+    #[default]
+    Standard,
+    /// Faces `0` to `range - 1`, as on most d10s.
+    ZeroBased,
+    /// Faces `00`, `10`, ... `90` of a percentile die. Only fits a range of 10.
+    Percentile,
+    /// Faces `A`, `B`, `C` and so on. Fits ranges up to 26.
+    Lettered,
+    /// Faces with the given labels, in order. Fits a range equal to the number of labels.
+    /// Labels are matched case insensitively and may not contain whitespace, commas or
+    /// semicolons.
+    Custom(Vec<String>),
+}
+
+impl FaceAlphabet {
+    /// Checks that the alphabet can label every face of a die with `range` faces.
+    pub fn check_range(&self, range: u64) -> Result<(), ProcessError> {
+        let fits = match self {
+            FaceAlphabet::Standard | FaceAlphabet::ZeroBased => true,
+            FaceAlphabet::Percentile => range == 10,
+            FaceAlphabet::Lettered => range <= LETTERS.len() as u64,
+            FaceAlphabet::Custom(labels) => {
+                if let Some(label) = labels.iter().find(|label| !is_valid_custom_label(label)) {
+                    return Err(ProcessError::FaceLabelIsNotValid {
+                        label: label.clone(),
+                    });
+                }
+                if let Some(label) = labels.iter().enumerate().find_map(|(index, label)| {
+                    labels[..index]
+                        .iter()
+                        .any(|other| other.eq_ignore_ascii_case(label))
+                        .then_some(label)
+                }) {
+                    return Err(ProcessError::FaceLabelIsRepeated {
+                        label: label.clone(),
+                    });
+                }
+                range == labels.len() as u64
+            }
+        };
+        if fits {
+            Ok(())
+        } else {
+            Err(ProcessError::FaceAlphabetDoesNotFitRange {
+                range,
+                alphabet: self.get_name(),
+            })
+        }
+    }
+
+    /// Label of the face with `zero_indexed_value`, or `None` if the alphabet has no such face.
+    pub fn get_label(&self, zero_indexed_value: u64, range: u64) -> Option<String> {
+        if zero_indexed_value >= range {
+            return None;
+        }
+        match self {
+            FaceAlphabet::Standard => Some((zero_indexed_value + 1).to_string()),
+            FaceAlphabet::ZeroBased => Some(zero_indexed_value.to_string()),
+            FaceAlphabet::Percentile if range == 10 => {
+                Some(format!("{:02}", zero_indexed_value * 10))
+            }
+            FaceAlphabet::Percentile => None,
+            FaceAlphabet::Lettered => LETTERS
+                .chars()
+                .nth(zero_indexed_value as usize)
+                .map(String::from),
+            FaceAlphabet::Custom(labels) => labels.get(zero_indexed_value as usize).cloned(),
+        }
+    }
+
+    /// Zero indexed value of the face with `label`, or `None` if no face of a die with `range`
+    /// faces has that label.
+    pub fn get_zero_indexed_value(&self, label: &str, range: u64) -> Option<u64> {
+        let value = match self {
+            FaceAlphabet::Standard => {
+                parse_plain_number(label).and_then(|value| value.checked_sub(1))
+            }
+            FaceAlphabet::ZeroBased => parse_plain_number(label),
+            FaceAlphabet::Percentile if range == 10 => label
+                .strip_suffix('0')
+                .filter(|tens| tens.len() == 1)
+                .and_then(parse_plain_number),
+            FaceAlphabet::Percentile => None,
+            FaceAlphabet::Lettered => {
+                let mut chars = label.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(letter), None) => LETTERS
+                        .find(letter.to_ascii_uppercase())
+                        .map(|index| index as u64),
+                    _ => None,
+                }
+            }
+            FaceAlphabet::Custom(labels) => labels
+                .iter()
+                .position(|face| face.eq_ignore_ascii_case(label))
+                .map(|index| index as u64),
+        };
+        value.filter(|value| *value < range)
+    }
+
+    /// Label of a face, or the zero indexed value in brackets when it is not in the alphabet.
+    pub fn describe_face(&self, zero_indexed_value: u64, range: u64) -> String {
+        self.get_label(zero_indexed_value, range)
+            .unwrap_or_else(|| format!("[{}]", zero_indexed_value))
+    }
+
+    /// Faces of a die with `range` faces, such as `0 to 9` or `A to F`.
+    pub fn describe_faces(&self, range: u64) -> String {
+        match (self.get_label(0, range), self.get_label(range - 1, range)) {
+            (Some(first), Some(last)) => format!("{} to {}", first, last),
+            _ => format!(
+                "none, as the {} alphabet does not fit {} faces",
+                self.get_name(),
+                range
+            ),
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            FaceAlphabet::Standard => "standard".to_string(),
+            FaceAlphabet::ZeroBased => "zero based".to_string(),
+            FaceAlphabet::Percentile => "percentile".to_string(),
+            FaceAlphabet::Lettered => "lettered".to_string(),
+            FaceAlphabet::Custom(labels) => format!("custom ({})", labels.join(" ")),
+        }
+    }
+}
+
+/// Reads a number written with plain digits and no leading zero, so that each number has
+/// exactly one spelling. Signs, spaces and forms such as `06` are rejected.
+pub fn parse_plain_number(text: &str) -> Option<u64> {
+    let is_plain = !text.is_empty()
+        && text.bytes().all(|byte| byte.is_ascii_digit())
+        && (text == "0" || !text.starts_with('0'));
+    if is_plain {
+        text.parse::<u64>().ok()
+    } else {
+        None
+    }
+}
+
+fn is_valid_custom_label(label: &str) -> bool {
+    !label.is_empty()
+        && !label
+            .chars()
+            .any(|c| c.is_whitespace() || ROLL_SEPARATORS.contains(&c))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get_label_works_01() {
+        assert_eq!(FaceAlphabet::Standard.get_label(0, 6).unwrap(), "1");
+        assert_eq!(FaceAlphabet::ZeroBased.get_label(9, 10).unwrap(), "9");
+        assert_eq!(FaceAlphabet::Percentile.get_label(0, 10).unwrap(), "00");
+        assert_eq!(FaceAlphabet::Percentile.get_label(9, 10).unwrap(), "90");
+        assert_eq!(FaceAlphabet::Lettered.get_label(5, 6).unwrap(), "F");
+        assert!(FaceAlphabet::Lettered.get_label(6, 6).is_none());
+        assert!(FaceAlphabet::Percentile.get_label(0, 6).is_none());
+    }
+
+    #[test]
+    fn get_zero_indexed_value_works_01() {
+        assert_eq!(
+            FaceAlphabet::Standard.get_zero_indexed_value("6", 6),
+            Some(5)
+        );
+        assert_eq!(FaceAlphabet::Standard.get_zero_indexed_value("0", 6), None);
+        assert_eq!(
+            FaceAlphabet::ZeroBased.get_zero_indexed_value("0", 10),
+            Some(0)
+        );
+        assert_eq!(
+            FaceAlphabet::ZeroBased.get_zero_indexed_value("10", 10),
+            None
+        );
+        assert_eq!(
+            FaceAlphabet::Percentile.get_zero_indexed_value("70", 10),
+            Some(7)
+        );
+        assert_eq!(
+            FaceAlphabet::Percentile.get_zero_indexed_value("7", 10),
+            None
+        );
+        assert_eq!(
+            FaceAlphabet::Lettered.get_zero_indexed_value("c", 6),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn get_zero_indexed_value_emits_none_for_unplain_numbers_01() {
+        for label in ["+3", "06", "00", " 3", "3 ", "-0", ""] {
+            assert_eq!(
+                FaceAlphabet::Standard.get_zero_indexed_value(label, 10),
+                None
+            );
+            assert_eq!(
+                FaceAlphabet::ZeroBased.get_zero_indexed_value(label, 10),
+                None
+            );
+        }
+        assert_eq!(
+            FaceAlphabet::ZeroBased.get_zero_indexed_value("0", 10),
+            Some(0)
+        );
+        assert_eq!(
+            FaceAlphabet::Standard.get_zero_indexed_value("10", 10),
+            Some(9)
+        );
+    }
+
+    #[test]
+    fn get_zero_indexed_value_works_with_huge_ranges_01() {
+        let range = u64::MAX;
+        assert_eq!(
+            FaceAlphabet::Lettered.get_zero_indexed_value("ZZ", range),
+            None
+        );
+        assert_eq!(
+            FaceAlphabet::Lettered.get_zero_indexed_value("z", range),
+            Some(25)
+        );
+        assert_eq!(
+            FaceAlphabet::Percentile.get_zero_indexed_value("90", range),
+            None
+        );
+        let alphabet = FaceAlphabet::Custom(vec!["heads".to_string(), "tails".to_string()]);
+        assert_eq!(alphabet.get_zero_indexed_value("edge", range), None);
+    }
+
+    #[test]
+    fn get_zero_indexed_value_works_02() {
+        let alphabet = FaceAlphabet::Custom(vec!["heads".to_string(), "tails".to_string()]);
+        assert_eq!(alphabet.get_zero_indexed_value("Tails", 2), Some(1));
+        assert_eq!(alphabet.get_zero_indexed_value("edge", 2), None);
+        for value in 0..2 {
+            let label = alphabet.get_label(value, 2).unwrap();
+            assert_eq!(alphabet.get_zero_indexed_value(&label, 2), Some(value));
+        }
+    }
+
+    #[test]
+    fn check_range_works_01() {
+        assert!(FaceAlphabet::Standard.check_range(100).is_ok());
+        assert!(FaceAlphabet::Percentile.check_range(10).is_ok());
+        assert_eq!(
+            FaceAlphabet::Percentile.check_range(6).err().unwrap(),
+            ProcessError::FaceAlphabetDoesNotFitRange {
+                range: 6,
+                alphabet: "percentile".to_string()
+            }
+        );
+        assert!(FaceAlphabet::Lettered.check_range(26).is_ok());
+        assert!(FaceAlphabet::Lettered.check_range(27).is_err());
+    }
+
+    #[test]
+    fn check_range_emits_error_for_bad_custom_labels_01() {
+        let alphabet = FaceAlphabet::Custom(vec!["x".to_string(), "a b".to_string()]);
+        assert_eq!(
+            alphabet.check_range(2).err().unwrap(),
+            ProcessError::FaceLabelIsNotValid {
+                label: "a b".to_string()
+            }
+        );
+        let alphabet = FaceAlphabet::Custom(vec!["x".to_string(), "X".to_string()]);
+        assert_eq!(
+            alphabet.check_range(2).err().unwrap(),
+            ProcessError::FaceLabelIsRepeated {
+                label: "X".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn describe_faces_works_01() {
+        assert_eq!(FaceAlphabet::Standard.describe_faces(6), "1 to 6");
+        assert_eq!(FaceAlphabet::ZeroBased.describe_faces(10), "0 to 9");
+        assert_eq!(FaceAlphabet::Percentile.describe_faces(10), "00 to 90");
+        assert_eq!(FaceAlphabet::Lettered.describe_faces(4), "A to D");
+    }
+}
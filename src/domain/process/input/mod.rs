@@ -1,5 +1,12 @@
+pub mod deck_permutation;
+pub mod face_alphabet;
 pub mod natural_datum;
+pub mod natural_datum_group;
 pub mod new;
+pub mod roll_text;
 
+pub use deck_permutation::DeckPermutation;
+pub use face_alphabet::FaceAlphabet;
 pub use natural_datum::NaturalDatum;
+pub use natural_datum_group::NaturalDatumGroup;
 pub use new::TychentropyNewInput;
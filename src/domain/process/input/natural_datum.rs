@@ -1,11 +1,12 @@
 use getset::Getters;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::domain::process::error::ProcessError;
+use crate::domain::process::{error::ProcessError, input::face_alphabet::FaceAlphabet};
 
 #[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone)]
 #[get = "pub with_prefix"]
 pub struct NaturalDatum {
+    range: u64,
     value: u64,
 }
 
@@ -14,11 +15,39 @@ impl NaturalDatum {
         if range < 2 {
             Err(ProcessError::MaxInclusiveLessThanTwo)
         } else if !(1..=range).contains(&value) {
-            Err(ProcessError::NaturalDatumOutOfRange { value, range })
+            Err(ProcessError::NaturalDatumOutOfRange {
+                value,
+                range,
+                faces: FaceAlphabet::Standard.describe_faces(range),
+            })
         } else {
-            Ok(NaturalDatum { value })
+            Ok(NaturalDatum { range, value })
         }
     }
+
+    /// Creates a datum from the label of the face that was rolled.
+    pub fn from_label(
+        range: u64,
+        label: &str,
+        face_alphabet: &FaceAlphabet,
+    ) -> Result<Self, ProcessError> {
+        if range < 2 {
+            return Err(ProcessError::MaxInclusiveLessThanTwo);
+        }
+        face_alphabet.check_range(range)?;
+        match face_alphabet.get_zero_indexed_value(label, range) {
+            Some(zero_indexed_value) => NaturalDatum::new(range, zero_indexed_value + 1),
+            None => Err(ProcessError::FaceIsNotInAlphabet {
+                face: label.to_string(),
+                faces: face_alphabet.describe_faces(range),
+            }),
+        }
+    }
+
+    /// Label of the face that was rolled.
+    pub fn get_label(&self, face_alphabet: &FaceAlphabet) -> String {
+        face_alphabet.describe_face(self.value - 1, self.range)
+    }
 }
 
 #[cfg(test)]
@@ -29,6 +58,7 @@ mod test {
     fn new_works_normal_in_min_value_01() {
         let test = NaturalDatum::new(6, 1).unwrap();
         assert_eq!(*test.get_value(), 1);
+        assert_eq!(*test.get_range(), 6);
     }
 
     #[test]
@@ -43,13 +73,48 @@ mod test {
         assert_eq!(*test.get_value(), 6);
     }
 
+    #[test]
+    fn from_label_works_01() {
+        let test = NaturalDatum::from_label(10, "0", &FaceAlphabet::ZeroBased).unwrap();
+        assert_eq!(*test.get_value(), 1);
+        assert_eq!(test.get_label(&FaceAlphabet::ZeroBased), "0");
+        let test = NaturalDatum::from_label(10, "90", &FaceAlphabet::Percentile).unwrap();
+        assert_eq!(*test.get_value(), 10);
+        assert_eq!(test.get_label(&FaceAlphabet::Percentile), "90");
+        assert_eq!(test.get_label(&FaceAlphabet::Standard), "10");
+    }
+
+    #[test]
+    fn from_label_emits_error_for_unknown_face_01() {
+        let test = NaturalDatum::from_label(10, "10", &FaceAlphabet::ZeroBased);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::FaceIsNotInAlphabet {
+                face: "10".to_string(),
+                faces: "0 to 9".to_string()
+            }
+        );
+        let test = NaturalDatum::from_label(6, "00", &FaceAlphabet::Percentile);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::FaceAlphabetDoesNotFitRange {
+                range: 6,
+                alphabet: "percentile".to_string()
+            }
+        );
+    }
+
     #[test]
     fn new_emits_error_in_zero_as_datum_01() {
         let test = NaturalDatum::new(6, 0);
         assert!(test.is_err());
         assert_eq!(
             test.err().unwrap(),
-            ProcessError::NaturalDatumOutOfRange { value: 0, range: 6 }
+            ProcessError::NaturalDatumOutOfRange {
+                value: 0,
+                range: 6,
+                faces: "1 to 6".to_string()
+            }
         )
     }
 
@@ -59,7 +124,11 @@ mod test {
         assert!(test.is_err());
         assert_eq!(
             test.err().unwrap(),
-            ProcessError::NaturalDatumOutOfRange { value: 7, range: 6 }
+            ProcessError::NaturalDatumOutOfRange {
+                value: 7,
+                range: 6,
+                faces: "1 to 6".to_string()
+            }
         )
     }
 
@@ -0,0 +1,143 @@
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::domain::process::{error::ProcessError, input::face_alphabet::FaceAlphabet};
+
+use super::NaturalDatum;
+
+/// Faces of several dice of the same kind that were rolled together, read left to right.
+///
+/// The faces are combined into one datum in `0..range^k`, with the leftmost face as the most
+/// significant digit. Range coding takes the combined datum whole and wastes none of it.
+/// Rejection sampling takes the faces one at a time instead, since it would throw away every
+/// face of a combined datum past its cutoff: five d6 give a range of 7776, and only the 4096
+/// values below 2^12 would be kept.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone)]
+#[get = "pub with_prefix"]
+pub struct NaturalDatumGroup {
+    range: u64,
+    values: Vec<u64>,
+}
+
+impl NaturalDatumGroup {
+    pub fn new(range: u64, values: Vec<u64>) -> Result<Self, ProcessError> {
+        if range < 2 {
+            return Err(ProcessError::MaxInclusiveLessThanTwo);
+        }
+        if values.is_empty() {
+            return Err(ProcessError::NaturalDatumGroupIsEmpty);
+        }
+        if let Some(value) = values.iter().find(|value| !(1..=range).contains(*value)) {
+            return Err(ProcessError::NaturalDatumOutOfRange {
+                value: *value,
+                range,
+                faces: FaceAlphabet::Standard.describe_faces(range),
+            });
+        }
+        combined_range(range, values.len())?;
+        Ok(NaturalDatumGroup { range, values })
+    }
+
+    /// Range of the combined datum, that is `range^k` for `k` faces.
+    pub fn get_combined_range(&self) -> u64 {
+        self.range.pow(self.values.len() as u32)
+    }
+
+    /// Zero indexed combined datum in `0..range^k`.
+    pub fn get_zero_indexed_combined_value(&self) -> u64 {
+        combine_zero_indexed_values(self.range, &self.values)
+    }
+}
+
+impl From<NaturalDatum> for NaturalDatumGroup {
+    fn from(value: NaturalDatum) -> Self {
+        NaturalDatumGroup {
+            range: *value.get_range(),
+            values: vec![*value.get_value()],
+        }
+    }
+}
+
+/// Returns `range^group_len`, or an error if it does not fit in a `u64`.
+pub fn combined_range(range: u64, group_len: usize) -> Result<u64, ProcessError> {
+    u32::try_from(group_len)
+        .ok()
+        .and_then(|exponent| range.checked_pow(exponent))
+        .ok_or(ProcessError::NaturalDatumGroupRangeOverflow {
+            range,
+            group_len: group_len as u64,
+        })
+}
+
+/// Combines one based faces of the same range into a zero indexed mixed-radix value, leftmost
+/// face first. The caller is responsible for the result fitting in a `u64`.
+pub fn combine_zero_indexed_values(range: u64, values: &[u64]) -> u64 {
+    values
+        .iter()
+        .fold(0, |acc, value| acc * range + (value - 1))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn new_works_in_normal_conditions_01() {
+        let test = NaturalDatumGroup::new(6, vec![1, 1, 1, 1, 2]).unwrap();
+        assert_eq!(test.get_combined_range(), 7776);
+        assert_eq!(test.get_zero_indexed_combined_value(), 1);
+    }
+
+    #[test]
+    fn new_works_in_normal_conditions_02() {
+        let test = NaturalDatumGroup::new(6, vec![6, 6, 6, 6, 6]).unwrap();
+        assert_eq!(test.get_zero_indexed_combined_value(), 7775);
+    }
+
+    #[test]
+    fn new_works_in_normal_conditions_03() {
+        let test = NaturalDatumGroup::new(10, vec![3, 1, 5]).unwrap();
+        assert_eq!(test.get_combined_range(), 1000);
+        assert_eq!(test.get_zero_indexed_combined_value(), 204);
+    }
+
+    #[test]
+    fn new_emits_error_for_out_of_range_face_01() {
+        let test = NaturalDatumGroup::new(6, vec![1, 7, 0]);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::NaturalDatumOutOfRange {
+                value: 7,
+                range: 6,
+                faces: "1 to 6".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn new_emits_error_for_empty_group_01() {
+        let test = NaturalDatumGroup::new(6, vec![]);
+        assert_eq!(test.err().unwrap(), ProcessError::NaturalDatumGroupIsEmpty);
+    }
+
+    #[test]
+    fn new_emits_error_for_overflowing_range_01() {
+        let test = NaturalDatumGroup::new(6, vec![1; 25]);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::NaturalDatumGroupRangeOverflow {
+                range: 6,
+                group_len: 25
+            }
+        );
+    }
+
+    #[test]
+    fn from_natural_datum_works_01() {
+        let datum = NaturalDatum::new(20, 13).unwrap();
+        let test = NaturalDatumGroup::from(datum);
+        assert_eq!(test.range, 20);
+        assert_eq!(test.values, vec![13]);
+        assert_eq!(test.get_zero_indexed_combined_value(), 12);
+    }
+}
@@ -1,20 +1,64 @@
-use getset::Getters;
+use getset::{Getters, Setters};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::domain::process::error::ProcessError;
+use crate::domain::{
+    extraction::{Conditioning, ExtractionMethod, Extractor},
+    process::{
+        bit_order::BitOrder, error::ProcessError, input::face_alphabet::FaceAlphabet,
+        mixing::MixingStrategy,
+    },
+    statistics::{
+        bias_monitor::{SprtConfig, MAX_SPRT_RANGE},
+        health_test::HealthTestConfig,
+    },
+};
 
-#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone)]
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Setters, Clone)]
 #[get = "pub with_prefix"]
 pub struct TychentropyNewInput {
     range_len: u64,
+    /// Ranges of the dice allowed in a session that mixes dice types. `None` for a session
+    /// that only uses dice with `range_len` sides.
+    accepted_ranges: Option<Vec<u64>>,
+    /// Bytes needed to hold `target_entropy_bits`, rounded up.
     target_entropy_bytes: u64,
+    target_entropy_bits: u64,
+    #[set = "pub"]
+    extraction_method: ExtractionMethod,
+    /// Labels of the faces, used to read and write rolls as text.
+    face_alphabet: FaceAlphabet,
+    /// Order in which entropy bits are packed into bytes.
+    #[set = "pub"]
+    bit_order: BitOrder,
+    /// How the entropy is combined with rng bytes by `mix_with_rng`.
+    #[set = "pub"]
+    mixing_strategy: MixingStrategy,
+    /// Watches the data for biased dice while they are added, when set.
+    sprt_config: Option<SprtConfig>,
+    /// Runs the continuous health tests on the data while they are added, when set.
+    #[set = "pub"]
+    health_test_config: Option<HealthTestConfig>,
+    /// Hashes the whole sequence into the entropy instead of using the extracted bits.
+    conditioning: Option<Conditioning>,
+    /// Extractor used instead of the one of `extraction_method`.
+    extractor: Option<Box<dyn Extractor>>,
 }
 
 impl Default for TychentropyNewInput {
     fn default() -> Self {
         Self {
             range_len: 6,
+            accepted_ranges: None,
             target_entropy_bytes: 32,
+            target_entropy_bits: 256,
+            extraction_method: ExtractionMethod::default(),
+            face_alphabet: FaceAlphabet::default(),
+            bit_order: BitOrder::default(),
+            mixing_strategy: MixingStrategy::default(),
+            sprt_config: None,
+            health_test_config: None,
+            conditioning: None,
+            extractor: None,
         }
     }
 }
@@ -29,9 +73,122 @@ impl TychentropyNewInput {
         }
         Ok(TychentropyNewInput {
             range_len,
+            accepted_ranges: None,
             target_entropy_bytes,
+            target_entropy_bits: target_entropy_bytes * 8,
+            extraction_method: ExtractionMethod::default(),
+            face_alphabet: FaceAlphabet::default(),
+            bit_order: BitOrder::default(),
+            mixing_strategy: MixingStrategy::default(),
+            sprt_config: None,
+            health_test_config: None,
+            conditioning: None,
+            extractor: None,
         })
     }
+
+    /// Input for a session in which each datum carries its own range, for example a kit of d6,
+    /// d8, d10, d12 and d20 dice used together. `range_len` is set to the largest range.
+    pub fn new_with_mixed_ranges(
+        accepted_ranges: Vec<u64>,
+        target_entropy_bytes: u64,
+    ) -> Result<Self, ProcessError> {
+        let mut accepted_ranges = accepted_ranges;
+        accepted_ranges.sort_unstable();
+        accepted_ranges.dedup();
+        let range_len = match accepted_ranges.first() {
+            Some(range) if *range >= 2 => *accepted_ranges.last().unwrap(),
+            _ => return Err(ProcessError::RangeCountIsLessThanTwo),
+        };
+        let mut input = TychentropyNewInput::new(range_len, target_entropy_bytes)?;
+        input.accepted_ranges = Some(accepted_ranges);
+        Ok(input)
+    }
+
+    /// Input for a target of any number of bits, such as 130 bits for a 26 character codex32
+    /// secret. The last byte of the output is padded when the target is not a multiple of 8.
+    pub fn new_with_target_bits(
+        range_len: u64,
+        target_entropy_bits: u64,
+    ) -> Result<Self, ProcessError> {
+        let mut input = TychentropyNewInput::new(range_len, 1)?;
+        input.set_target_entropy_bits(target_entropy_bits)?;
+        Ok(input)
+    }
+
+    /// Sets the target in bits. Also works on input for a session that mixes dice types.
+    pub fn set_target_entropy_bits(
+        &mut self,
+        target_entropy_bits: u64,
+    ) -> Result<&mut Self, ProcessError> {
+        if target_entropy_bits < 1 {
+            return Err(ProcessError::TargetEntropyBitsAreLessThanOne);
+        }
+        if let Some(conditioning) = &self.conditioning {
+            conditioning.check_target_bits(target_entropy_bits)?;
+        }
+        self.target_entropy_bits = target_entropy_bits;
+        self.target_entropy_bytes = target_entropy_bits.div_ceil(8);
+        Ok(self)
+    }
+
+    /// Sets a conditioning step, or removes it with `None`. Its function must give at least
+    /// the target bits. Hashed extraction methods condition the data themselves and ignore it.
+    pub fn set_conditioning(
+        &mut self,
+        conditioning: Option<Conditioning>,
+    ) -> Result<&mut Self, ProcessError> {
+        if let Some(conditioning) = &conditioning {
+            conditioning.check_target_bits(self.target_entropy_bits)?;
+        }
+        self.conditioning = conditioning;
+        Ok(self)
+    }
+
+    /// Sets the face labels, which must fit every range used in the session.
+    pub fn set_face_alphabet(
+        &mut self,
+        face_alphabet: FaceAlphabet,
+    ) -> Result<&mut Self, ProcessError> {
+        match &self.accepted_ranges {
+            None => face_alphabet.check_range(self.range_len)?,
+            Some(accepted_ranges) => accepted_ranges
+                .iter()
+                .try_for_each(|range| face_alphabet.check_range(*range))?,
+        }
+        self.face_alphabet = face_alphabet;
+        Ok(self)
+    }
+
+    /// Sets the die bias monitor, or removes it with `None`. Every range used in the session
+    /// must be at most [`MAX_SPRT_RANGE`].
+    pub fn set_sprt_config(
+        &mut self,
+        sprt_config: Option<SprtConfig>,
+    ) -> Result<&mut Self, ProcessError> {
+        if sprt_config.is_some() {
+            let largest_range = match &self.accepted_ranges {
+                None => self.range_len,
+                Some(accepted_ranges) => *accepted_ranges.iter().max().unwrap(),
+            };
+            if largest_range > MAX_SPRT_RANGE {
+                return Err(ProcessError::RangeIsTooLargeForDieBiasMonitor {
+                    range: largest_range,
+                    max_range: MAX_SPRT_RANGE,
+                });
+            }
+        }
+        self.sprt_config = sprt_config;
+        Ok(self)
+    }
+
+    /// Uses `extractor` to turn data into bits instead of the extractor of the extraction
+    /// method. Hashed methods and conditioning make the entropy from the whole sequence and
+    /// do not use it.
+    pub fn set_extractor(&mut self, extractor: Box<dyn Extractor>) -> &mut Self {
+        self.extractor = Some(extractor);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -62,10 +219,116 @@ mod test {
         );
     }
 
+    #[test]
+    fn new_with_mixed_ranges_works_01() {
+        let test = TychentropyNewInput::new_with_mixed_ranges(vec![20, 6, 8, 6], 32).unwrap();
+        assert_eq!(*test.get_range_len(), 20);
+        assert_eq!(*test.get_accepted_ranges(), Some(vec![6, 8, 20]));
+    }
+
+    #[test]
+    fn new_with_mixed_ranges_emits_error_when_a_range_is_less_than_2_01() {
+        let test = TychentropyNewInput::new_with_mixed_ranges(vec![6, 1], 32);
+        assert_eq!(test.err().unwrap(), ProcessError::RangeCountIsLessThanTwo);
+    }
+
+    #[test]
+    fn set_sprt_config_emits_error_for_large_range_01() {
+        let mut test = TychentropyNewInput::new(1 << 32, 32).unwrap();
+        assert_eq!(
+            test.set_sprt_config(Some(SprtConfig::default()))
+                .err()
+                .unwrap(),
+            ProcessError::RangeIsTooLargeForDieBiasMonitor {
+                range: 1 << 32,
+                max_range: MAX_SPRT_RANGE
+            }
+        );
+        assert!(test.set_sprt_config(None).is_ok());
+        let mut test =
+            TychentropyNewInput::new_with_mixed_ranges(vec![6, MAX_SPRT_RANGE + 1], 32).unwrap();
+        assert!(test.set_sprt_config(Some(SprtConfig::default())).is_err());
+        let mut test = TychentropyNewInput::new(MAX_SPRT_RANGE, 32).unwrap();
+        assert!(test.set_sprt_config(Some(SprtConfig::default())).is_ok());
+    }
+
+    #[test]
+    fn new_with_mixed_ranges_emits_error_when_empty_01() {
+        let test = TychentropyNewInput::new_with_mixed_ranges(vec![], 32);
+        assert_eq!(test.err().unwrap(), ProcessError::RangeCountIsLessThanTwo);
+    }
+
     #[test]
     fn default_works_01() {
         let result = TychentropyNewInput::default();
         assert_eq!(result.range_len, 6);
         assert_eq!(result.target_entropy_bytes, 32);
+        assert_eq!(result.extraction_method, ExtractionMethod::Rejection);
+    }
+
+    #[test]
+    fn set_extraction_method_works_01() {
+        let mut test = TychentropyNewInput::new(6, 32).unwrap();
+        test.set_extraction_method(ExtractionMethod::RangeCoding);
+        assert_eq!(*test.get_extraction_method(), ExtractionMethod::RangeCoding);
+    }
+
+    #[test]
+    fn set_face_alphabet_works_01() {
+        let mut test = TychentropyNewInput::new(10, 32).unwrap();
+        test.set_face_alphabet(FaceAlphabet::Percentile).unwrap();
+        assert_eq!(*test.get_face_alphabet(), FaceAlphabet::Percentile);
+    }
+
+    #[test]
+    fn set_face_alphabet_emits_error_for_range_not_fitting_01() {
+        let mut test = TychentropyNewInput::new_with_mixed_ranges(vec![6, 30], 32).unwrap();
+        assert_eq!(
+            test.set_face_alphabet(FaceAlphabet::Lettered)
+                .err()
+                .unwrap(),
+            ProcessError::FaceAlphabetDoesNotFitRange {
+                range: 30,
+                alphabet: "lettered".to_string()
+            }
+        );
+        assert_eq!(*test.get_face_alphabet(), FaceAlphabet::Standard);
+    }
+
+    #[test]
+    fn new_with_target_bits_works_01() {
+        let test = TychentropyNewInput::new_with_target_bits(6, 130).unwrap();
+        assert_eq!(*test.get_target_entropy_bits(), 130);
+        assert_eq!(*test.get_target_entropy_bytes(), 17);
+        let test = TychentropyNewInput::new_with_target_bits(6, 128).unwrap();
+        assert_eq!(*test.get_target_entropy_bytes(), 16);
+    }
+
+    #[test]
+    fn new_with_target_bits_emits_error_for_zero_bits_01() {
+        let test = TychentropyNewInput::new_with_target_bits(6, 0);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::TargetEntropyBitsAreLessThanOne
+        );
+    }
+
+    #[test]
+    fn set_conditioning_emits_error_for_long_target_01() {
+        let mut test = TychentropyNewInput::new(6, 64).unwrap();
+        let conditioning = Conditioning::default();
+        assert_eq!(
+            test.set_conditioning(Some(conditioning.clone()))
+                .err()
+                .unwrap(),
+            ProcessError::ConditioningOutputIsShorterThanTarget {
+                output_bits: 256,
+                target_bits: 512
+            }
+        );
+        let mut test = TychentropyNewInput::new(6, 32).unwrap();
+        test.set_conditioning(Some(conditioning)).unwrap();
+        assert!(test.set_target_entropy_bits(257).is_err());
+        assert_eq!(*test.get_target_entropy_bits(), 256);
     }
 }
@@ -0,0 +1,241 @@
+use crate::domain::process::{
+    error::ProcessError,
+    input::{
+        face_alphabet::{parse_plain_number, FaceAlphabet},
+        natural_datum::NaturalDatum,
+    },
+};
+
+/// Characters allowed between rolls, besides whitespace.
+pub const ROLL_SEPARATORS: [char; 2] = [',', ';'];
+
+/// Parses rolls of a die with `range` faces from text such as `1 4 6 2, 3 4 1`, and returns
+/// them as values from 1 to `range`.
+///
+/// Each token is read as a face label of `face_alphabet`. Rolls are separated by any mix of
+/// whitespace, commas and semicolons, so empty tokens are skipped. A token that is not a face
+/// of the die is reported with its zero indexed position among the tokens and its byte offset
+/// in the text.
+pub fn parse_roll_text(
+    text: &str,
+    range: u64,
+    face_alphabet: &FaceAlphabet,
+) -> Result<Vec<u64>, ProcessError> {
+    roll_tokens(text)
+        .enumerate()
+        .map(|(index, (offset, token))| {
+            face_alphabet
+                .get_zero_indexed_value(token, range)
+                .map(|value| value + 1)
+                .ok_or_else(|| ProcessError::InvalidRollToken {
+                    index: index as u64,
+                    offset: offset as u64,
+                    token: token.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Parses rolls of dice of several ranges from text such as `d6:5 d20:13`, as written by
+/// `Tychentropy::get_roll_log` for a session that mixes dice types.
+///
+/// Each token is `d`, the range of its die, a colon and a face label of `face_alphabet` for
+/// that range. A token whose range is not in `accepted_ranges`, or cannot be labelled by
+/// `face_alphabet`, is invalid. Separators and errors are as in [`parse_roll_text`].
+pub fn parse_ranged_roll_text(
+    text: &str,
+    accepted_ranges: &[u64],
+    face_alphabet: &FaceAlphabet,
+) -> Result<Vec<NaturalDatum>, ProcessError> {
+    roll_tokens(text)
+        .enumerate()
+        .map(|(index, (offset, token))| {
+            parse_ranged_roll_token(token, accepted_ranges, face_alphabet).ok_or_else(|| {
+                ProcessError::InvalidRollToken {
+                    index: index as u64,
+                    offset: offset as u64,
+                    token: token.to_string(),
+                }
+            })
+        })
+        .collect()
+}
+
+fn parse_ranged_roll_token(
+    token: &str,
+    accepted_ranges: &[u64],
+    face_alphabet: &FaceAlphabet,
+) -> Option<NaturalDatum> {
+    let (range, label) = token
+        .strip_prefix(['d', 'D'])
+        .and_then(|ranged_label| ranged_label.split_once(':'))?;
+    let range = parse_plain_number(range)?;
+    if !accepted_ranges.contains(&range) || face_alphabet.check_range(range).is_err() {
+        return None;
+    }
+    let value = face_alphabet.get_zero_indexed_value(label, range)?;
+    NaturalDatum::new(range, value + 1).ok()
+}
+
+/// Non-empty tokens of the text with their byte offsets.
+fn roll_tokens(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(|c: char| c.is_whitespace() || ROLL_SEPARATORS.contains(&c))
+        .filter(|token| !token.is_empty())
+        .map(move |token| (token.as_ptr() as usize - text.as_ptr() as usize, token))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_roll_text_works_01() {
+        let test = parse_roll_text("1 4 6 2, 3 4 1", 6, &FaceAlphabet::Standard).unwrap();
+        assert_eq!(test, vec![1, 4, 6, 2, 3, 4, 1]);
+    }
+
+    #[test]
+    fn parse_roll_text_works_02() {
+        let test = parse_roll_text("\n 12;3,,\t20 ;\n", 20, &FaceAlphabet::Standard).unwrap();
+        assert_eq!(test, vec![12, 3, 20]);
+        assert_eq!(
+            parse_roll_text(" , ; ", 6, &FaceAlphabet::Standard).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn parse_roll_text_works_with_face_alphabet_01() {
+        let test = parse_roll_text("0 9;0", 10, &FaceAlphabet::ZeroBased).unwrap();
+        assert_eq!(test, vec![1, 10, 1]);
+        let test = parse_roll_text("00 90 30", 10, &FaceAlphabet::Percentile).unwrap();
+        assert_eq!(test, vec![1, 10, 4]);
+        let test = parse_roll_text("a F c", 6, &FaceAlphabet::Lettered).unwrap();
+        assert_eq!(test, vec![1, 6, 3]);
+        let test = parse_roll_text("a G", 6, &FaceAlphabet::Lettered);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::InvalidRollToken {
+                index: 1,
+                offset: 2,
+                token: "G".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_roll_text_emits_error_for_invalid_token_01() {
+        let test = parse_roll_text("1 4, 6x 2", 6, &FaceAlphabet::Standard);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::InvalidRollToken {
+                index: 2,
+                offset: 5,
+                token: "6x".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_roll_text_emits_error_for_unplain_number_01() {
+        let test = parse_roll_text("1 +3", 6, &FaceAlphabet::Standard);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::InvalidRollToken {
+                index: 1,
+                offset: 2,
+                token: "+3".to_string()
+            }
+        );
+        let test = parse_roll_text("06", 10, &FaceAlphabet::ZeroBased);
+        assert!(test.is_err());
+    }
+
+    #[test]
+    fn parse_ranged_roll_text_works_01() {
+        let test =
+            parse_ranged_roll_text("d6:5, D20:13;d2:1", &[2, 6, 20], &FaceAlphabet::Standard)
+                .unwrap();
+        let test = test
+            .iter()
+            .map(|datum| (*datum.get_range(), *datum.get_value()))
+            .collect::<Vec<(u64, u64)>>();
+        assert_eq!(test, vec![(6, 5), (20, 13), (2, 1)]);
+        let test = parse_ranged_roll_text("d6:a d8:H", &[6, 8], &FaceAlphabet::Lettered).unwrap();
+        assert_eq!(*test[1].get_value(), 8);
+    }
+
+    #[test]
+    fn parse_ranged_roll_text_emits_error_for_invalid_token_01() {
+        for token in [
+            "5",
+            "d6:7",
+            "d06:5",
+            "d1:1",
+            "d:5",
+            "d6-5",
+            "x6:5",
+            "d8:5",
+            "d99999999999:5",
+        ] {
+            let test = parse_ranged_roll_text(
+                &format!("d6:1 {}", token),
+                &[6, 20],
+                &FaceAlphabet::Standard,
+            );
+            assert_eq!(
+                test.err().unwrap(),
+                ProcessError::InvalidRollToken {
+                    index: 1,
+                    offset: 5,
+                    token: token.to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn parse_ranged_roll_text_emits_error_for_huge_ranges_01() {
+        for face_alphabet in [
+            FaceAlphabet::Lettered,
+            FaceAlphabet::Percentile,
+            FaceAlphabet::Custom(vec!["x".to_string(), "y".to_string()]),
+        ] {
+            let test = parse_ranged_roll_text(
+                "d99999999999:ZZ",
+                &[2, 10, 26, 99999999999],
+                &face_alphabet,
+            );
+            assert_eq!(
+                test.err().unwrap(),
+                ProcessError::InvalidRollToken {
+                    index: 0,
+                    offset: 0,
+                    token: "d99999999999:ZZ".to_string()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn parse_roll_text_emits_error_for_out_of_range_roll_01() {
+        let test = parse_roll_text("1 4;  0 2", 6, &FaceAlphabet::Standard);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::InvalidRollToken {
+                index: 2,
+                offset: 6,
+                token: "0".to_string()
+            }
+        );
+        let test = parse_roll_text("1 7", 6, &FaceAlphabet::Standard);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::InvalidRollToken {
+                index: 1,
+                offset: 2,
+                token: "7".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::ops::BitXor;
+
+use getset::Getters;
+use ring::{hkdf, hmac};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::domain::process::error::ProcessError;
+
+/// Label that keeps mixed outputs apart from any other use of the same keyed function.
+pub const MIXING_DOMAIN: &str = "tychentropy/mixing/v1";
+
+/// Most bytes HKDF-SHA-256 can expand to.
+const HKDF_SHA256_MAX_OUTPUT_BYTES: u64 = 255 * 32;
+
+/// How the entropy of the data is combined with rng bytes.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, Default, PartialEq, Eq)]
+pub enum MixingStrategy {
+    /// XOR of the two, byte by byte. Kept for compatibility with sessions mixed before the
+    /// keyed strategies existed. It has no domain separation and reusing the rng bytes with
+    /// other entropy reveals the XOR of both entropies.
+    #[default]
+    Xor,
+    /// HKDF-SHA-256 with the rng bytes as the salt and the entropy as the input keying material,
+    /// expanded with the domain label and the target as the info.
+    HkdfSha256,
+    /// HMAC-SHA-256 keyed with the rng bytes over the domain label, the target, a block counter
+    /// and the entropy, with as many blocks as the target needs.
+    HmacSha256,
+}
+
+impl MixingStrategy {
+    pub fn get_name(&self) -> String {
+        match self {
+            MixingStrategy::Xor => "XOR".to_string(),
+            MixingStrategy::HkdfSha256 => "HKDF-SHA-256".to_string(),
+            MixingStrategy::HmacSha256 => "HMAC-SHA-256".to_string(),
+        }
+    }
+
+    /// Domain label the strategy uses, or `None` for XOR.
+    pub fn get_domain(&self) -> Option<String> {
+        match self {
+            MixingStrategy::Xor => None,
+            MixingStrategy::HkdfSha256 | MixingStrategy::HmacSha256 => {
+                Some(MIXING_DOMAIN.to_string())
+            }
+        }
+    }
+
+    /// Mixes `entropy_bytes` with `rng_entropy_bytes` of the same length into as many bytes.
+    /// `target_entropy_bits` is bound into the keyed strategies, so different targets never
+    /// share an output. Padding bits of a partial last byte are left to the caller.
+    pub fn mix(
+        &self,
+        entropy_bytes: &[u8],
+        rng_entropy_bytes: &[u8],
+        target_entropy_bits: u64,
+    ) -> Result<Vec<u8>, ProcessError> {
+        let output_len = entropy_bytes.len();
+        match self {
+            MixingStrategy::Xor => Ok(entropy_bytes
+                .iter()
+                .zip(rng_entropy_bytes.iter())
+                .map(|(data, rng)| data.bitxor(rng))
+                .collect()),
+            MixingStrategy::HkdfSha256 => {
+                if output_len as u64 > HKDF_SHA256_MAX_OUTPUT_BYTES {
+                    return Err(ProcessError::MixingOutputIsTooLong {
+                        strategy: self.get_name(),
+                        output_bytes: output_len as u64,
+                        max_output_bytes: HKDF_SHA256_MAX_OUTPUT_BYTES,
+                    });
+                }
+                let info = labelled_target(target_entropy_bits);
+                let info = [info.as_slice()];
+                let mut output = vec![0u8; output_len];
+                hkdf::Salt::new(hkdf::HKDF_SHA256, rng_entropy_bytes)
+                    .extract(entropy_bytes)
+                    .expand(&info, OutputLen(output_len))
+                    .and_then(|okm| okm.fill(&mut output))
+                    .expect("output length is checked against the HKDF limit");
+                Ok(output)
+            }
+            MixingStrategy::HmacSha256 => {
+                let key = hmac::Key::new(hmac::HMAC_SHA256, rng_entropy_bytes);
+                let mut output = vec![];
+                let mut counter = 1u32;
+                while output.len() < output_len {
+                    let mut message = labelled_target(target_entropy_bits);
+                    message.extend(counter.to_be_bytes());
+                    message.extend(entropy_bytes);
+                    output.extend(hmac::sign(&key, &message).as_ref());
+                    message.zeroize();
+                    counter += 1;
+                }
+                output.truncate(output_len);
+                Ok(output)
+            }
+        }
+    }
+}
+
+/// The domain label prefixed with its length, then the target as an 8 byte big endian integer.
+fn labelled_target(target_entropy_bits: u64) -> Vec<u8> {
+    let mut label = (MIXING_DOMAIN.len() as u64).to_be_bytes().to_vec();
+    label.extend(MIXING_DOMAIN.as_bytes());
+    label.extend(target_entropy_bits.to_be_bytes());
+    label
+}
+
+struct OutputLen(usize);
+
+impl hkdf::KeyType for OutputLen {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+/// Everything a mix used besides the entropy of the data, kept by the session so the final
+/// entropy can be derived again.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct MixingRecord {
+    strategy: MixingStrategy,
+    domain: Option<String>,
+    target_entropy_bits: u64,
+    rng_entropy_bytes: Vec<u8>,
+}
+
+impl MixingRecord {
+    pub fn new(
+        strategy: &MixingStrategy,
+        target_entropy_bits: u64,
+        rng_entropy_bytes: &[u8],
+    ) -> Self {
+        MixingRecord {
+            strategy: strategy.clone(),
+            domain: strategy.get_domain(),
+            target_entropy_bits,
+            rng_entropy_bytes: rng_entropy_bytes.to_vec(),
+        }
+    }
+
+    /// Mixes `entropy_bytes` again with the recorded parameters.
+    pub fn remix(&self, entropy_bytes: &[u8]) -> Result<Vec<u8>, ProcessError> {
+        self.strategy.mix(
+            entropy_bytes,
+            &self.rng_entropy_bytes,
+            self.target_entropy_bits,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn xor_mix_works_01() {
+        let test = MixingStrategy::Xor.mix(&[0b1100, 0xff], &[0b1010, 0x0f], 16);
+        assert_eq!(test.unwrap(), vec![0b0110, 0xf0]);
+    }
+
+    #[test]
+    fn hkdf_mix_works_01() {
+        let entropy = [0x11; 32];
+        let rng = [0x22; 32];
+        let test = MixingStrategy::HkdfSha256.mix(&entropy, &rng, 256).unwrap();
+        // HKDF-Extract and HKDF-Expand of RFC 5869 for a single block.
+        let prk = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &rng), &entropy);
+        let mut block_input = labelled_target(256);
+        block_input.push(1);
+        let expected = hmac::sign(
+            &hmac::Key::new(hmac::HMAC_SHA256, prk.as_ref()),
+            &block_input,
+        );
+        assert_eq!(test, expected.as_ref().to_vec());
+    }
+
+    #[test]
+    fn hmac_mix_works_01() {
+        let entropy = [0x11; 40];
+        let rng = [0x22; 40];
+        let test = MixingStrategy::HmacSha256.mix(&entropy, &rng, 320).unwrap();
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &rng);
+        let block = |counter: u32| {
+            let mut message = labelled_target(320);
+            message.extend(counter.to_be_bytes());
+            message.extend(entropy);
+            hmac::sign(&key, &message).as_ref().to_vec()
+        };
+        assert_eq!(test.len(), 40);
+        assert_eq!(test[..32], block(1));
+        assert_eq!(test[32..], block(2)[..8]);
+    }
+
+    #[test]
+    fn keyed_mix_separates_targets_and_strategies_01() {
+        let entropy = [0x5a; 16];
+        let rng = [0xa5; 16];
+        let hkdf_128 = MixingStrategy::HkdfSha256.mix(&entropy, &rng, 128).unwrap();
+        let hkdf_127 = MixingStrategy::HkdfSha256.mix(&entropy, &rng, 127).unwrap();
+        let hmac_128 = MixingStrategy::HmacSha256.mix(&entropy, &rng, 128).unwrap();
+        assert_ne!(hkdf_128, hkdf_127);
+        assert_ne!(hkdf_128, hmac_128);
+    }
+
+    #[test]
+    fn hkdf_mix_emits_error_for_long_output_01() {
+        let test = MixingStrategy::HkdfSha256.mix(&[0; 8161], &[0; 8161], 65288);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::MixingOutputIsTooLong {
+                strategy: "HKDF-SHA-256".to_string(),
+                output_bytes: 8161,
+                max_output_bytes: 8160
+            }
+        );
+    }
+}
@@ -1,4 +1,11 @@
+pub mod bit_order;
 pub mod error;
+pub mod ingestion_report;
 pub mod input;
+pub mod mixing;
+pub mod range_account;
+pub mod rng_commitment;
+pub mod rng_source;
+pub mod session_event;
 
-pub use error::ProcessError;
\ No newline at end of file
+pub use error::ProcessError;
@@ -0,0 +1,61 @@
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Bookkeeping of the data of one die type in a session.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct RangeAccount {
+    range: u64,
+    sequence: Vec<u64>,
+    entropy_generating_data: u64,
+    /// Bits emitted while data of this range were absorbed. With range coding, part of these
+    /// bits may come from entropy carried over from data of other ranges.
+    generated_entropy_bits: u64,
+}
+
+impl RangeAccount {
+    pub fn new(range: u64) -> Self {
+        RangeAccount {
+            range,
+            sequence: vec![],
+            entropy_generating_data: 0,
+            generated_entropy_bits: 0,
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        data: &[u64],
+        entropy_generating_data: u64,
+        generated_entropy_bits: u64,
+    ) {
+        self.sequence.extend(data);
+        self.entropy_generating_data += entropy_generating_data;
+        self.generated_entropy_bits += generated_entropy_bits;
+    }
+
+    /// Number of times each face, from 1 to `range`, was seen.
+    pub fn get_face_counts(&self) -> Vec<u64> {
+        let mut face_counts = vec![0; self.range as usize];
+        self.sequence
+            .iter()
+            .for_each(|value| face_counts[(value - 1) as usize] += 1);
+        face_counts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn record_works_01() {
+        let mut account = RangeAccount::new(6);
+        account.record(&[1, 6], 1, 2);
+        account.record(&[6], 0, 0);
+        assert_eq!(account.sequence, vec![1, 6, 6]);
+        assert_eq!(account.entropy_generating_data, 1);
+        assert_eq!(account.generated_entropy_bits, 2);
+        assert_eq!(account.get_face_counts(), vec![1, 0, 0, 0, 0, 2]);
+    }
+}
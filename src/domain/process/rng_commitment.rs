@@ -0,0 +1,133 @@
+use std::fmt::Debug;
+
+use ring::digest::{digest, SHA256};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Rng bytes a session is committed to before any data are added, and their SHA-256.
+///
+/// The commitment is shown first; the bytes are only revealed when the session is mixed with
+/// them. As the bytes are random and as long as the target entropy, the commitment does not
+/// give them away, and the bytes cannot be swapped once the dice are seen. `Debug` leaves the
+/// bytes out until they are revealed.
+#[derive(Zeroize, ZeroizeOnDrop, Clone, PartialEq, Eq)]
+pub struct RngCommitment {
+    commitment: Vec<u8>,
+    rng_entropy_bytes: Vec<u8>,
+    is_revealed: bool,
+}
+
+impl RngCommitment {
+    pub fn new(rng_entropy_bytes: &[u8]) -> Self {
+        RngCommitment::with_commitment(&commit(rng_entropy_bytes), rng_entropy_bytes)
+    }
+
+    /// A commitment as it was recorded, which may not match the bytes.
+    pub fn with_commitment(commitment: &[u8], rng_entropy_bytes: &[u8]) -> Self {
+        RngCommitment {
+            commitment: commitment.to_vec(),
+            rng_entropy_bytes: rng_entropy_bytes.to_vec(),
+            is_revealed: false,
+        }
+    }
+
+    /// SHA-256 of the committed bytes.
+    pub fn get_commitment(&self) -> &Vec<u8> {
+        &self.commitment
+    }
+
+    /// The committed bytes, once they are revealed.
+    pub fn get_revealed_bytes(&self) -> Option<&Vec<u8>> {
+        self.is_revealed.then_some(&self.rng_entropy_bytes)
+    }
+
+    pub fn is_revealed(&self) -> bool {
+        self.is_revealed
+    }
+
+    /// Whether the committed bytes match `shown_commitment`, the commitment written down
+    /// before rolling. Comparing with the commitment kept here would prove nothing, as it is
+    /// computed from the same bytes.
+    pub fn check_reveal(&self, shown_commitment: &[u8]) -> bool {
+        commit(&self.rng_entropy_bytes) == shown_commitment
+    }
+
+    /// Reveals the committed bytes and returns them, whether or not they match the commitment.
+    pub fn reveal(&mut self) -> Vec<u8> {
+        self.is_revealed = true;
+        self.rng_entropy_bytes.clone()
+    }
+}
+
+impl Debug for RngCommitment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RngCommitment")
+            .field("commitment", &self.commitment)
+            .field("rng_entropy_bytes", &self.get_revealed_bytes())
+            .field("is_revealed", &self.is_revealed)
+            .finish()
+    }
+}
+
+/// Rng bytes a session is committed to, kept apart from its history until they are revealed.
+/// `Debug` does not print them.
+#[derive(Zeroize, ZeroizeOnDrop, Clone, Default, PartialEq, Eq)]
+pub struct CommittedRngBytes(Vec<u8>);
+
+impl CommittedRngBytes {
+    pub fn new(rng_entropy_bytes: Vec<u8>) -> Self {
+        CommittedRngBytes(rng_entropy_bytes)
+    }
+
+    pub fn get_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Debug for CommittedRngBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CommittedRngBytes({} bytes)", self.0.len())
+    }
+}
+
+fn commit(rng_entropy_bytes: &[u8]) -> Vec<u8> {
+    digest(&SHA256, rng_entropy_bytes).as_ref().to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reveal_works_01() {
+        let mut test = RngCommitment::new(&[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(
+            *test.get_commitment(),
+            digest(&SHA256, &[0xde, 0xad, 0xbe, 0xef]).as_ref().to_vec()
+        );
+        assert!(test.get_revealed_bytes().is_none());
+        assert_eq!(test.reveal(), vec![0xde, 0xad, 0xbe, 0xef]);
+        assert!(test.is_revealed());
+        let commitment = test.get_commitment().clone();
+        assert!(test.check_reveal(&commitment));
+        assert!(!test.check_reveal(&commit(&[0xde, 0xad, 0xbe, 0xee])));
+    }
+
+    #[test]
+    fn check_reveal_detects_swapped_bytes_01() {
+        let commitment = RngCommitment::new(&[1, 2, 3]);
+        let mut test = RngCommitment::with_commitment(commitment.get_commitment(), &[1, 2, 4]);
+        test.reveal();
+        assert!(!test.check_reveal(commitment.get_commitment()));
+        assert_eq!(*test.get_revealed_bytes().unwrap(), vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn debug_leaves_out_unrevealed_bytes_01() {
+        let mut test = RngCommitment::new(&[0xde, 0xad, 0xbe, 0xef]);
+        assert!(format!("{:?}", test).contains("rng_entropy_bytes: None"));
+        test.reveal();
+        assert!(format!("{:?}", test).contains("rng_entropy_bytes: Some([222, 173, 190, 239])"));
+        let bytes = CommittedRngBytes::new(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(format!("{:?}", bytes), "CommittedRngBytes(4 bytes)");
+    }
+}
@@ -0,0 +1,224 @@
+use std::any::type_name;
+
+use rand::{CryptoRng, RngCore};
+use ring::{
+    digest::{digest, SHA256},
+    rand::SecureRandom,
+};
+use zeroize::Zeroize;
+
+use crate::domain::process::error::ProcessError;
+
+/// Source of the rng bytes a session is mixed with. When a session is mixed with several
+/// sources, their outputs are XORed, so the mix is as good as the best of them.
+pub trait RngSource {
+    fn get_name(&self) -> String;
+
+    /// Fills `bytes` with random bytes, or tells why it could not.
+    fn fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), ProcessError>;
+}
+
+/// A [`RngSource`] backed by a `rand` rng, such as `rand::thread_rng()`.
+pub struct CryptoRngSource<R: RngCore + CryptoRng> {
+    rng: R,
+}
+
+impl<R: RngCore + CryptoRng> CryptoRngSource<R> {
+    pub fn new(rng: R) -> Self {
+        CryptoRngSource { rng }
+    }
+}
+
+impl<R: RngCore + CryptoRng> RngSource for CryptoRngSource<R> {
+    fn get_name(&self) -> String {
+        type_name::<R>().to_string()
+    }
+
+    fn fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), ProcessError> {
+        self.rng
+            .try_fill_bytes(bytes)
+            .map_err(|error| ProcessError::RngSourceFailed {
+                source: self.get_name(),
+                error: error.to_string(),
+            })
+    }
+}
+
+/// A [`RngSource`] backed by a `ring` rng, such as `ring::rand::SystemRandom`.
+pub struct SecureRandomSource<S: SecureRandom> {
+    secure_random: S,
+}
+
+impl<S: SecureRandom> SecureRandomSource<S> {
+    pub fn new(secure_random: S) -> Self {
+        SecureRandomSource { secure_random }
+    }
+}
+
+impl<S: SecureRandom> RngSource for SecureRandomSource<S> {
+    fn get_name(&self) -> String {
+        type_name::<S>().to_string()
+    }
+
+    fn fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), ProcessError> {
+        self.secure_random
+            .fill(bytes)
+            .map_err(|error| ProcessError::RngSourceFailed {
+                source: self.get_name(),
+                error: error.to_string(),
+            })
+    }
+}
+
+/// Gives the same bytes for the same seed: SHA-256 of the seed and a 8 byte big endian block
+/// counter, block after block. Meant for tests and for reproducing a mix, never for real
+/// entropy.
+#[derive(Debug, Clone, Zeroize)]
+pub struct DeterministicRngSource {
+    seed: Vec<u8>,
+    counter: u64,
+}
+
+impl DeterministicRngSource {
+    pub fn new(seed: &[u8]) -> Self {
+        DeterministicRngSource {
+            seed: seed.to_vec(),
+            counter: 0,
+        }
+    }
+}
+
+impl RngSource for DeterministicRngSource {
+    fn get_name(&self) -> String {
+        "deterministic".to_string()
+    }
+
+    fn fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), ProcessError> {
+        for chunk in bytes.chunks_mut(32) {
+            let mut block_input = self.seed.clone();
+            block_input.extend(self.counter.to_be_bytes());
+            let block = digest(&SHA256, &block_input);
+            chunk.copy_from_slice(&block.as_ref()[..chunk.len()]);
+            block_input.zeroize();
+            self.counter += 1;
+        }
+        Ok(())
+    }
+}
+
+/// XOR of `len` bytes from each of `sources`.
+pub fn fill_from_rng_sources(
+    sources: &mut [&mut dyn RngSource],
+    len: usize,
+) -> Result<Vec<u8>, ProcessError> {
+    if sources.is_empty() {
+        return Err(ProcessError::NoRngSourceGiven);
+    }
+    let mut mixed = vec![0u8; len];
+    let mut bytes = vec![0u8; len];
+    for source in sources.iter_mut() {
+        if let Err(error) = source.fill_bytes(&mut bytes) {
+            mixed.zeroize();
+            bytes.zeroize();
+            return Err(error);
+        }
+        mixed
+            .iter_mut()
+            .zip(bytes.iter())
+            .for_each(|(mixed_byte, byte)| *mixed_byte ^= byte);
+    }
+    bytes.zeroize();
+    Ok(mixed)
+}
+
+#[cfg(test)]
+mod test {
+    use ring::rand::SystemRandom;
+
+    use super::*;
+
+    struct FailingRng;
+
+    impl RngCore for FailingRng {
+        fn next_u32(&mut self) -> u32 {
+            0
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            0
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(0)
+        }
+
+        fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), rand::Error> {
+            Err(rand::Error::new("entropy pool is empty"))
+        }
+    }
+
+    impl CryptoRng for FailingRng {}
+
+    #[test]
+    fn deterministic_rng_source_works_01() {
+        let mut source = DeterministicRngSource::new(b"seed");
+        let mut bytes = [0u8; 40];
+        source.fill_bytes(&mut bytes).unwrap();
+        let mut first_block_input = b"seed".to_vec();
+        first_block_input.extend(0u64.to_be_bytes());
+        let mut second_block_input = b"seed".to_vec();
+        second_block_input.extend(1u64.to_be_bytes());
+        assert_eq!(bytes[..32], *digest(&SHA256, &first_block_input).as_ref());
+        assert_eq!(
+            bytes[32..],
+            digest(&SHA256, &second_block_input).as_ref()[..8]
+        );
+        let mut again = [0u8; 40];
+        DeterministicRngSource::new(b"seed")
+            .fill_bytes(&mut again)
+            .unwrap();
+        assert_eq!(bytes, again);
+    }
+
+    #[test]
+    fn fill_from_rng_sources_works_01() {
+        let mut first = DeterministicRngSource::new(b"first");
+        let mut second = DeterministicRngSource::new(b"second");
+        let test = fill_from_rng_sources(&mut [&mut first, &mut second], 16).unwrap();
+        let mut first_bytes = [0u8; 16];
+        DeterministicRngSource::new(b"first")
+            .fill_bytes(&mut first_bytes)
+            .unwrap();
+        let mut second_bytes = [0u8; 16];
+        DeterministicRngSource::new(b"second")
+            .fill_bytes(&mut second_bytes)
+            .unwrap();
+        let expected = first_bytes
+            .iter()
+            .zip(second_bytes.iter())
+            .map(|(a, b)| a ^ b)
+            .collect::<Vec<u8>>();
+        assert_eq!(test, expected);
+        let mut system_random = SecureRandomSource::new(SystemRandom::new());
+        assert_eq!(
+            fill_from_rng_sources(&mut [&mut system_random], 16)
+                .unwrap()
+                .len(),
+            16
+        );
+    }
+
+    #[test]
+    fn fill_from_rng_sources_emits_error_for_failing_source_01() {
+        let mut failing = CryptoRngSource::new(FailingRng);
+        let test = fill_from_rng_sources(&mut [&mut failing], 16);
+        assert!(matches!(
+            test.err().unwrap(),
+            ProcessError::RngSourceFailed { source, .. } if source.ends_with("FailingRng")
+        ));
+        assert_eq!(
+            fill_from_rng_sources(&mut [], 16).err().unwrap(),
+            ProcessError::NoRngSourceGiven
+        );
+    }
+}
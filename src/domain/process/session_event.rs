@@ -0,0 +1,49 @@
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::input::{DeckPermutation, NaturalDatumGroup};
+
+/// Something that happened in a session. The state of a [`crate::Tychentropy`] is derived from
+/// its log of events, which is what makes undoing and redoing exact.
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop)]
+pub enum SessionEvent {
+    DatumAdded(NaturalDatumGroup),
+    DeckPermutationAdded(DeckPermutation),
+    /// A datum that failed a health test. It was not added, but the failure it raised stays
+    /// until it is acknowledged.
+    DatumRejected(NaturalDatumGroup),
+    /// Keeps the rng bytes that were used, so the mix can be applied again on replay.
+    MixApplied {
+        rng_entropy_bytes: Vec<u8>,
+    },
+    MixReverted,
+    /// SHA-256 of the rng bytes the session is committed to before any data are added. The
+    /// bytes themselves are kept out of the history until they are revealed.
+    RngCommitted {
+        commitment: Vec<u8>,
+    },
+    /// The committed bytes were revealed and checked against the commitment the operator
+    /// wrote down before rolling. They are only mixed in when they match.
+    RngRevealed {
+        rng_entropy_bytes: Vec<u8>,
+        shown_commitment: Vec<u8>,
+        matches_commitment: bool,
+    },
+    HashedEntropyFinalized,
+    /// The operator saw the die bias warning, for example by swapping the dice. The bias
+    /// monitor starts again from the next datum.
+    DieBiasWarningAcknowledged,
+    /// The operator saw the health test failure, for example by swapping the dice. Data are
+    /// accepted again.
+    HealthTestFailureAcknowledged,
+    Reset,
+}
+
+impl SessionEvent {
+    /// Whether the event added data to the session.
+    pub fn is_data_event(&self) -> bool {
+        matches!(
+            self,
+            SessionEvent::DatumAdded(_) | SessionEvent::DeckPermutationAdded(_)
+        )
+    }
+}
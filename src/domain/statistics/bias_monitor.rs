@@ -0,0 +1,277 @@
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::error::StatisticsError;
+
+/// Largest range a [`SprtBiasMonitor`] watches. Every datum updates two tests for each face of
+/// its range, so the monitor's time and memory grow with the range.
+pub const MAX_SPRT_RANGE: u64 = 1024;
+
+/// Settings of the sequential probability ratio tests a [`SprtBiasMonitor`] runs.
+///
+/// The rates hold for a single test, that is one face and one direction until the test
+/// decides. A monitor runs two tests for every face and starts each again once it finds the
+/// die fair, so a session of fair rolls is warned more often than `false_alarm_rate`, and
+/// more often the longer it is. With the defaults about 0.4% of sessions of 200 fair d6 rolls
+/// get a warning.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct SprtConfig {
+    /// Bias to look for, as a fraction of the fair chance of a face. With `0.5`, a d6 face that
+    /// comes up with a chance of 1/4 or 1/12 instead of 1/6.
+    relative_bias: f64,
+    /// Chance that one test warns about a fair die before it finds the die fair. This is per
+    /// face, direction and restart of the test, not per session.
+    false_alarm_rate: f64,
+    /// Chance of missing a die that is biased by `relative_bias`.
+    missed_detection_rate: f64,
+}
+
+impl Default for SprtConfig {
+    fn default() -> Self {
+        SprtConfig {
+            relative_bias: 0.5,
+            false_alarm_rate: 0.001,
+            missed_detection_rate: 0.01,
+        }
+    }
+}
+
+impl SprtConfig {
+    pub fn new(
+        relative_bias: f64,
+        false_alarm_rate: f64,
+        missed_detection_rate: f64,
+    ) -> Result<Self, StatisticsError> {
+        let is_rate = |rate: f64| rate > 0.0 && rate < 1.0;
+        if !(is_rate(relative_bias)
+            && is_rate(false_alarm_rate)
+            && is_rate(missed_detection_rate)
+            && false_alarm_rate + missed_detection_rate < 1.0)
+        {
+            return Err(StatisticsError::SprtParametersAreIncorrect);
+        }
+        Ok(SprtConfig {
+            relative_bias,
+            false_alarm_rate,
+            missed_detection_rate,
+        })
+    }
+
+    /// Log likelihood ratio at which a die is taken to be biased, `ln((1 - beta) / alpha)`.
+    pub fn get_upper_threshold(&self) -> f64 {
+        ((1.0 - self.missed_detection_rate) / self.false_alarm_rate).ln()
+    }
+
+    /// Log likelihood ratio at which a die is taken to be fair, `ln(beta / (1 - alpha))`.
+    pub fn get_lower_threshold(&self) -> f64 {
+        (self.missed_detection_rate / (1.0 - self.false_alarm_rate)).ln()
+    }
+}
+
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, PartialEq, Eq)]
+pub enum BiasDirection {
+    Overrepresented,
+    Underrepresented,
+}
+
+/// Evidence that a face of the dice of a range comes up more or less often than it should.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct DieBiasWarning {
+    range: u64,
+    face: u64,
+    direction: BiasDirection,
+    /// Data of the range absorbed since the monitor was started when the warning was raised.
+    num_observations: u64,
+    log_likelihood_ratio: f64,
+}
+
+/// Log likelihood ratios of one range, one for each face and direction.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, PartialEq)]
+struct RangeSprtState {
+    range: u64,
+    num_observations: u64,
+    overrepresented_ratios: Vec<f64>,
+    underrepresented_ratios: Vec<f64>,
+}
+
+/// Watches the data as they are added for dice whose faces do not come up evenly.
+///
+/// For every face of every range two Wald sequential probability ratio tests run, one against
+/// a face that comes up `1 + relative_bias` times as often as it should and one against a face
+/// that comes up `1 - relative_bias` times as often. A test that finds the die fair starts
+/// again, so the monitor keeps watching for as long as data are added. Data of ranges above
+/// [`MAX_SPRT_RANGE`] are not watched.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+pub struct SprtBiasMonitor {
+    #[get = "pub with_prefix"]
+    config: SprtConfig,
+    range_states: Vec<RangeSprtState>,
+}
+
+impl SprtBiasMonitor {
+    pub fn new(config: SprtConfig) -> Self {
+        SprtBiasMonitor {
+            config,
+            range_states: vec![],
+        }
+    }
+
+    /// Feeds a datum from 1 to `range`. Returns a warning for the face whose test found the die
+    /// biased, if any.
+    pub fn absorb(&mut self, datum_value: u64, range: u64) -> Option<DieBiasWarning> {
+        if range > MAX_SPRT_RANGE {
+            return None;
+        }
+        let upper_threshold = self.config.get_upper_threshold();
+        let lower_threshold = self.config.get_lower_threshold();
+        let fair_chance = 1.0 / range as f64;
+        let over_chance = fair_chance * (1.0 + self.config.relative_bias);
+        let under_chance = fair_chance * (1.0 - self.config.relative_bias);
+        let state = self.get_range_state_mut(range);
+        state.num_observations += 1;
+        let mut warning = None;
+        for face in 1..=range {
+            let index = (face - 1) as usize;
+            for (direction, chance) in [
+                (BiasDirection::Overrepresented, over_chance),
+                (BiasDirection::Underrepresented, under_chance),
+            ] {
+                let increment = if face == datum_value {
+                    (chance / fair_chance).ln()
+                } else {
+                    ((1.0 - chance) / (1.0 - fair_chance)).ln()
+                };
+                let ratio = match direction {
+                    BiasDirection::Overrepresented => &mut state.overrepresented_ratios[index],
+                    BiasDirection::Underrepresented => &mut state.underrepresented_ratios[index],
+                };
+                *ratio += increment;
+                if *ratio >= upper_threshold {
+                    if warning.is_none() {
+                        warning = Some(DieBiasWarning {
+                            range,
+                            face,
+                            direction,
+                            num_observations: state.num_observations,
+                            log_likelihood_ratio: *ratio,
+                        });
+                    }
+                    *ratio = 0.0;
+                } else if *ratio <= lower_threshold {
+                    *ratio = 0.0;
+                }
+            }
+        }
+        warning
+    }
+
+    /// Forgets all data, for example after the dice are swapped.
+    pub fn restart(&mut self) {
+        self.range_states.clear();
+    }
+
+    fn get_range_state_mut(&mut self, range: u64) -> &mut RangeSprtState {
+        let index = match self
+            .range_states
+            .iter()
+            .position(|state| state.range == range)
+        {
+            Some(index) => index,
+            None => {
+                self.range_states.push(RangeSprtState {
+                    range,
+                    num_observations: 0,
+                    overrepresented_ratios: vec![0.0; range as usize],
+                    underrepresented_ratios: vec![0.0; range as usize],
+                });
+                self.range_states.len() - 1
+            }
+        };
+        &mut self.range_states[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::process::rng_source::{DeterministicRngSource, RngSource};
+
+    #[test]
+    fn sprt_config_new_emits_error_for_bad_parameters_01() {
+        assert!(SprtConfig::new(0.5, 0.001, 0.01).is_ok());
+        assert_eq!(
+            SprtConfig::new(1.0, 0.001, 0.01).err().unwrap(),
+            StatisticsError::SprtParametersAreIncorrect
+        );
+        assert!(SprtConfig::new(0.5, 0.6, 0.5).is_err());
+        assert!(SprtConfig::new(f64::NAN, 0.001, 0.01).is_err());
+    }
+
+    #[test]
+    fn absorb_warns_for_loaded_die_01() {
+        let mut monitor = SprtBiasMonitor::new(SprtConfig::default());
+        // Each 6 adds ln(1.5) to the ratio of face 6, which passes ln(990) on the 18th roll.
+        let warnings = (0..18)
+            .map(|_| monitor.absorb(6, 6))
+            .collect::<Vec<Option<DieBiasWarning>>>();
+        assert!(warnings[..17].iter().all(|warning| warning.is_none()));
+        let warning = warnings[17].clone().unwrap();
+        assert_eq!(warning.face, 6);
+        assert_eq!(warning.direction, BiasDirection::Overrepresented);
+        assert_eq!(warning.num_observations, 18);
+        assert!(warning.log_likelihood_ratio >= SprtConfig::default().get_upper_threshold());
+    }
+
+    #[test]
+    fn absorb_ignores_ranges_above_max_01() {
+        let mut monitor = SprtBiasMonitor::new(SprtConfig::default());
+        assert!((0..100).all(|_| monitor.absorb(1, 1 << 32).is_none()));
+        assert!(monitor.range_states.is_empty());
+    }
+
+    #[test]
+    fn absorb_keeps_quiet_for_even_faces_01() {
+        let mut monitor = SprtBiasMonitor::new(SprtConfig::default());
+        assert!((0..6000).all(|index| monitor.absorb(index % 6 + 1, 6).is_none()));
+    }
+
+    #[test]
+    fn absorb_rarely_warns_about_fair_dice_01() {
+        let mut bytes = vec![0u8; 204_000];
+        DeterministicRngSource::new(b"fair dice")
+            .fill_bytes(&mut bytes)
+            .unwrap();
+        let rolls = bytes
+            .iter()
+            .filter(|byte| **byte < 252)
+            .map(|byte| *byte as u64 % 6 + 1)
+            .collect::<Vec<u64>>();
+        let sessions = rolls.chunks_exact(200).take(1000).collect::<Vec<&[u64]>>();
+        assert_eq!(sessions.len(), 1000);
+        let warned_sessions = sessions
+            .iter()
+            .filter(|session| {
+                let mut monitor = SprtBiasMonitor::new(SprtConfig::default());
+                session
+                    .iter()
+                    .any(|roll| monitor.absorb(*roll, 6).is_some())
+            })
+            .count();
+        // Twelve restarting tests warn more often than the 0.1% of a single test, but still
+        // rarely.
+        assert!(warned_sessions <= 10, "{} sessions warned", warned_sessions);
+    }
+
+    #[test]
+    fn absorb_keeps_ranges_apart_01() {
+        let mut monitor = SprtBiasMonitor::new(SprtConfig::default());
+        for _ in 0..17 {
+            assert!(monitor.absorb(6, 6).is_none());
+        }
+        assert!(monitor.absorb(6, 8).is_none());
+        monitor.restart();
+        assert!(monitor.absorb(6, 6).is_none());
+    }
+}
@@ -8,8 +8,15 @@ pub enum StatisticsError {
         num_given_samples: u64,
         num_required_samples: u64,
     },
+    NotEnoughSamplesForMinEntropyEstimate {
+        num_given_samples: u64,
+        num_required_samples: u64,
+    },
     RangeBoundsDoNotFullyCoverAllObservations,
     RangeBoundsAreIncorrect,
+    SprtParametersAreIncorrect,
+    HealthTestFalsePositiveRateIsIncorrect,
+    SerialDependenceParametersAreIncorrect,
 
     BlockFrequencyTestError,
     LongestRunOfOnesTestError,
@@ -22,8 +29,12 @@ impl Display for StatisticsError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             StatisticsError::NotEnoughSamplesForChiSquaredTest { num_given_samples, num_required_samples } => write!(f, "Number of samples are {}, which is less than the minimum number of samples per possible outcome required to perform the chi-squared test, that is 5 times the number of possible outcomes or {} in this case.", num_given_samples, num_required_samples),
+            StatisticsError::NotEnoughSamplesForMinEntropyEstimate { num_given_samples, num_required_samples } => write!(f, "Number of samples are {}, which is less than the {} samples required to estimate min-entropy.", num_given_samples, num_required_samples),
             StatisticsError::RangeBoundsDoNotFullyCoverAllObservations => write!(f, "There are observations in data that are not present in the bases."),
             StatisticsError::RangeBoundsAreIncorrect => write!(f, "Provided range min and max do not form a correct inclusive range of more than one member."),
+            StatisticsError::SprtParametersAreIncorrect => write!(f, "The relative bias and the error rates of a sequential probability ratio test must be between 0 and 1, and the error rates must add up to less than 1."),
+            StatisticsError::HealthTestFalsePositiveRateIsIncorrect => write!(f, "The false positive rate of the health tests must be between 0 and 1."),
+            StatisticsError::SerialDependenceParametersAreIncorrect => write!(f, "The lag of an autocorrelation must be at least 1 and less than the number of observations, and the significance level must be between 0 and 1."),
             StatisticsError::BlockFrequencyTestError => write!(f, "Size of block must be lower than number of bits"),
             StatisticsError::LongestRunOfOnesTestError => write!(f, "At least 128 bits are required."),
             StatisticsError::NonOverlappingTemplateTestError => write!(f, "In this implementation for non-overlapping template test, 2 <= m <= 16"),
@@ -0,0 +1,294 @@
+use getset::Getters;
+use statrs::function::factorial::ln_binomial;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::error::StatisticsError;
+
+/// Window of the adaptive proportion test for coins.
+const BINARY_WINDOW_SIZE: u64 = 512;
+/// Window of the adaptive proportion test for any other dice.
+const NON_BINARY_WINDOW_SIZE: u64 = 1024;
+
+/// Settings of the continuous health tests of NIST SP 800-90B, section 4.4.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct HealthTestConfig {
+    /// Chance that a fair die fails a test, for each test and sample.
+    false_positive_rate: f64,
+}
+
+impl Default for HealthTestConfig {
+    fn default() -> Self {
+        HealthTestConfig {
+            false_positive_rate: 2f64.powi(-20),
+        }
+    }
+}
+
+impl HealthTestConfig {
+    pub fn new(false_positive_rate: f64) -> Result<Self, StatisticsError> {
+        if !(false_positive_rate > 0.0 && false_positive_rate < 1.0) {
+            return Err(StatisticsError::HealthTestFalsePositiveRateIsIncorrect);
+        }
+        Ok(HealthTestConfig {
+            false_positive_rate,
+        })
+    }
+
+    /// Repeats of a face in a row that fail the repetition count test,
+    /// `1 + ceil(-log2(false_positive_rate) / log2(range))`.
+    pub fn get_repetition_count_cutoff(&self, range: u64) -> u64 {
+        1 + (-self.false_positive_rate.log2() / (range as f64).log2()).ceil() as u64
+    }
+
+    pub fn get_adaptive_proportion_window_size(&self, range: u64) -> u64 {
+        if range == 2 {
+            BINARY_WINDOW_SIZE
+        } else {
+            NON_BINARY_WINDOW_SIZE
+        }
+    }
+
+    /// Counts of the first face of a window, within the window, that fail the adaptive
+    /// proportion test: one more than the smallest count a fair die exceeds with a chance of at
+    /// most `false_positive_rate`.
+    pub fn get_adaptive_proportion_cutoff(&self, range: u64) -> u64 {
+        let window_size = self.get_adaptive_proportion_window_size(range);
+        let chance = 1.0 / range as f64;
+        let mut exceeding_chance = 0.0;
+        for count in (0..=window_size).rev() {
+            if exceeding_chance > self.false_positive_rate {
+                return count + 2;
+            }
+            exceeding_chance += (ln_binomial(window_size, count)
+                + count as f64 * chance.ln()
+                + (window_size - count) as f64 * (1.0 - chance).ln())
+            .exp();
+        }
+        1
+    }
+}
+
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, PartialEq, Eq)]
+pub enum HealthTest {
+    RepetitionCount,
+    AdaptiveProportion,
+}
+
+impl HealthTest {
+    pub fn get_name(&self) -> String {
+        match self {
+            HealthTest::RepetitionCount => "repetition count".to_string(),
+            HealthTest::AdaptiveProportion => "adaptive proportion".to_string(),
+        }
+    }
+}
+
+/// A face of the dice of a range came up too often for a working die.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq, Eq)]
+#[get = "pub with_prefix"]
+pub struct HealthTestFailure {
+    test: HealthTest,
+    range: u64,
+    face: u64,
+    /// Times the face came up in a row, or in the window.
+    count: u64,
+    cutoff: u64,
+    /// Data of the range absorbed since the tests were started when the test failed.
+    num_observations: u64,
+}
+
+/// State of both tests for one range.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, PartialEq)]
+struct RangeHealthTestState {
+    range: u64,
+    num_observations: u64,
+    repetition_count_cutoff: u64,
+    adaptive_proportion_cutoff: u64,
+    window_size: u64,
+    repeated_face: u64,
+    repetition_count: u64,
+    window_face: u64,
+    window_count: u64,
+    /// Data absorbed in the current window, 0 before its first face.
+    window_position: u64,
+}
+
+/// Runs the repetition count and adaptive proportion tests on the data as they are added, with
+/// cutoffs for a fair die of each range. A range that fails a test starts both tests again.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+pub struct HealthTestMonitor {
+    #[get = "pub with_prefix"]
+    config: HealthTestConfig,
+    range_states: Vec<RangeHealthTestState>,
+}
+
+impl HealthTestMonitor {
+    pub fn new(config: HealthTestConfig) -> Self {
+        HealthTestMonitor {
+            config,
+            range_states: vec![],
+        }
+    }
+
+    /// Feeds a datum from 1 to `range`. Returns the failure of the repetition count test, or
+    /// else of the adaptive proportion test, if either fails.
+    pub fn absorb(&mut self, datum_value: u64, range: u64) -> Option<HealthTestFailure> {
+        let state = self.get_range_state_mut(range);
+        state.num_observations += 1;
+        if state.repetition_count > 0 && datum_value == state.repeated_face {
+            state.repetition_count += 1;
+        } else {
+            state.repeated_face = datum_value;
+            state.repetition_count = 1;
+        }
+        if state.window_position == 0 {
+            state.window_face = datum_value;
+            state.window_count = 0;
+        }
+        state.window_position += 1;
+        if datum_value == state.window_face {
+            state.window_count += 1;
+        }
+        let failure = if state.repetition_count >= state.repetition_count_cutoff {
+            Some(HealthTestFailure {
+                test: HealthTest::RepetitionCount,
+                range,
+                face: state.repeated_face,
+                count: state.repetition_count,
+                cutoff: state.repetition_count_cutoff,
+                num_observations: state.num_observations,
+            })
+        } else if state.window_count >= state.adaptive_proportion_cutoff {
+            Some(HealthTestFailure {
+                test: HealthTest::AdaptiveProportion,
+                range,
+                face: state.window_face,
+                count: state.window_count,
+                cutoff: state.adaptive_proportion_cutoff,
+                num_observations: state.num_observations,
+            })
+        } else {
+            None
+        };
+        if state.window_position == state.window_size {
+            state.window_position = 0;
+        }
+        if failure.is_some() {
+            self.range_states.retain(|state| state.range != range);
+        }
+        failure
+    }
+
+    /// Forgets all data, for example after the dice are swapped.
+    pub fn restart(&mut self) {
+        self.range_states.clear();
+    }
+
+    fn get_range_state_mut(&mut self, range: u64) -> &mut RangeHealthTestState {
+        let index = match self
+            .range_states
+            .iter()
+            .position(|state| state.range == range)
+        {
+            Some(index) => index,
+            None => {
+                self.range_states.push(RangeHealthTestState {
+                    range,
+                    num_observations: 0,
+                    repetition_count_cutoff: self.config.get_repetition_count_cutoff(range),
+                    adaptive_proportion_cutoff: self.config.get_adaptive_proportion_cutoff(range),
+                    window_size: self.config.get_adaptive_proportion_window_size(range),
+                    repeated_face: 0,
+                    repetition_count: 0,
+                    window_face: 0,
+                    window_count: 0,
+                    window_position: 0,
+                });
+                self.range_states.len() - 1
+            }
+        };
+        &mut self.range_states[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn health_test_config_new_emits_error_for_bad_rate_01() {
+        assert!(HealthTestConfig::new(2f64.powi(-40)).is_ok());
+        assert_eq!(
+            HealthTestConfig::new(0.0).err().unwrap(),
+            StatisticsError::HealthTestFalsePositiveRateIsIncorrect
+        );
+        assert!(HealthTestConfig::new(1.0).is_err());
+        assert!(HealthTestConfig::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn cutoffs_work_01() {
+        let config = HealthTestConfig::default();
+        assert_eq!(config.get_repetition_count_cutoff(2), 21);
+        assert_eq!(config.get_repetition_count_cutoff(6), 9);
+        assert_eq!(config.get_repetition_count_cutoff(20), 6);
+        assert_eq!(config.get_adaptive_proportion_window_size(2), 512);
+        assert_eq!(config.get_adaptive_proportion_cutoff(2), 311);
+        assert_eq!(config.get_adaptive_proportion_window_size(6), 1024);
+        assert_eq!(config.get_adaptive_proportion_cutoff(6), 231);
+        let strict = HealthTestConfig::new(2f64.powi(-40)).unwrap();
+        assert_eq!(strict.get_repetition_count_cutoff(6), 17);
+        assert_eq!(strict.get_adaptive_proportion_cutoff(4), 358);
+    }
+
+    #[test]
+    fn absorb_fails_repetition_count_test_01() {
+        let mut monitor = HealthTestMonitor::new(HealthTestConfig::default());
+        assert!(monitor.absorb(1, 6).is_none());
+        let failures = (0..9)
+            .map(|_| monitor.absorb(6, 6))
+            .collect::<Vec<Option<HealthTestFailure>>>();
+        assert!(failures[..8].iter().all(|failure| failure.is_none()));
+        let failure = failures[8].clone().unwrap();
+        assert_eq!(failure.test, HealthTest::RepetitionCount);
+        assert_eq!(failure.face, 6);
+        assert_eq!(failure.count, 9);
+        assert_eq!(failure.cutoff, 9);
+        assert_eq!(failure.num_observations, 10);
+        // The range starts again after a failure.
+        assert!(monitor.absorb(6, 6).is_none());
+    }
+
+    #[test]
+    fn absorb_fails_adaptive_proportion_test_01() {
+        let mut monitor = HealthTestMonitor::new(HealthTestConfig::default());
+        // Every other roll is a 1, which no run of repeats gives away.
+        let failure = (0..1024)
+            .find_map(|index| monitor.absorb(if index % 2 == 0 { 1 } else { 2 }, 6))
+            .unwrap();
+        assert_eq!(failure.test, HealthTest::AdaptiveProportion);
+        assert_eq!(failure.face, 1);
+        assert_eq!(failure.count, 231);
+        assert_eq!(failure.num_observations, 461);
+    }
+
+    #[test]
+    fn absorb_keeps_quiet_for_even_faces_01() {
+        let mut monitor = HealthTestMonitor::new(HealthTestConfig::default());
+        assert!((0..6000).all(|index| monitor.absorb(index % 6 + 1, 6).is_none()));
+        assert!((0..6000).all(|index| monitor.absorb(index % 2 + 1, 2).is_none()));
+    }
+
+    #[test]
+    fn absorb_keeps_ranges_apart_01() {
+        let mut monitor = HealthTestMonitor::new(HealthTestConfig::default());
+        for _ in 0..7 {
+            assert!(monitor.absorb(6, 6).is_none());
+            assert!(monitor.absorb(6, 8).is_none());
+        }
+        assert!(monitor.absorb(6, 6).is_none());
+        monitor.restart();
+        assert!(monitor.absorb(6, 8).is_none());
+    }
+}
@@ -0,0 +1,854 @@
+use std::collections::HashMap;
+
+use getset::Getters;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::error::StatisticsError;
+
+/// Quantile of the standard normal for the 99 % upper bounds of NIST SP 800-90B.
+const Z_ALPHA: f64 = 2.576;
+/// Least count a tuple needs to take part in the t-tuple estimate.
+const TUPLE_CUTOFF: u64 = 35;
+/// Bits in each block of the compression estimate.
+const COMPRESSION_BLOCK_BITS: usize = 6;
+/// Blocks of the compression estimate that only fill the dictionary.
+const COMPRESSION_DICTIONARY_BLOCKS: usize = 1000;
+const MULTI_MCW_WINDOWS: [usize; 4] = [63, 255, 1023, 4095];
+const LAG_MAX_DEPTH: usize = 128;
+const MULTI_MMC_MAX_DEPTH: usize = 16;
+const MULTI_MMC_MAX_ENTRIES: usize = 100_000;
+const LZ78Y_MAX_DEPTH: usize = 16;
+const LZ78Y_MAX_DICTIONARY_SIZE: usize = 65_536;
+
+/// The non-IID estimators of NIST SP 800-90B, section 6.3.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, PartialEq, Eq)]
+pub enum MinEntropyEstimator {
+    MostCommonValue,
+    Collision,
+    Markov,
+    Compression,
+    TTuple,
+    LongestRepeatedSubstring,
+    MultiMostCommonInWindow,
+    Lag,
+    MultiMarkovModelWithCounting,
+    Lz78y,
+}
+
+impl MinEntropyEstimator {
+    pub fn get_name(&self) -> String {
+        match self {
+            MinEntropyEstimator::MostCommonValue => "most common value".to_string(),
+            MinEntropyEstimator::Collision => "collision".to_string(),
+            MinEntropyEstimator::Markov => "Markov".to_string(),
+            MinEntropyEstimator::Compression => "compression".to_string(),
+            MinEntropyEstimator::TTuple => "t-tuple".to_string(),
+            MinEntropyEstimator::LongestRepeatedSubstring => {
+                "longest repeated substring".to_string()
+            }
+            MinEntropyEstimator::MultiMostCommonInWindow => "MultiMCW prediction".to_string(),
+            MinEntropyEstimator::Lag => "lag prediction".to_string(),
+            MinEntropyEstimator::MultiMarkovModelWithCounting => "MultiMMC prediction".to_string(),
+            MinEntropyEstimator::Lz78y => "LZ78Y prediction".to_string(),
+        }
+    }
+
+    /// Whether the estimator only takes bits, in which case it runs on the bits of the data.
+    pub fn is_binary_only(&self) -> bool {
+        matches!(
+            self,
+            MinEntropyEstimator::Collision
+                | MinEntropyEstimator::Markov
+                | MinEntropyEstimator::Compression
+        )
+    }
+}
+
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct MinEntropyEstimate {
+    estimator: MinEntropyEstimator,
+    /// `None` when there are too few data, or data too regular, for the estimator to apply.
+    min_entropy_bits_per_datum: Option<f64>,
+}
+
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct MinEntropyAssessment {
+    num_samples: u64,
+    num_faces: u64,
+    /// Bits each datum takes in the bit stream the binary only estimators run on.
+    bits_in_each_datum: u32,
+    estimates: Vec<MinEntropyEstimate>,
+    /// The lowest of the estimates.
+    min_entropy_bits_per_datum: f64,
+}
+
+/// Min-entropy estimates of NIST SP 800-90B for data that may not be independent and
+/// identically distributed. Estimates on symbols take `symbols` from 0 to `num_symbols - 1`,
+/// estimates on bits take `bits` that are 0 or 1. All give bits of min-entropy per sample, or
+/// `None` when they do not apply.
+pub struct MinEntropyEstimation;
+
+impl MinEntropyEstimation {
+    /// Runs every estimator over `observations`, each a face from `range_min` to `range_max`
+    /// inclusive, and assesses the min-entropy of a datum as the lowest estimate. The
+    /// collision, Markov and compression estimates run on the faces written in bits, as in
+    /// section 6.1 of SP 800-90B, and are scaled up to a datum.
+    pub fn assess(
+        observations: &[u64],
+        range_min: u64,
+        range_max: u64,
+    ) -> Result<MinEntropyAssessment, StatisticsError> {
+        if range_min >= range_max {
+            return Err(StatisticsError::RangeBoundsAreIncorrect);
+        }
+        let num_samples = observations.len() as u64;
+        if num_samples < 2 {
+            return Err(StatisticsError::NotEnoughSamplesForMinEntropyEstimate {
+                num_given_samples: num_samples,
+                num_required_samples: 2,
+            });
+        }
+        if observations
+            .iter()
+            .any(|observation| !(range_min..=range_max).contains(observation))
+        {
+            return Err(StatisticsError::RangeBoundsDoNotFullyCoverAllObservations);
+        }
+        let num_faces = range_max - range_min + 1;
+        let num_symbols = num_faces as usize;
+        let bits_in_each_datum = (num_faces - 1).ilog2() + 1;
+        let symbols = observations
+            .iter()
+            .map(|observation| (observation - range_min) as usize)
+            .collect::<Vec<usize>>();
+        let bits = symbols
+            .iter()
+            .flat_map(|symbol| {
+                (0..bits_in_each_datum)
+                    .rev()
+                    .map(move |index| ((symbol >> index) & 1) as u8)
+            })
+            .collect::<Vec<u8>>();
+        let per_datum = |min_entropy_per_bit: Option<f64>| {
+            min_entropy_per_bit.map(|min_entropy| min_entropy * bits_in_each_datum as f64)
+        };
+        let estimates = vec![
+            (
+                MinEntropyEstimator::MostCommonValue,
+                MinEntropyEstimation::most_common_value_estimate(&symbols, num_symbols),
+            ),
+            (
+                MinEntropyEstimator::Collision,
+                per_datum(MinEntropyEstimation::collision_estimate(&bits)),
+            ),
+            (
+                MinEntropyEstimator::Markov,
+                per_datum(MinEntropyEstimation::markov_estimate(&bits)),
+            ),
+            (
+                MinEntropyEstimator::Compression,
+                per_datum(MinEntropyEstimation::compression_estimate(&bits)),
+            ),
+            (
+                MinEntropyEstimator::TTuple,
+                MinEntropyEstimation::t_tuple_estimate(&symbols),
+            ),
+            (
+                MinEntropyEstimator::LongestRepeatedSubstring,
+                MinEntropyEstimation::longest_repeated_substring_estimate(&symbols),
+            ),
+            (
+                MinEntropyEstimator::MultiMostCommonInWindow,
+                MinEntropyEstimation::multi_mcw_prediction_estimate(&symbols, num_symbols),
+            ),
+            (
+                MinEntropyEstimator::Lag,
+                MinEntropyEstimation::lag_prediction_estimate(&symbols, num_symbols),
+            ),
+            (
+                MinEntropyEstimator::MultiMarkovModelWithCounting,
+                MinEntropyEstimation::multi_mmc_prediction_estimate(&symbols, num_symbols),
+            ),
+            (
+                MinEntropyEstimator::Lz78y,
+                MinEntropyEstimation::lz78y_prediction_estimate(&symbols, num_symbols),
+            ),
+        ]
+        .into_iter()
+        .map(
+            |(estimator, min_entropy_bits_per_datum)| MinEntropyEstimate {
+                estimator,
+                min_entropy_bits_per_datum,
+            },
+        )
+        .collect::<Vec<MinEntropyEstimate>>();
+        let min_entropy_bits_per_datum = estimates
+            .iter()
+            .filter_map(|estimate| estimate.min_entropy_bits_per_datum)
+            .fold(f64::INFINITY, f64::min);
+        Ok(MinEntropyAssessment {
+            num_samples,
+            num_faces,
+            bits_in_each_datum,
+            estimates,
+            min_entropy_bits_per_datum,
+        })
+    }
+
+    /// Section 6.3.1.
+    pub fn most_common_value_estimate(symbols: &[usize], num_symbols: usize) -> Option<f64> {
+        if symbols.len() < 2 {
+            return None;
+        }
+        let mut counts = vec![0u64; num_symbols];
+        symbols.iter().for_each(|symbol| counts[*symbol] += 1);
+        let max_count = *counts.iter().max()?;
+        let proportion = max_count as f64 / symbols.len() as f64;
+        Some(min_entropy(upper_bound(proportion, symbols.len())))
+    }
+
+    /// Section 6.3.2.
+    pub fn collision_estimate(bits: &[u8]) -> Option<f64> {
+        let mut collision_times = vec![];
+        let mut index = 0;
+        while index + 1 < bits.len() {
+            if bits[index] == bits[index + 1] {
+                collision_times.push(2.0);
+                index += 2;
+            } else if index + 2 < bits.len() {
+                // Of three bits, two are always the same.
+                collision_times.push(3.0);
+                index += 3;
+            } else {
+                break;
+            }
+        }
+        let num_collisions = collision_times.len();
+        if num_collisions < 2 {
+            return None;
+        }
+        let mean = collision_times.iter().sum::<f64>() / num_collisions as f64;
+        let standard_deviation = (collision_times
+            .iter()
+            .map(|time| (time - mean).powi(2))
+            .sum::<f64>()
+            / (num_collisions - 1) as f64)
+            .sqrt();
+        let lower_mean = mean - Z_ALPHA * standard_deviation / (num_collisions as f64).sqrt();
+        // A bit that is 1 with chance p has a mean collision time of 2 + 2 * p * (1 - p).
+        let probability = if lower_mean >= 2.5 {
+            0.5
+        } else {
+            (0.5 + (1.25 - 0.5 * lower_mean).max(0.0).sqrt()).min(1.0)
+        };
+        Some(min_entropy(probability))
+    }
+
+    /// Section 6.3.3.
+    pub fn markov_estimate(bits: &[u8]) -> Option<f64> {
+        if bits.len() < 2 {
+            return None;
+        }
+        let mut transition_counts = [[0u64; 2]; 2];
+        bits.windows(2)
+            .for_each(|pair| transition_counts[pair[0] as usize][pair[1] as usize] += 1);
+        let num_ones = bits.iter().filter(|bit| **bit == 1).count();
+        let log_first = [
+            ((bits.len() - num_ones) as f64 / bits.len() as f64).log2(),
+            (num_ones as f64 / bits.len() as f64).log2(),
+        ];
+        let log_transition = |from: usize, to: usize| {
+            let from_total = transition_counts[from][0] + transition_counts[from][1];
+            if from_total == 0 {
+                f64::NEG_INFINITY
+            } else {
+                (transition_counts[from][to] as f64 / from_total as f64).log2()
+            }
+        };
+        // Log chances of the six 128 bit sequences that can be the likeliest.
+        let max_log_chance = [
+            log_first[0] + 127.0 * log_transition(0, 0),
+            log_first[0] + 64.0 * log_transition(0, 1) + 63.0 * log_transition(1, 0),
+            log_first[0] + log_transition(0, 1) + 126.0 * log_transition(1, 1),
+            log_first[1] + log_transition(1, 0) + 126.0 * log_transition(0, 0),
+            log_first[1] + 64.0 * log_transition(1, 0) + 63.0 * log_transition(0, 1),
+            log_first[1] + 127.0 * log_transition(1, 1),
+        ]
+        .into_iter()
+        .fold(f64::NEG_INFINITY, f64::max);
+        Some((-max_log_chance / 128.0).min(1.0))
+    }
+
+    /// Section 6.3.4. Needs 1002 blocks of 6 bits.
+    pub fn compression_estimate(bits: &[u8]) -> Option<f64> {
+        let num_blocks = bits.len() / COMPRESSION_BLOCK_BITS;
+        if num_blocks < COMPRESSION_DICTIONARY_BLOCKS + 2 {
+            return None;
+        }
+        let num_test_blocks = num_blocks - COMPRESSION_DICTIONARY_BLOCKS;
+        let mut last_seen = vec![0usize; 1 << COMPRESSION_BLOCK_BITS];
+        let mut log_distances = Vec::with_capacity(num_test_blocks);
+        for (index, block) in
+            bits.chunks_exact(COMPRESSION_BLOCK_BITS)
+                .enumerate()
+                .map(|(index, block)| {
+                    let value = block
+                        .iter()
+                        .fold(0usize, |value, bit| (value << 1) | *bit as usize);
+                    (index + 1, value)
+                })
+        {
+            if index > COMPRESSION_DICTIONARY_BLOCKS {
+                let distance = index - last_seen[block];
+                log_distances.push((distance as f64).log2());
+            }
+            last_seen[block] = index;
+        }
+        let num_test_blocks_f64 = num_test_blocks as f64;
+        let mean = log_distances.iter().sum::<f64>() / num_test_blocks_f64;
+        let variance = log_distances
+            .iter()
+            .map(|log_distance| log_distance.powi(2))
+            .sum::<f64>()
+            / (num_test_blocks_f64 - 1.0)
+            - mean.powi(2);
+        let standard_deviation = 0.5907 * variance.max(0.0).sqrt();
+        let lower_mean = mean - Z_ALPHA * standard_deviation / num_test_blocks_f64.sqrt();
+        let num_other_blocks = ((1 << COMPRESSION_BLOCK_BITS) - 1) as f64;
+        let expected_log_distance = |probability: f64| {
+            expected_compression_log_distance(probability, num_blocks, num_test_blocks)
+                + num_other_blocks
+                    * expected_compression_log_distance(
+                        (1.0 - probability) / num_other_blocks,
+                        num_blocks,
+                        num_test_blocks,
+                    )
+        };
+        let probability = solve_decreasing(
+            expected_log_distance,
+            lower_mean,
+            1.0 / (1 << COMPRESSION_BLOCK_BITS) as f64,
+            1.0,
+        );
+        Some(min_entropy(probability) / COMPRESSION_BLOCK_BITS as f64)
+    }
+
+    /// Section 6.3.5. Needs a symbol that comes up 35 times.
+    pub fn t_tuple_estimate(symbols: &[usize]) -> Option<f64> {
+        let max_counts = repeated_tuple_counts(symbols)
+            .iter()
+            .map(|counts| *counts.iter().max().unwrap_or(&0))
+            .take_while(|max_count| *max_count >= TUPLE_CUTOFF)
+            .collect::<Vec<u64>>();
+        if max_counts.is_empty() {
+            return None;
+        }
+        let max_proportion = max_counts
+            .iter()
+            .enumerate()
+            .map(|(index, max_count)| {
+                let tuple_len = index + 1;
+                (*max_count as f64 / (symbols.len() - tuple_len + 1) as f64)
+                    .powf(1.0 / tuple_len as f64)
+            })
+            .fold(0.0, f64::max);
+        Some(min_entropy(upper_bound(max_proportion, symbols.len())))
+    }
+
+    /// Section 6.3.6. Needs a repeated tuple longer than the longest the t-tuple estimate uses.
+    pub fn longest_repeated_substring_estimate(symbols: &[usize]) -> Option<f64> {
+        let tuple_counts = repeated_tuple_counts(symbols);
+        let first_tuple_len = tuple_counts
+            .iter()
+            .take_while(|counts| *counts.iter().max().unwrap_or(&0) >= TUPLE_CUTOFF)
+            .count()
+            + 1;
+        if first_tuple_len > tuple_counts.len() {
+            return None;
+        }
+        let pairs = |count: f64| count * (count - 1.0) / 2.0;
+        let max_proportion = tuple_counts
+            .iter()
+            .enumerate()
+            .skip(first_tuple_len - 1)
+            .map(|(index, counts)| {
+                let tuple_len = index + 1;
+                let collision_chance = counts.iter().map(|count| pairs(*count as f64)).sum::<f64>()
+                    / pairs((symbols.len() - tuple_len + 1) as f64);
+                collision_chance.powf(1.0 / tuple_len as f64)
+            })
+            .fold(0.0, f64::max);
+        Some(min_entropy(upper_bound(max_proportion, symbols.len())))
+    }
+
+    /// Section 6.3.7. Needs more than 64 symbols.
+    pub fn multi_mcw_prediction_estimate(symbols: &[usize], num_symbols: usize) -> Option<f64> {
+        let first_window = MULTI_MCW_WINDOWS[0];
+        if symbols.len() <= first_window + 1 {
+            return None;
+        }
+        let mut window_counts = vec![vec![0u64; num_symbols]; MULTI_MCW_WINDOWS.len()];
+        let mut last_seen = vec![0usize; num_symbols];
+        for (position, symbol) in symbols[..first_window].iter().enumerate() {
+            window_counts
+                .iter_mut()
+                .for_each(|counts| counts[*symbol] += 1);
+            last_seen[*symbol] = position;
+        }
+        let mut scoreboard = [0u64; MULTI_MCW_WINDOWS.len()];
+        let mut winner = 0;
+        let mut correct = Vec::with_capacity(symbols.len() - first_window);
+        for position in first_window..symbols.len() {
+            let predictions = MULTI_MCW_WINDOWS
+                .iter()
+                .zip(window_counts.iter())
+                .map(|(window, counts)| {
+                    (position >= *window).then(|| {
+                        // Ties go to the symbol seen last.
+                        (0..num_symbols)
+                            .max_by_key(|symbol| (counts[*symbol], last_seen[*symbol]))
+                            .unwrap_or(0)
+                    })
+                })
+                .collect::<Vec<Option<usize>>>();
+            let symbol = symbols[position];
+            correct.push(predictions[winner] == Some(symbol));
+            update_scoreboard(&mut scoreboard, &mut winner, &predictions, symbol);
+            for (window, counts) in MULTI_MCW_WINDOWS.iter().zip(window_counts.iter_mut()) {
+                counts[symbol] += 1;
+                if position >= *window {
+                    counts[symbols[position - window]] -= 1;
+                }
+            }
+            last_seen[symbol] = position;
+        }
+        prediction_min_entropy(&correct, num_symbols)
+    }
+
+    /// Section 6.3.8.
+    pub fn lag_prediction_estimate(symbols: &[usize], num_symbols: usize) -> Option<f64> {
+        if symbols.len() < 3 {
+            return None;
+        }
+        let mut scoreboard = [0u64; LAG_MAX_DEPTH];
+        let mut winner = 0;
+        let mut correct = Vec::with_capacity(symbols.len() - 1);
+        for position in 1..symbols.len() {
+            let predictions = (1..=LAG_MAX_DEPTH)
+                .map(|lag| (lag <= position).then(|| symbols[position - lag]))
+                .collect::<Vec<Option<usize>>>();
+            let symbol = symbols[position];
+            correct.push(predictions[winner] == Some(symbol));
+            update_scoreboard(&mut scoreboard, &mut winner, &predictions, symbol);
+        }
+        prediction_min_entropy(&correct, num_symbols)
+    }
+
+    /// Section 6.3.9.
+    pub fn multi_mmc_prediction_estimate(symbols: &[usize], num_symbols: usize) -> Option<f64> {
+        if symbols.len() < 4 {
+            return None;
+        }
+        let mut models = vec![HashMap::<&[usize], HashMap<usize, u64>>::new(); MULTI_MMC_MAX_DEPTH];
+        let mut scoreboard = [0u64; MULTI_MMC_MAX_DEPTH];
+        let mut winner = 0;
+        let mut correct = Vec::with_capacity(symbols.len() - 2);
+        for position in 2..symbols.len() {
+            for (depth, model) in (1..=MULTI_MMC_MAX_DEPTH).zip(models.iter_mut()) {
+                if depth < position {
+                    let context = &symbols[position - depth - 1..position - 1];
+                    if model.contains_key(context) || model.len() < MULTI_MMC_MAX_ENTRIES {
+                        *model
+                            .entry(context)
+                            .or_default()
+                            .entry(symbols[position - 1])
+                            .or_default() += 1;
+                    }
+                }
+            }
+            let predictions = (1..=MULTI_MMC_MAX_DEPTH)
+                .zip(models.iter())
+                .map(|(depth, model)| {
+                    if depth > position {
+                        return None;
+                    }
+                    model
+                        .get(&symbols[position - depth..position])
+                        .and_then(most_frequent_next_symbol)
+                        .map(|(next_symbol, _)| next_symbol)
+                })
+                .collect::<Vec<Option<usize>>>();
+            let symbol = symbols[position];
+            correct.push(predictions[winner] == Some(symbol));
+            update_scoreboard(&mut scoreboard, &mut winner, &predictions, symbol);
+        }
+        prediction_min_entropy(&correct, num_symbols)
+    }
+
+    /// Section 6.3.10. Needs more than 18 symbols.
+    pub fn lz78y_prediction_estimate(symbols: &[usize], num_symbols: usize) -> Option<f64> {
+        if symbols.len() < LZ78Y_MAX_DEPTH + 3 {
+            return None;
+        }
+        let mut dictionary = HashMap::<&[usize], HashMap<usize, u64>>::new();
+        let mut correct = Vec::with_capacity(symbols.len() - LZ78Y_MAX_DEPTH - 1);
+        for position in LZ78Y_MAX_DEPTH + 1..symbols.len() {
+            for depth in (1..=LZ78Y_MAX_DEPTH).rev() {
+                let context = &symbols[position - depth - 1..position - 1];
+                if dictionary.contains_key(context) || dictionary.len() < LZ78Y_MAX_DICTIONARY_SIZE
+                {
+                    *dictionary
+                        .entry(context)
+                        .or_default()
+                        .entry(symbols[position - 1])
+                        .or_default() += 1;
+                }
+            }
+            let mut prediction = None;
+            let mut max_count = 0;
+            for depth in (1..=LZ78Y_MAX_DEPTH).rev() {
+                if let Some((next_symbol, count)) = dictionary
+                    .get(&symbols[position - depth..position])
+                    .and_then(most_frequent_next_symbol)
+                {
+                    if count > max_count {
+                        prediction = Some(next_symbol);
+                        max_count = count;
+                    }
+                }
+            }
+            correct.push(prediction == Some(symbols[position]));
+        }
+        prediction_min_entropy(&correct, num_symbols)
+    }
+}
+
+/// `-log2(probability)`, written so that a certain outcome gives 0 rather than -0.
+fn min_entropy(probability: f64) -> f64 {
+    0.0 - probability.log2()
+}
+
+/// `min(1, proportion + z * sqrt(proportion * (1 - proportion) / (num_samples - 1)))`.
+fn upper_bound(proportion: f64, num_samples: usize) -> f64 {
+    (proportion + Z_ALPHA * (proportion * (1.0 - proportion) / (num_samples - 1) as f64).sqrt())
+        .min(1.0)
+}
+
+/// For tuple lengths 1, 2 and so on, as long as some tuple repeats, the counts of the tuples
+/// of that length that come up more than once, overlaps included.
+fn repeated_tuple_counts(symbols: &[usize]) -> Vec<Vec<u64>> {
+    let mut tuple_counts = vec![];
+    // Start positions of tuples that are equal up to the current length.
+    let mut groups = vec![(0..symbols.len()).collect::<Vec<usize>>()];
+    let mut tuple_len = 0;
+    loop {
+        let mut next_groups = vec![];
+        for group in groups.iter() {
+            let mut by_next_symbol = HashMap::<usize, Vec<usize>>::new();
+            group
+                .iter()
+                .filter(|start| *start + tuple_len < symbols.len())
+                .for_each(|start| {
+                    by_next_symbol
+                        .entry(symbols[start + tuple_len])
+                        .or_default()
+                        .push(*start)
+                });
+            next_groups.extend(
+                by_next_symbol
+                    .into_values()
+                    .filter(|next_group| next_group.len() > 1),
+            );
+        }
+        if next_groups.is_empty() {
+            return tuple_counts;
+        }
+        tuple_counts.push(next_groups.iter().map(|group| group.len() as u64).collect());
+        groups = next_groups;
+        tuple_len += 1;
+    }
+}
+
+/// Mean over the test blocks of the expected log distance of a block value with chance
+/// `probability`, the function G of section 6.3.4.
+fn expected_compression_log_distance(
+    probability: f64,
+    num_blocks: usize,
+    num_test_blocks: usize,
+) -> f64 {
+    let first_test_block = num_blocks - num_test_blocks + 1;
+    let mut expected = 0.0;
+    // Sum of log2(u) * (1 - probability)^(u - 1) for u below the current distance.
+    let mut shorter_distances_sum = 0.0;
+    let mut miss_chance = 1.0;
+    for distance in 1..=num_blocks {
+        let log_distance = (distance as f64).log2();
+        if distance >= first_test_block {
+            expected += probability.powi(2) * shorter_distances_sum
+                + probability * log_distance * miss_chance;
+        }
+        shorter_distances_sum += log_distance * miss_chance;
+        miss_chance *= 1.0 - probability;
+    }
+    expected / num_test_blocks as f64
+}
+
+/// The `x` from `min` to `max` where `function`, decreasing in `x`, meets `target`, or the
+/// bound nearest to it.
+fn solve_decreasing<F: Fn(f64) -> f64>(function: F, target: f64, min: f64, max: f64) -> f64 {
+    if function(min) <= target {
+        return min;
+    }
+    if function(max) >= target {
+        return max;
+    }
+    let (mut low, mut high) = (min, max);
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if function(middle) > target {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// The next symbol with the highest count, and the count. Ties go to the larger symbol.
+fn most_frequent_next_symbol(counts: &HashMap<usize, u64>) -> Option<(usize, u64)> {
+    counts
+        .iter()
+        .map(|(symbol, count)| (*symbol, *count))
+        .max_by_key(|(symbol, count)| (*count, *symbol))
+}
+
+/// Credits the subpredictors that were right and makes the best one so far the winner. Ties
+/// go to the later subpredictor.
+fn update_scoreboard(
+    scoreboard: &mut [u64],
+    winner: &mut usize,
+    predictions: &[Option<usize>],
+    symbol: usize,
+) {
+    for (index, prediction) in predictions.iter().enumerate() {
+        if *prediction == Some(symbol) {
+            scoreboard[index] += 1;
+            if scoreboard[index] >= scoreboard[*winner] {
+                *winner = index;
+            }
+        }
+    }
+}
+
+/// Min-entropy from the predictions a predictor got right, using the higher of the global
+/// and the local chance of a right prediction, section 6.3.7 steps 4 to 7.
+fn prediction_min_entropy(correct: &[bool], num_symbols: usize) -> Option<f64> {
+    let num_predictions = correct.len();
+    if num_predictions < 2 {
+        return None;
+    }
+    let num_correct = correct.iter().filter(|is_correct| **is_correct).count();
+    let global_chance = if num_correct == 0 {
+        1.0 - 0.01f64.powf(1.0 / num_predictions as f64)
+    } else {
+        upper_bound(num_correct as f64 / num_predictions as f64, num_predictions)
+    };
+    let longest_run = correct
+        .split(|is_correct| !is_correct)
+        .map(|run| run.len())
+        .max()
+        .unwrap_or(0);
+    let local_chance = local_prediction_chance(longest_run as u64 + 1, num_predictions as u64);
+    Some(min_entropy(
+        global_chance
+            .max(local_chance)
+            .max(1.0 / num_symbols as f64),
+    ))
+}
+
+/// The chance of a right prediction at which `num_predictions` have no run of
+/// `run_len` right predictions with a chance of 99 %.
+fn local_prediction_chance(run_len: u64, num_predictions: u64) -> f64 {
+    let no_run_chance = |chance: f64| {
+        let miss_chance = 1.0 - chance;
+        let mut root = 1.0f64;
+        for _ in 0..10 {
+            root = 1.0 + miss_chance * chance.powi(run_len as i32) * root.powi(run_len as i32 + 1);
+        }
+        (1.0 - chance * root)
+            / ((run_len as f64 + 1.0 - run_len as f64 * root) * miss_chance)
+            / root.powf(num_predictions as f64 + 1.0)
+    };
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if no_run_chance(middle) > 0.99 {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::process::rng_source::{DeterministicRngSource, RngSource};
+
+    fn random_symbols(seed: &[u8], len: usize, num_symbols: usize) -> Vec<usize> {
+        let mut bytes = vec![0u8; len];
+        DeterministicRngSource::new(seed)
+            .fill_bytes(&mut bytes)
+            .unwrap();
+        bytes
+            .iter()
+            .map(|byte| *byte as usize % num_symbols)
+            .collect()
+    }
+
+    #[test]
+    fn most_common_value_estimate_works_01() {
+        let symbols = [
+            vec![0; 30],
+            vec![1; 14],
+            vec![2; 14],
+            vec![3; 14],
+            vec![4; 14],
+            vec![5; 14],
+        ]
+        .concat();
+        let test = MinEntropyEstimation::most_common_value_estimate(&symbols, 6).unwrap();
+        assert!((test - 1.2562115567213012).abs() < 1e-12);
+        assert!(MinEntropyEstimation::most_common_value_estimate(&[1], 6).is_none());
+    }
+
+    #[test]
+    fn collision_estimate_works_01() {
+        let bits = [0, 1, 1, 0, 1, 0, 0, 1].repeat(5);
+        let test = MinEntropyEstimation::collision_estimate(&bits).unwrap();
+        assert!((test - 0.44833958779109057).abs() < 1e-12);
+        assert_eq!(
+            MinEntropyEstimation::collision_estimate(&[0; 20]).unwrap(),
+            0.0
+        );
+        assert!(MinEntropyEstimation::collision_estimate(&[0, 1, 1]).is_none());
+    }
+
+    #[test]
+    fn markov_estimate_works_01() {
+        let bits = [0, 0, 0, 1, 1, 0, 0, 0, 1, 0, 0, 0, 0, 1, 1, 0];
+        let test = MinEntropyEstimation::markov_estimate(&bits).unwrap();
+        assert!((test - 0.514776260396422).abs() < 1e-12);
+        // Bits that always flip give two sequences of 128 bits with a chance of 1/2 each.
+        let flipping = [0, 1].repeat(50);
+        let flipping_test = MinEntropyEstimation::markov_estimate(&flipping).unwrap();
+        assert!((flipping_test - 1.0 / 128.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn compression_estimate_works_01() {
+        let constant = vec![1u8; 6 * 1100];
+        assert_eq!(
+            MinEntropyEstimation::compression_estimate(&constant).unwrap(),
+            0.0
+        );
+        let random = random_symbols(b"compression", 6 * 3000, 2)
+            .iter()
+            .map(|bit| *bit as u8)
+            .collect::<Vec<u8>>();
+        let test = MinEntropyEstimation::compression_estimate(&random).unwrap();
+        assert!(test > 0.5 && test <= 1.0);
+        assert!(MinEntropyEstimation::compression_estimate(&random[..6006]).is_none());
+    }
+
+    #[test]
+    fn tuple_estimates_work_01() {
+        let constant = vec![3; 100];
+        assert_eq!(
+            MinEntropyEstimation::t_tuple_estimate(&constant).unwrap(),
+            0.0
+        );
+        assert_eq!(
+            MinEntropyEstimation::longest_repeated_substring_estimate(&constant).unwrap(),
+            0.0
+        );
+        let random = random_symbols(b"tuples", 1500, 6);
+        let t_tuple = MinEntropyEstimation::t_tuple_estimate(&random).unwrap();
+        let longest_repeated_substring =
+            MinEntropyEstimation::longest_repeated_substring_estimate(&random).unwrap();
+        assert!(t_tuple > 1.8 && t_tuple < 6f64.log2());
+        assert!(longest_repeated_substring > 1.8 && longest_repeated_substring < 6f64.log2());
+        let short = (0..30).map(|index| index % 6).collect::<Vec<usize>>();
+        assert!(MinEntropyEstimation::t_tuple_estimate(&short).is_none());
+        assert!(MinEntropyEstimation::longest_repeated_substring_estimate(&[0, 1, 2]).is_none());
+    }
+
+    type SymbolEstimator = fn(&[usize], usize) -> Option<f64>;
+
+    #[test]
+    fn prediction_estimates_work_01() {
+        let random = random_symbols(b"predictions", 1500, 6);
+        let periodic = (0..1500).map(|index| index % 6).collect::<Vec<usize>>();
+        let estimators: [SymbolEstimator; 4] = [
+            MinEntropyEstimation::multi_mcw_prediction_estimate,
+            MinEntropyEstimation::lag_prediction_estimate,
+            MinEntropyEstimation::multi_mmc_prediction_estimate,
+            MinEntropyEstimation::lz78y_prediction_estimate,
+        ];
+        for estimator in estimators {
+            let random_test = estimator(&random, 6).unwrap();
+            assert!(random_test > 1.8 && random_test <= 6f64.log2());
+            assert!(estimator(&[0, 1], 6).is_none());
+        }
+        // All but MultiMCW learn to predict the next face of a die turned one face up each time.
+        for estimator in &estimators[1..] {
+            assert!(estimator(&periodic, 6).unwrap() < 0.05);
+        }
+    }
+
+    #[test]
+    fn assess_works_01() {
+        let observations = random_symbols(b"assessment", 1500, 6)
+            .iter()
+            .map(|symbol| *symbol as u64 + 1)
+            .collect::<Vec<u64>>();
+        let test = MinEntropyEstimation::assess(&observations, 1, 6).unwrap();
+        assert_eq!(test.num_samples, 1500);
+        assert_eq!(test.bits_in_each_datum, 3);
+        assert_eq!(test.estimates.len(), 10);
+        assert!(test
+            .estimates
+            .iter()
+            .all(|estimate| estimate.min_entropy_bits_per_datum.is_some()
+                || estimate.estimator == MinEntropyEstimator::Compression));
+        let lowest = test
+            .estimates
+            .iter()
+            .filter_map(|estimate| estimate.min_entropy_bits_per_datum)
+            .fold(f64::INFINITY, f64::min);
+        assert_eq!(test.min_entropy_bits_per_datum, lowest);
+        assert!(test.min_entropy_bits_per_datum < 6f64.log2());
+    }
+
+    #[test]
+    fn assess_emits_errors_01() {
+        assert_eq!(
+            MinEntropyEstimation::assess(&[1], 1, 6).err().unwrap(),
+            StatisticsError::NotEnoughSamplesForMinEntropyEstimate {
+                num_given_samples: 1,
+                num_required_samples: 2
+            }
+        );
+        assert_eq!(
+            MinEntropyEstimation::assess(&[1, 7], 1, 6).err().unwrap(),
+            StatisticsError::RangeBoundsDoNotFullyCoverAllObservations
+        );
+        assert_eq!(
+            MinEntropyEstimation::assess(&[1, 1], 6, 1).err().unwrap(),
+            StatisticsError::RangeBoundsAreIncorrect
+        );
+    }
+}
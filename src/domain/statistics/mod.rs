@@ -1,3 +1,7 @@
 pub mod uniform_random_test;
+pub mod bias_monitor;
+pub mod health_test;
+pub mod raw_datum_test;
+pub mod min_entropy_estimate;
 pub mod error;
 pub mod statistical_tests_results;
\ No newline at end of file
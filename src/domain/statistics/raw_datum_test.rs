@@ -0,0 +1,535 @@
+use getset::Getters;
+use statrs::function::{erf::erfc, gamma::checked_gamma_ur};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::error::StatisticsError;
+
+/// Statistic used to compare observed face counts with a uniform die.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Clone, Default, PartialEq, Eq)]
+pub enum GoodnessOfFitStatistic {
+    /// Pearson's chi-squared, `sum((observed - expected)^2 / expected)`.
+    #[default]
+    ChiSquared,
+    /// The likelihood ratio G, `2 * sum(observed * ln(observed / expected))`. Closer to the
+    /// chi-squared distribution than Pearson's statistic when some counts are small.
+    G,
+}
+
+impl GoodnessOfFitStatistic {
+    pub fn get_name(&self) -> String {
+        match self {
+            GoodnessOfFitStatistic::ChiSquared => "chi-squared".to_string(),
+            GoodnessOfFitStatistic::G => "G".to_string(),
+        }
+    }
+}
+
+/// How often a face came up, and how often a uniform die would show it.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct FaceCount {
+    face: u64,
+    observed: u64,
+    expected: f64,
+}
+
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct GoodnessOfFitTestResult {
+    statistic_kind: GoodnessOfFitStatistic,
+    face_counts: Vec<FaceCount>,
+    num_samples: u64,
+    statistic: f64,
+    degrees_of_freedom: u64,
+    /// Chance of a statistic at least this large from a uniform die.
+    p_value: f64,
+}
+
+/// Chi-squared test of independence on the counts of each face following each face.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct TransitionTestResult {
+    /// `transition_counts[from][to]`, faces counted from `range_min`.
+    transition_counts: Vec<Vec<u64>>,
+    num_transitions: u64,
+    statistic: f64,
+    /// `(rows - 1) * (columns - 1)`, counting only faces that were rolled.
+    degrees_of_freedom: u64,
+    /// Chance of a statistic at least this large when each roll is independent of the last.
+    p_value: f64,
+}
+
+/// Correlation between each roll and the roll `lag` places later.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct AutocorrelationResult {
+    lag: u64,
+    /// From -1 to 1, and 0 when all rolls are the same face.
+    autocorrelation: f64,
+    /// `autocorrelation * sqrt(num_samples)`, close to standard normal for independent rolls.
+    z_score: f64,
+    /// Two sided chance of a correlation at least this strong from independent rolls.
+    p_value: f64,
+}
+
+/// Whether consecutive rolls depend on each other, for example because the die was placed
+/// rather than thrown. Says nothing about bias: a loaded die that is thrown properly passes.
+#[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone, PartialEq)]
+#[get = "pub with_prefix"]
+pub struct SerialDependenceTestResult {
+    transition_test: TransitionTestResult,
+    /// One result for each lag from 1 to the largest lag asked for.
+    autocorrelations: Vec<AutocorrelationResult>,
+    significance_level: f64,
+    /// The transition test or any autocorrelation is significant. Autocorrelations are held
+    /// to `significance_level` divided by the number of lags.
+    is_dependence_detected: bool,
+}
+
+/// Tests run on the data as they were rolled, before any extraction.
+pub struct RawDatumStatisticalTest;
+
+impl RawDatumStatisticalTest {
+    /// Tests whether `observations`, each a face from `range_min` to `range_max` inclusive, fit
+    /// a uniform die. Every face needs 5 observations on average.
+    pub fn goodness_of_fit_test(
+        observations: &[u64],
+        range_min: u64,
+        range_max: u64,
+        statistic_kind: GoodnessOfFitStatistic,
+    ) -> Result<GoodnessOfFitTestResult, StatisticsError> {
+        if range_min >= range_max {
+            return Err(StatisticsError::RangeBoundsAreIncorrect);
+        }
+        // Saturates for ranges near the full width of a u64, which need more samples than any
+        // input can hold anyway.
+        let num_faces = (range_max - range_min).saturating_add(1);
+        let num_samples = observations.len() as u64;
+        let num_required_samples = num_faces.saturating_mul(5);
+        if num_samples < num_required_samples {
+            return Err(StatisticsError::NotEnoughSamplesForChiSquaredTest {
+                num_given_samples: num_samples,
+                num_required_samples,
+            });
+        }
+        let mut observed_counts = vec![0u64; num_faces as usize];
+        for observation in observations {
+            if !(range_min..=range_max).contains(observation) {
+                return Err(StatisticsError::RangeBoundsDoNotFullyCoverAllObservations);
+            }
+            observed_counts[(observation - range_min) as usize] += 1;
+        }
+        let expected = num_samples as f64 / num_faces as f64;
+        let statistic = observed_counts
+            .iter()
+            .map(|observed| {
+                let observed = *observed as f64;
+                match statistic_kind {
+                    GoodnessOfFitStatistic::ChiSquared => (observed - expected).powi(2) / expected,
+                    GoodnessOfFitStatistic::G if observed == 0.0 => 0.0,
+                    GoodnessOfFitStatistic::G => 2.0 * observed * (observed / expected).ln(),
+                }
+            })
+            .sum::<f64>();
+        let degrees_of_freedom = num_faces - 1;
+        let p_value = upper_tail_chi_squared(statistic, degrees_of_freedom);
+        Ok(GoodnessOfFitTestResult {
+            statistic_kind,
+            face_counts: observed_counts
+                .iter()
+                .enumerate()
+                .map(|(index, observed)| FaceCount {
+                    face: range_min + index as u64,
+                    observed: *observed,
+                    expected,
+                })
+                .collect(),
+            num_samples,
+            statistic,
+            degrees_of_freedom,
+            p_value,
+        })
+    }
+
+    /// Tests whether each of `observations`, in the order they were rolled, is independent of
+    /// the one before. Every pair of faces needs 5 transitions on average.
+    pub fn transition_test(
+        observations: &[u64],
+        range_min: u64,
+        range_max: u64,
+    ) -> Result<TransitionTestResult, StatisticsError> {
+        if range_min >= range_max {
+            return Err(StatisticsError::RangeBoundsAreIncorrect);
+        }
+        let num_faces = (range_max - range_min).saturating_add(1);
+        let num_transitions = observations.len().saturating_sub(1) as u64;
+        let num_required_transitions = num_faces.saturating_mul(num_faces).saturating_mul(5);
+        if num_transitions < num_required_transitions {
+            return Err(StatisticsError::NotEnoughSamplesForChiSquaredTest {
+                num_given_samples: num_transitions,
+                num_required_samples: num_required_transitions,
+            });
+        }
+        let num_faces = num_faces as usize;
+        if observations
+            .iter()
+            .any(|observation| !(range_min..=range_max).contains(observation))
+        {
+            return Err(StatisticsError::RangeBoundsDoNotFullyCoverAllObservations);
+        }
+        let mut transition_counts = vec![vec![0u64; num_faces]; num_faces];
+        for pair in observations.windows(2) {
+            transition_counts[(pair[0] - range_min) as usize][(pair[1] - range_min) as usize] += 1;
+        }
+        let row_totals = transition_counts
+            .iter()
+            .map(|row| row.iter().sum::<u64>())
+            .collect::<Vec<u64>>();
+        let column_totals = (0..num_faces)
+            .map(|column| transition_counts.iter().map(|row| row[column]).sum::<u64>())
+            .collect::<Vec<u64>>();
+        let mut statistic = 0.0;
+        for (row, row_total) in transition_counts.iter().zip(row_totals.iter()) {
+            for (observed, column_total) in row.iter().zip(column_totals.iter()) {
+                let expected = *row_total as f64 * *column_total as f64 / num_transitions as f64;
+                if expected > 0.0 {
+                    statistic += (*observed as f64 - expected).powi(2) / expected;
+                }
+            }
+        }
+        let num_rows = row_totals.iter().filter(|total| **total > 0).count() as u64;
+        let num_columns = column_totals.iter().filter(|total| **total > 0).count() as u64;
+        let degrees_of_freedom = (num_rows - 1) * (num_columns - 1);
+        let p_value = if degrees_of_freedom == 0 {
+            1.0
+        } else {
+            upper_tail_chi_squared(statistic, degrees_of_freedom)
+        };
+        Ok(TransitionTestResult {
+            transition_counts,
+            num_transitions,
+            statistic,
+            degrees_of_freedom,
+            p_value,
+        })
+    }
+
+    /// Correlation between `observations` and themselves shifted by `lag`. `lag` must be at
+    /// least 1 and less than the number of observations.
+    pub fn autocorrelation_test(
+        observations: &[u64],
+        lag: u64,
+    ) -> Result<AutocorrelationResult, StatisticsError> {
+        let num_samples = observations.len();
+        if lag == 0 || lag as usize >= num_samples {
+            return Err(StatisticsError::SerialDependenceParametersAreIncorrect);
+        }
+        let mean = observations
+            .iter()
+            .map(|observation| *observation as f64)
+            .sum::<f64>()
+            / num_samples as f64;
+        let deviations = observations
+            .iter()
+            .map(|observation| *observation as f64 - mean)
+            .collect::<Vec<f64>>();
+        let variance_sum = deviations
+            .iter()
+            .map(|deviation| deviation.powi(2))
+            .sum::<f64>();
+        let autocorrelation = if variance_sum == 0.0 {
+            0.0
+        } else {
+            deviations
+                .iter()
+                .zip(deviations.iter().skip(lag as usize))
+                .map(|(first, second)| first * second)
+                .sum::<f64>()
+                / variance_sum
+        };
+        let z_score = autocorrelation * (num_samples as f64).sqrt();
+        Ok(AutocorrelationResult {
+            lag,
+            autocorrelation,
+            z_score,
+            p_value: erfc(z_score.abs() / std::f64::consts::SQRT_2),
+        })
+    }
+
+    /// Runs the transition test and the autocorrelation test for lags 1 to `max_lag`, and
+    /// flags dependence at `significance_level`.
+    pub fn serial_dependence_test(
+        observations: &[u64],
+        range_min: u64,
+        range_max: u64,
+        max_lag: u64,
+        significance_level: f64,
+    ) -> Result<SerialDependenceTestResult, StatisticsError> {
+        if !(significance_level > 0.0 && significance_level < 1.0) {
+            return Err(StatisticsError::SerialDependenceParametersAreIncorrect);
+        }
+        let transition_test =
+            RawDatumStatisticalTest::transition_test(observations, range_min, range_max)?;
+        let autocorrelations = (1..=max_lag)
+            .map(|lag| RawDatumStatisticalTest::autocorrelation_test(observations, lag))
+            .collect::<Result<Vec<AutocorrelationResult>, StatisticsError>>()?;
+        if autocorrelations.is_empty() {
+            return Err(StatisticsError::SerialDependenceParametersAreIncorrect);
+        }
+        let lag_significance_level = significance_level / max_lag as f64;
+        let is_dependence_detected = transition_test.p_value < significance_level
+            || autocorrelations
+                .iter()
+                .any(|autocorrelation| autocorrelation.p_value < lag_significance_level);
+        Ok(SerialDependenceTestResult {
+            transition_test,
+            autocorrelations,
+            significance_level,
+            is_dependence_detected,
+        })
+    }
+}
+
+/// Chance that a chi-squared variable with `degrees_of_freedom` is at least `statistic`.
+pub fn upper_tail_chi_squared(statistic: f64, degrees_of_freedom: u64) -> f64 {
+    checked_gamma_ur(degrees_of_freedom as f64 / 2.0, statistic / 2.0).unwrap_or(1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::process::rng_source::{DeterministicRngSource, RngSource};
+
+    fn counts_to_observations(counts: &[u64]) -> Vec<u64> {
+        counts
+            .iter()
+            .enumerate()
+            .flat_map(|(index, count)| vec![index as u64 + 1; *count as usize])
+            .collect()
+    }
+
+    #[test]
+    fn goodness_of_fit_test_works_01() {
+        let observations = counts_to_observations(&[10, 5, 5, 5, 5, 0]);
+        let test = RawDatumStatisticalTest::goodness_of_fit_test(
+            &observations,
+            1,
+            6,
+            GoodnessOfFitStatistic::ChiSquared,
+        )
+        .unwrap();
+        assert_eq!(test.num_samples, 30);
+        assert_eq!(test.degrees_of_freedom, 5);
+        assert!((test.statistic - 10.0).abs() < 1e-12);
+        assert!((test.p_value - 0.0752352461465122).abs() < 1e-9);
+        assert_eq!(
+            test.face_counts[0],
+            FaceCount {
+                face: 1,
+                observed: 10,
+                expected: 5.0
+            }
+        );
+        assert_eq!(*test.face_counts[5].get_observed(), 0);
+    }
+
+    #[test]
+    fn goodness_of_fit_test_works_with_g_01() {
+        let observations = counts_to_observations(&[10, 5, 5, 5, 5, 0]);
+        let test = RawDatumStatisticalTest::goodness_of_fit_test(
+            &observations,
+            1,
+            6,
+            GoodnessOfFitStatistic::G,
+        )
+        .unwrap();
+        assert!((test.statistic - 20.0 * 2f64.ln()).abs() < 1e-12);
+        assert!((test.p_value - 0.016503859005433313).abs() < 1e-9);
+    }
+
+    #[test]
+    fn goodness_of_fit_test_works_for_perfect_fit_01() {
+        let observations = counts_to_observations(&[7; 10]);
+        let test = RawDatumStatisticalTest::goodness_of_fit_test(
+            &observations,
+            1,
+            10,
+            GoodnessOfFitStatistic::ChiSquared,
+        )
+        .unwrap();
+        assert_eq!(test.statistic, 0.0);
+        assert_eq!(test.p_value, 1.0);
+    }
+
+    #[test]
+    fn goodness_of_fit_test_emits_errors_01() {
+        let observations = counts_to_observations(&[5; 6]);
+        let test = |observations: &[u64], range_min, range_max| {
+            RawDatumStatisticalTest::goodness_of_fit_test(
+                observations,
+                range_min,
+                range_max,
+                GoodnessOfFitStatistic::ChiSquared,
+            )
+            .err()
+            .unwrap()
+        };
+        assert_eq!(
+            test(&observations, 6, 6),
+            StatisticsError::RangeBoundsAreIncorrect
+        );
+        assert_eq!(
+            test(&observations[1..], 1, 6),
+            StatisticsError::NotEnoughSamplesForChiSquaredTest {
+                num_given_samples: 29,
+                num_required_samples: 30
+            }
+        );
+        assert_eq!(
+            test(&observations, 1, 5),
+            StatisticsError::RangeBoundsDoNotFullyCoverAllObservations
+        );
+        assert_eq!(
+            test(&observations, 0, u64::MAX),
+            StatisticsError::NotEnoughSamplesForChiSquaredTest {
+                num_given_samples: 30,
+                num_required_samples: u64::MAX
+            }
+        );
+        assert_eq!(
+            test(&observations, 1, u64::MAX / 4),
+            StatisticsError::NotEnoughSamplesForChiSquaredTest {
+                num_given_samples: 30,
+                num_required_samples: u64::MAX
+            }
+        );
+    }
+
+    #[test]
+    fn transition_test_works_01() {
+        // A coin that always lands on the other side: 10 heads to tails, 10 tails to heads.
+        let observations = (0..21).map(|index| index % 2 + 1).collect::<Vec<u64>>();
+        let test = RawDatumStatisticalTest::transition_test(&observations, 1, 2).unwrap();
+        assert_eq!(test.transition_counts, vec![vec![0, 10], vec![10, 0]]);
+        assert_eq!(test.num_transitions, 20);
+        assert_eq!(test.degrees_of_freedom, 1);
+        assert!((test.statistic - 20.0).abs() < 1e-12);
+        assert!((test.p_value - 7.744216431044074e-6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn transition_test_ignores_bias_01() {
+        // Face 1 comes up half the time, but every roll is independent of the last.
+        let mut bytes = [0u8; 600];
+        DeterministicRngSource::new(b"biased die")
+            .fill_bytes(&mut bytes)
+            .unwrap();
+        let observations = bytes
+            .iter()
+            .map(|byte| match byte {
+                0..=127 => 1,
+                128..=191 => 2,
+                _ => 3,
+            })
+            .collect::<Vec<u64>>();
+        let transition_test =
+            RawDatumStatisticalTest::transition_test(&observations, 1, 3).unwrap();
+        assert!(transition_test.p_value > 0.01);
+        let fit_test = RawDatumStatisticalTest::goodness_of_fit_test(
+            &observations,
+            1,
+            3,
+            GoodnessOfFitStatistic::ChiSquared,
+        )
+        .unwrap();
+        assert!(fit_test.p_value < 1e-6);
+    }
+
+    #[test]
+    fn transition_test_emits_errors_01() {
+        let observations = (0..20).map(|index| index % 2 + 1).collect::<Vec<u64>>();
+        assert_eq!(
+            RawDatumStatisticalTest::transition_test(&observations, 1, 2)
+                .err()
+                .unwrap(),
+            StatisticsError::NotEnoughSamplesForChiSquaredTest {
+                num_given_samples: 19,
+                num_required_samples: 20
+            }
+        );
+        assert_eq!(
+            RawDatumStatisticalTest::transition_test(&[3; 30], 1, 2)
+                .err()
+                .unwrap(),
+            StatisticsError::RangeBoundsDoNotFullyCoverAllObservations
+        );
+        assert_eq!(
+            RawDatumStatisticalTest::transition_test(&observations, 1, 1 << 32)
+                .err()
+                .unwrap(),
+            StatisticsError::NotEnoughSamplesForChiSquaredTest {
+                num_given_samples: 19,
+                num_required_samples: u64::MAX
+            }
+        );
+    }
+
+    #[test]
+    fn autocorrelation_test_works_for_huge_faces_01() {
+        let observations = (0..20)
+            .map(|index| u64::MAX - index % 2)
+            .collect::<Vec<u64>>();
+        let test = RawDatumStatisticalTest::autocorrelation_test(&observations, 1).unwrap();
+        assert!(test.autocorrelation.is_finite());
+    }
+
+    #[test]
+    fn autocorrelation_test_works_01() {
+        let observations = (0..21).map(|index| index % 2 + 1).collect::<Vec<u64>>();
+        let first_lag = RawDatumStatisticalTest::autocorrelation_test(&observations, 1).unwrap();
+        assert!((first_lag.autocorrelation + 0.952380952380953).abs() < 1e-12);
+        assert!(first_lag.p_value < 1e-4);
+        let second_lag = RawDatumStatisticalTest::autocorrelation_test(&observations, 2).unwrap();
+        assert!((second_lag.autocorrelation - 0.9043290043290043).abs() < 1e-12);
+        let constant = RawDatumStatisticalTest::autocorrelation_test(&[4; 10], 1).unwrap();
+        assert_eq!(constant.autocorrelation, 0.0);
+        assert_eq!(constant.p_value, 1.0);
+        assert_eq!(
+            RawDatumStatisticalTest::autocorrelation_test(&observations, 21)
+                .err()
+                .unwrap(),
+            StatisticsError::SerialDependenceParametersAreIncorrect
+        );
+    }
+
+    #[test]
+    fn serial_dependence_test_works_01() {
+        // Rolls of a d6 that is turned one face up each time look uniform but are dependent.
+        let observations = (0..600).map(|index| index % 6 + 1).collect::<Vec<u64>>();
+        let test =
+            RawDatumStatisticalTest::serial_dependence_test(&observations, 1, 6, 3, 0.01).unwrap();
+        assert!(test.is_dependence_detected);
+        assert_eq!(test.autocorrelations.len(), 3);
+        assert_eq!(*test.autocorrelations[2].get_lag(), 3);
+        let fit_test = RawDatumStatisticalTest::goodness_of_fit_test(
+            &observations,
+            1,
+            6,
+            GoodnessOfFitStatistic::ChiSquared,
+        )
+        .unwrap();
+        assert_eq!(fit_test.p_value, 1.0);
+        assert_eq!(
+            RawDatumStatisticalTest::serial_dependence_test(&observations, 1, 6, 0, 0.01)
+                .err()
+                .unwrap(),
+            StatisticsError::SerialDependenceParametersAreIncorrect
+        );
+        assert_eq!(
+            RawDatumStatisticalTest::serial_dependence_test(&observations, 1, 6, 3, 1.0)
+                .err()
+                .unwrap(),
+            StatisticsError::SerialDependenceParametersAreIncorrect
+        );
+    }
+}
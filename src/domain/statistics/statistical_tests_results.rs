@@ -1,8 +1,22 @@
-
 use getset::Getters;
 use nistrs::TestResultT;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+/// Tests that need at least [`LONG_DATA_TESTS_MIN_BITS`] bits and are not run on less.
+pub const LONG_DATA_TESTS: [&str; 2] = ["block frequency", "longest run of ones"];
+pub const LONG_DATA_TESTS_MIN_BITS: usize = 128;
+/// Names of the selected tests, in the order of the fields of [`StatisticalTestsResults`].
+pub const SELECTED_TESTS: [&str; 8] = [
+    "approximate entropy (m = 2)",
+    "approximate entropy (m = 3)",
+    "block frequency",
+    "cumulative sums",
+    "frequency",
+    "longest run of ones",
+    "runs",
+    "serial (pattern size 6)",
+];
+
 #[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Default, Clone)]
 #[get = "pub with_prefix"]
 pub struct StatisticalTestsResults {
@@ -17,6 +31,7 @@ pub struct StatisticalTestsResults {
 }
 
 impl StatisticalTestsResults {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         approximate_entropy_test_m2_result: Option<TestResultT>,
         approximate_entropy_test_m3_result: Option<TestResultT>,
@@ -38,6 +53,48 @@ impl StatisticalTestsResults {
             serial_test_pattern_size_6_result,
         }
     }
+
+    /// Names of the tests that fail once `significance_level` is split evenly among all the
+    /// p-values of the tests that ran, as in a Bonferroni correction. Uniformly random data
+    /// then fail any of the tests with a chance of at most `significance_level`, while each
+    /// test on its own fails them with a chance of 1%.
+    pub fn get_failed_tests_with_bonferroni_correction(
+        &self,
+        significance_level: f64,
+    ) -> Vec<String> {
+        let p_values = self.get_p_values();
+        let num_p_values = p_values.iter().map(Vec::len).sum::<usize>();
+        let corrected_significance_level = significance_level / num_p_values.max(1) as f64;
+        SELECTED_TESTS
+            .iter()
+            .zip(p_values.iter())
+            .filter(|(_, test_p_values)| {
+                test_p_values
+                    .iter()
+                    .any(|p_value| p_value.is_nan() || *p_value < corrected_significance_level)
+            })
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
+    /// P-values of each test in the order of [`SELECTED_TESTS`], empty for a test that did
+    /// not run.
+    fn get_p_values(&self) -> Vec<Vec<f64>> {
+        let single = |result: &Option<TestResultT>| result.iter().map(|result| result.1).collect();
+        let pair = |result: &Option<[TestResultT; 2]>| {
+            result.iter().flatten().map(|result| result.1).collect()
+        };
+        vec![
+            single(&self.approximate_entropy_test_m2_result),
+            single(&self.approximate_entropy_test_m3_result),
+            single(&self.block_frequency_test_result),
+            pair(&self.cumulative_sums_test_result),
+            single(&self.frequency_test_result),
+            single(&self.longest_run_of_ones_test_result),
+            single(&self.runs_test_result),
+            pair(&self.serial_test_pattern_size_6_result),
+        ]
+    }
 }
 
 #[derive(Debug, Clone, Zeroize, ZeroizeOnDrop, PartialEq, Default)]
@@ -61,6 +118,46 @@ pub struct StatisticalTestsOverview {
     serial_test_pattern_size_6_result: StatsTestState,
 }
 
+impl StatisticalTestsOverview {
+    /// Names of the tests that ran and failed.
+    pub fn get_failed_tests(&self) -> Vec<String> {
+        self.get_tests_in_state(StatsTestState::Failed)
+    }
+
+    /// Names of the tests that did not run, for example because the data were too short.
+    pub fn get_not_available_tests(&self) -> Vec<String> {
+        self.get_tests_in_state(StatsTestState::NotAvailable)
+    }
+
+    /// Names of the tests that did not run although `num_bits` of data are enough for them.
+    pub fn get_missing_tests(&self, num_bits: usize) -> Vec<String> {
+        self.get_not_available_tests()
+            .into_iter()
+            .filter(|name| {
+                num_bits >= LONG_DATA_TESTS_MIN_BITS || !LONG_DATA_TESTS.contains(&name.as_str())
+            })
+            .collect()
+    }
+
+    fn get_tests_in_state(&self, wanted_state: StatsTestState) -> Vec<String> {
+        SELECTED_TESTS
+            .iter()
+            .zip([
+                &self.approximate_entropy_test_m2_result,
+                &self.approximate_entropy_test_m3_result,
+                &self.block_frequency_test_result,
+                &self.cumulative_sums_test_result,
+                &self.frequency_test_result,
+                &self.longest_run_of_ones_test_result,
+                &self.runs_test_result,
+                &self.serial_test_pattern_size_6_result,
+            ])
+            .filter(|(_, state)| **state == wanted_state)
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+}
+
 impl From<StatisticalTestsResults> for StatisticalTestsOverview {
     fn from(value: StatisticalTestsResults) -> Self {
         let approximate_entropy_test_m2_result = match value.approximate_entropy_test_m2_result {
@@ -251,3 +348,83 @@ impl From<&StatisticalTestsResults> for StatisticalTestsOverview {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::domain::statistics::uniform_random_test::UniformRandomDistStatisticalTest;
+    use ring::digest::{digest, SHA256};
+
+    #[test]
+    fn get_missing_tests_works_01() {
+        let results = StatisticalTestsResults::new(
+            Some((true, 0.5)),
+            Some((true, 0.5)),
+            None,
+            Some([(true, 0.5), (true, 0.5)]),
+            Some((false, 0.001)),
+            None,
+            None,
+            Some([(true, 0.5), (true, 0.5)]),
+        );
+        let overview = StatisticalTestsOverview::from(&results);
+        assert_eq!(overview.get_failed_tests(), vec!["frequency"]);
+        assert_eq!(
+            overview.get_not_available_tests(),
+            vec!["block frequency", "longest run of ones", "runs"]
+        );
+        assert_eq!(overview.get_missing_tests(64), vec!["runs"]);
+        assert_eq!(
+            overview.get_missing_tests(128),
+            vec!["block frequency", "longest run of ones", "runs"]
+        );
+    }
+
+    #[test]
+    fn get_missing_tests_works_02() {
+        let overview = StatisticalTestsOverview::from(&StatisticalTestsResults::default());
+        assert_eq!(overview.get_missing_tests(256).len(), 8);
+        assert!(overview.get_failed_tests().is_empty());
+    }
+
+    #[test]
+    fn get_failed_tests_with_bonferroni_correction_works_01() {
+        let results = StatisticalTestsResults::new(
+            Some((true, 0.5)),
+            Some((false, 0.005)),
+            None,
+            Some([(true, 0.5), (false, 0.0005)]),
+            Some((false, 0.001)),
+            None,
+            None,
+            Some([(true, 0.5), (true, f64::NAN)]),
+        );
+        // 7 p-values ran, so each is held to 0.01 / 7.
+        assert_eq!(
+            results.get_failed_tests_with_bonferroni_correction(0.01),
+            vec!["cumulative sums", "frequency", "serial (pattern size 6)"]
+        );
+        assert!(StatisticalTestsResults::default()
+            .get_failed_tests_with_bonferroni_correction(0.01)
+            .is_empty());
+    }
+
+    #[test]
+    fn get_failed_tests_with_bonferroni_correction_rarely_fails_random_data_01() {
+        // SHA-256 of a counter stands in for 32 bytes drawn uniformly at random.
+        let rejected = (0u32..1000)
+            .map(|counter| digest(&SHA256, &counter.to_be_bytes()))
+            .filter(|data| {
+                !UniformRandomDistStatisticalTest::perform_selected_tests(data.as_ref())
+                    .get_failed_tests_with_bonferroni_correction(0.01)
+                    .is_empty()
+            })
+            .count();
+        // About 1% of them are expected to be rejected.
+        assert!(
+            rejected <= 20,
+            "{} of 1000 random inputs were rejected",
+            rejected
+        );
+    }
+}
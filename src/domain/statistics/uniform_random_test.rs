@@ -24,83 +24,47 @@ pub struct UniformRandomDistStatisticalTest;
 impl UniformRandomDistStatisticalTest {
     pub fn approximate_entropy_test(data: &[u8], block_length: usize) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| approximate_entropy_test(&bits_data, block_length));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| approximate_entropy_test(&bits_data, block_length)).ok()
     }
 
     pub fn approximate_entropy_test_m2(data: &[u8]) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| approximate_entropy_test(&bits_data, 2));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| approximate_entropy_test(&bits_data, 2)).ok()
     }
 
     pub fn approximate_entropy_test_m3(data: &[u8]) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| approximate_entropy_test(&bits_data, 3));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| approximate_entropy_test(&bits_data, 3)).ok()
     }
 
     pub fn block_frequency_test(data: &[u8], block_size: usize) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| block_frequency_test(&bits_data, block_size).unwrap());
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| block_frequency_test(&bits_data, block_size).unwrap()).ok()
     }
 
     pub fn cumulative_sums_test(data: &[u8]) -> Option<[TestResultT; 2]> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| cumulative_sums_test(&bits_data));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| cumulative_sums_test(&bits_data)).ok()
     }
 
     pub fn fft_test(data: &[u8]) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| fft_test(&bits_data));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| fft_test(&bits_data)).ok()
     }
 
     pub fn frequency_test(data: &[u8]) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| frequency_test(&bits_data));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| frequency_test(&bits_data)).ok()
     }
 
     pub fn linear_complexity_test(data: &[u8], block_size: usize) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| linear_complexity_test(&bits_data, block_size));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| linear_complexity_test(&bits_data, block_size)).ok()
     }
 
     pub fn longest_run_of_ones_test(data: &[u8]) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| longest_run_of_ones_test(&bits_data).unwrap());
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| longest_run_of_ones_test(&bits_data).unwrap()).ok()
     }
 
     pub fn non_overlapping_template_test(
@@ -108,67 +72,39 @@ impl UniformRandomDistStatisticalTest {
         template_length_2_to_16: u8,
     ) -> Option<Vec<TestResultT>> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| {
+        panic::catch_unwind(|| {
             non_overlapping_template_test(&bits_data, template_length_2_to_16 as usize).unwrap()
-        });
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        }).ok()
     }
 
     pub fn overlapping_template_test(data: &[u8], template_length: usize) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| overlapping_template_test(&bits_data, template_length));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| overlapping_template_test(&bits_data, template_length)).ok()
     }
 
     pub fn random_excursions_test(data: &[u8]) -> Option<[TestResultT; 8]> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| random_excursions_test(&bits_data).unwrap());
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| random_excursions_test(&bits_data).unwrap()).ok()
     }
 
     pub fn random_excursions_variant_test(data: &[u8]) -> Option<[TestResultT; 18]> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| random_excursions_variant_test(&bits_data).unwrap());
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| random_excursions_variant_test(&bits_data).unwrap()).ok()
     }
 
     pub fn runs_test(data: &[u8]) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| runs_test(&bits_data));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| runs_test(&bits_data)).ok()
     }
 
     pub fn serial_test(data: &[u8], pattern_size: usize) -> Option<[TestResultT; 2]> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| serial_test(&bits_data, pattern_size));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| serial_test(&bits_data, pattern_size)).ok()
     }
 
     pub fn universal_test(data: &[u8]) -> Option<TestResultT> {
         let bits_data = BitsData::from_text(vec_u8_to_bit_string(data));
-        let result = panic::catch_unwind(|| universal_test(&bits_data));
-        match result {
-            Ok(test_result) => Some(test_result),
-            Err(_) => None,
-        }
+        panic::catch_unwind(|| universal_test(&bits_data)).ok()
     }
 
     pub fn perform_selected_tests(data: &[u8]) -> StatisticalTestsResults {
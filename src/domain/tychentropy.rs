@@ -1,36 +1,103 @@
-use std::{ops::BitXor, panic};
+use std::{fs::File, io::Read, panic, path::Path};
 
 use bip39::Mnemonic;
 use getset::Getters;
-use rand::RngCore;
-use ring::rand::{SecureRandom, SystemRandom};
+use ring::rand::SystemRandom;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::utils::domain_utils::u64_to_binary;
+use crate::utils::domain_utils::u128_to_bit_vec;
 
 use super::{
+    extraction::{
+        dice_hashing::{
+            sha256_of_roll_digits, COLDCARD_MIN_ROLLS, SEEDSIGNER_12_WORD_ROLLS,
+            SEEDSIGNER_24_WORD_ROLLS,
+        },
+        ian_coleman::IanColemanEntropy,
+        Conditioning, ConditioningRecord, ExtractionCost, ExtractionMethod, ExtractionState,
+        ExtractionStep, Extractor, RangeCodingState, ToeplitzExtraction, ToeplitzExtractor,
+    },
     mnemonics::MnemonicLength,
     process::{
+        bit_order::BitOrder,
         error::ProcessError,
-        input::{new::TychentropyNewInput, NaturalDatum},
+        ingestion_report::IngestionReport,
+        input::{
+            natural_datum_group::{combine_zero_indexed_values, combined_range},
+            new::TychentropyNewInput,
+            roll_text::{parse_ranged_roll_text, parse_roll_text},
+            DeckPermutation, FaceAlphabet, NaturalDatum, NaturalDatumGroup,
+        },
+        mixing::{MixingRecord, MixingStrategy},
+        range_account::RangeAccount,
+        rng_commitment::{CommittedRngBytes, RngCommitment},
+        rng_source::{fill_from_rng_sources, CryptoRngSource, RngSource, SecureRandomSource},
+        session_event::SessionEvent,
     },
     statistics::{
-        statistical_tests_results::StatisticalTestsResults,
+        bias_monitor::{DieBiasWarning, SprtBiasMonitor},
+        error::StatisticsError,
+        health_test::{HealthTestFailure, HealthTestMonitor},
+        min_entropy_estimate::{MinEntropyAssessment, MinEntropyEstimation},
+        raw_datum_test::{
+            GoodnessOfFitStatistic, GoodnessOfFitTestResult, RawDatumStatisticalTest,
+            SerialDependenceTestResult,
+        },
+        statistical_tests_results::{StatisticalTestsOverview, StatisticalTestsResults},
         uniform_random_test::UniformRandomDistStatisticalTest,
     },
 };
 
+/// Chance that external entropy drawn uniformly at random fails the statistical tests.
+pub const EXTERNAL_ENTROPY_SIGNIFICANCE_LEVEL: f64 = 0.01;
+
 #[derive(Debug, Zeroize, ZeroizeOnDrop, Getters, Clone)]
 #[get = "pub with_prefix"]
 pub struct Tychentropy {
     /// Range length of possible numbers that are to be drawn. Think of this as how many sides your dice has. It must have at least 2 to produce any entropy.
     range: u64,
+    /// Ranges allowed in a session that mixes dice types, or `None` when only `range` is used.
+    accepted_ranges: Option<Vec<u64>>,
     full_bits_in_each_datum: u32,
+    extraction_method: ExtractionMethod,
+    extractor: Box<dyn Extractor>,
+    /// Labels of the faces, used to read rolls from text, in error messages and in the roll log.
+    face_alphabet: FaceAlphabet,
+    /// Order in which entropy bits are packed into `entropy_bytes_vector`.
+    bit_order: BitOrder,
+    mixing_strategy: MixingStrategy,
+    /// Parameters of the mix applied to the entropy, `None` while it is not mixed.
+    mixing_record: Option<MixingRecord>,
+    /// Watches the data for biased dice, when the session was created with a SPRT config.
+    die_bias_monitor: Option<SprtBiasMonitor>,
+    /// First warning of the bias monitor that is not acknowledged yet.
+    die_bias_warning: Option<DieBiasWarning>,
+    /// Runs the continuous health tests, when the session was created with a health test
+    /// config.
+    health_test_monitor: Option<HealthTestMonitor>,
+    /// Health test failure that is not acknowledged yet. No data are accepted until it is.
+    health_test_failure: Option<HealthTestFailure>,
+    /// Rng bytes committed to before the data were added, if any.
+    rng_commitment: Option<RngCommitment>,
+    /// Committed rng bytes, kept out of the history until they are revealed.
+    #[getset(skip)]
+    committed_rng_entropy_bytes: CommittedRngBytes,
+    /// Hashes the whole sequence into the entropy instead of using the extracted bits.
+    conditioning: Option<Conditioning>,
+    /// What was conditioned, once the entropy has been made by conditioning.
+    conditioning_record: Option<ConditioningRecord>,
     target_entropy_bytes: u64,
     target_entropy_bits: u64,
     sequence: Vec<u64>,
+    /// Range of the die behind each entry of `sequence`.
+    sequence_ranges: Vec<u64>,
     entropy_generating_sequence: Vec<u64>,
+    range_accounts: Vec<RangeAccount>,
+    deck_permutations: Vec<DeckPermutation>,
     generated_entropy_bits: u64,
+    /// Raw data spent on each group of emitted entropy bits, including discarded data.
+    extraction_costs: Vec<ExtractionCost>,
+    data_since_last_extraction: u64,
     is_entropy_ready: bool,
     entropy_bit_string: String,
     entropy_bit_vector: Vec<u8>,
@@ -41,6 +108,10 @@ pub struct Tychentropy {
     statistical_test_results: StatisticalTestsResults,
     mnemonic_length: Option<MnemonicLength>,
     mnemonic: Option<Mnemonic>,
+    /// Log of the events the state is derived from.
+    history: Vec<SessionEvent>,
+    /// Groups of events taken back by `undo_last_datum`, most recent last.
+    redo_stack: Vec<Vec<SessionEvent>>,
 }
 
 impl Default for Tychentropy {
@@ -52,12 +123,38 @@ impl Default for Tychentropy {
 impl Tychentropy {
     pub fn new(input: TychentropyNewInput) -> Self {
         let range_len = *input.get_range_len();
+        let accepted_ranges = input.get_accepted_ranges().clone();
         let full_bits_in_each_datum = range_len.ilog2();
+        let extraction_method = input.get_extraction_method().clone();
+        let extractor = match input.get_extractor() {
+            Some(extractor) if !extraction_method.is_hashed() => extractor.new_empty(),
+            _ => Box::new(ExtractionState::from(&extraction_method)),
+        };
+        let face_alphabet = input.get_face_alphabet().clone();
+        let bit_order = input.get_bit_order().clone();
+        let mixing_strategy = input.get_mixing_strategy().clone();
+        let mixing_record = None;
+        let rng_commitment = None;
+        let committed_rng_entropy_bytes = CommittedRngBytes::default();
+        let die_bias_monitor = input.get_sprt_config().clone().map(SprtBiasMonitor::new);
+        let die_bias_warning = None;
+        let health_test_monitor = input
+            .get_health_test_config()
+            .clone()
+            .map(HealthTestMonitor::new);
+        let health_test_failure = None;
+        let conditioning = input.get_conditioning().clone();
+        let conditioning_record = None;
         let target_entropy_bytes = *input.get_target_entropy_bytes();
-        let target_entropy_bits = target_entropy_bytes * 8;
+        let target_entropy_bits = *input.get_target_entropy_bits();
         let sequence = vec![];
+        let sequence_ranges = vec![];
         let entropy_generating_sequence = vec![];
+        let range_accounts = vec![];
+        let deck_permutations = vec![];
         let generated_entropy_bits = 0;
+        let extraction_costs = vec![];
+        let data_since_last_extraction = 0;
         let is_entropy_ready = false;
         let entropy_bit_string = String::new();
         let entropy_bit_vector = vec![];
@@ -66,23 +163,45 @@ impl Tychentropy {
         let mixed_entropy_and_rng_bytes_vector = vec![];
         let final_entropy_bytes_vector = vec![];
         let statistical_test_results = StatisticalTestsResults::default();
-        let mnemonic_length = match *input.get_target_entropy_bytes() {
-            16 => Some(MnemonicLength::Twelve),
-            20 => Some(MnemonicLength::Fifteen),
-            24 => Some(MnemonicLength::Eighteen),
-            28 => Some(MnemonicLength::TwentyOne),
-            32 => Some(MnemonicLength::TwentyFour),
+        let mnemonic_length = match target_entropy_bits {
+            128 => Some(MnemonicLength::Twelve),
+            160 => Some(MnemonicLength::Fifteen),
+            192 => Some(MnemonicLength::Eighteen),
+            224 => Some(MnemonicLength::TwentyOne),
+            256 => Some(MnemonicLength::TwentyFour),
             _ => None,
         };
         let mnemonic = None;
+        let history = vec![];
+        let redo_stack = vec![];
         Tychentropy {
             range: range_len,
+            accepted_ranges,
             full_bits_in_each_datum,
+            extraction_method,
+            extractor,
+            face_alphabet,
+            bit_order,
+            mixing_strategy,
+            mixing_record,
+            die_bias_monitor,
+            die_bias_warning,
+            health_test_monitor,
+            health_test_failure,
+            rng_commitment,
+            committed_rng_entropy_bytes,
+            conditioning,
+            conditioning_record,
             target_entropy_bytes,
             target_entropy_bits,
             sequence,
+            sequence_ranges,
             entropy_generating_sequence,
+            range_accounts,
+            deck_permutations,
             generated_entropy_bits,
+            extraction_costs,
+            data_since_last_extraction,
             is_entropy_ready,
             entropy_bit_string,
             entropy_bit_vector,
@@ -93,434 +212,3131 @@ impl Tychentropy {
             statistical_test_results,
             mnemonic_length,
             mnemonic,
+            history,
+            redo_stack,
         }
     }
 
-    pub fn add_natural_datum(&mut self, datum: NaturalDatum) -> Result<(), ProcessError> {
-        let datum_value = *datum.get_value();
-        if self.is_entropy_ready {
-            Err(ProcessError::EntropyAlreadyCreated)
-        } else if datum_value > self.range {
-            Err(ProcessError::NaturalDatumOutOfRange {
-                value: datum_value,
-                range: self.range,
-            })
-        } else {
-            self.sequence.push(datum_value);
-            let zero_indexed_datum_value = datum_value - 1;
-            let cutoff_value = 2u64.pow(self.full_bits_in_each_datum);
-            if zero_indexed_datum_value < cutoff_value {
-                self.entropy_generating_sequence
-                    .push(zero_indexed_datum_value);
-                let zero_indexed_bit_string =
-                    u64_to_binary(zero_indexed_datum_value, self.full_bits_in_each_datum)
-                        .map_err(|err| err)
-                        .unwrap();
-                self.entropy_bit_string
-                    .push_str(&zero_indexed_bit_string.clone());
-                let zero_indexed_bit_vec = zero_indexed_bit_string
-                    .chars()
-                    .map(|c| c.to_string().parse::<u8>().unwrap())
-                    .collect::<Vec<u8>>();
-                self.entropy_bit_vector.extend(zero_indexed_bit_vec);
-                self.generated_entropy_bits += self.full_bits_in_each_datum as u64;
-                if self.generated_entropy_bits >= self.target_entropy_bits {
-                    self.is_entropy_ready = true;
-                    self.entropy_bit_string
-                        .truncate(self.target_entropy_bits as usize);
-                    self.entropy_bit_vector
-                        .truncate(self.target_entropy_bits as usize);
-
-                    let entropy_bytes_vector = self
-                        .entropy_bit_vector
-                        .chunks(8)
-                        .map(|byte_sized_chunk| {
-                            byte_sized_chunk
-                                .iter()
-                                .enumerate()
-                                .fold(0, |acc, (index, bit)| acc + bit * 2u8.pow(index as u32))
-                        })
-                        .collect::<Vec<u8>>();
-                    self.entropy_bytes_vector = entropy_bytes_vector.clone();
-                    self.final_entropy_bytes_vector = entropy_bytes_vector;
-                    self.perform_selected_statistical_tests();
-                    self.generate_mnemonic();
+    /// Adds a datum, or a group of data rolled together with dice of the same range.
+    ///
+    /// In a session with a single range, the data must fit that range. In a session that mixes
+    /// dice types, the range the datum was created with must be one of the accepted ranges.
+    /// A datum that fails a health test is not added, and no data are accepted until the
+    /// failure is acknowledged.
+    pub fn add_natural_datum<D: Into<NaturalDatumGroup>>(
+        &mut self,
+        datum: D,
+    ) -> Result<(), ProcessError> {
+        let datum_group: NaturalDatumGroup = datum.into();
+        let result = self.apply_natural_datum(&datum_group);
+        match result {
+            Ok(()) => self.record_event(SessionEvent::DatumAdded(datum_group)),
+            Err(ProcessError::NaturalDatumFailedHealthTest { .. }) => {
+                self.record_event(SessionEvent::DatumRejected(datum_group))
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Adds data in order until the target entropy is reached. Every datum is checked before
+    /// any is added, and an invalid one is reported with its zero indexed position. Data left
+    /// over once entropy is ready are not added and are counted as ignored in the report. A
+    /// datum that cannot be added, such as one that fails a health test, stops the data. The
+    /// error then gives its position and the report of the data added before it.
+    pub fn add_natural_data<I, D>(&mut self, data: I) -> Result<IngestionReport, ProcessError>
+    where
+        I: IntoIterator<Item = D>,
+        D: Into<NaturalDatumGroup>,
+    {
+        let datum_groups = data
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<NaturalDatumGroup>>();
+        for (index, datum_group) in datum_groups.iter().enumerate() {
+            self.check_natural_datum(datum_group).map_err(|error| {
+                ProcessError::InvalidDatumAtPosition {
+                    index: index as u64,
+                    error: Box::new(error),
                 }
+            })?;
+        }
+        if self.is_entropy_ready {
+            return Err(ProcessError::EntropyAlreadyCreated);
+        }
+        let mut report = IngestionReport::default();
+        for (index, datum_group) in datum_groups.into_iter().enumerate() {
+            if self.is_entropy_ready {
+                report.record_ignored();
+            } else if let Err(error) = self.add_natural_datum(datum_group) {
+                return Err(ProcessError::IngestionStoppedAtPosition {
+                    index: index as u64,
+                    report,
+                    error: Box::new(error),
+                });
+            } else {
+                report.record_accepted();
             }
-            Ok(())
         }
+        Ok(report)
     }
 
-    pub fn mix_with_rng(&mut self) -> Result<(), ProcessError> {
-        if !self.is_entropy_ready {
-            Err(ProcessError::EntropyBitsAreNotReady {
-                required_entropy_bits: self.target_entropy_bits,
-                current_entropy_bits: self.generated_entropy_bits,
+    /// Adds rolls of the session's die, given as values from 1 to `range`. See
+    /// `add_natural_data` for how invalid and leftover rolls are handled.
+    pub fn add_rolls<I: IntoIterator<Item = u64>>(
+        &mut self,
+        rolls: I,
+    ) -> Result<IngestionReport, ProcessError> {
+        if self.accepted_ranges.is_some() {
+            return Err(ProcessError::RollsNeedRangeInMixedRangeSession);
+        }
+        let data = rolls
+            .into_iter()
+            .enumerate()
+            .map(|(index, roll)| {
+                NaturalDatum::new(self.range, roll).map_err(|error| {
+                    ProcessError::InvalidDatumAtPosition {
+                        index: index as u64,
+                        error: Box::new(self.label_datum_error(error)),
+                    }
+                })
             })
-        } else {
-            let mut rand_rng = rand::thread_rng();
-            let mut rand_rng_bytes = vec![0u8; self.target_entropy_bytes as usize];
-            rand_rng.fill_bytes(&mut rand_rng_bytes);
-
-            let mut ring_rng_bytes = vec![0u8; self.target_entropy_bytes as usize];
-            let sys_random = SystemRandom::new();
-            sys_random.fill(&mut ring_rng_bytes).unwrap();
+            .collect::<Result<Vec<NaturalDatum>, ProcessError>>()?;
+        self.add_natural_data(data)
+    }
 
-            let mixed = rand_rng_bytes
-                .iter()
-                .zip(ring_rng_bytes.iter())
-                .map(|(rand_byte, ring_byte)| rand_byte.bitxor(ring_byte))
-                .collect();
-            self.rng_entropy_bytes_vector = mixed;
-            self.final_entropy_bytes_vector = self
-                .entropy_bytes_vector
-                .iter()
-                .zip(self.rng_entropy_bytes_vector.iter())
-                .map(|(data, rng)| data.bitxor(rng))
-                .collect();
-            self.perform_selected_statistical_tests();
-            self.generate_mnemonic();
-            Ok(())
+    /// Adds rolls of the session's die written as text, such as `1 4 6 2, 3 4 1`, with faces
+    /// labelled by the session's face alphabet. Rolls may be separated by whitespace, commas or
+    /// semicolons.
+    ///
+    /// In a session that mixes dice types each roll carries its range, as in `d6:5 d20:13`.
+    pub fn add_rolls_from_text(&mut self, text: &str) -> Result<IngestionReport, ProcessError> {
+        match self.accepted_ranges {
+            None => {
+                let rolls = parse_roll_text(text, self.range, &self.face_alphabet)?;
+                self.add_rolls(rolls)
+            }
+            Some(ref accepted_ranges) => {
+                let data = parse_ranged_roll_text(text, accepted_ranges, &self.face_alphabet)?;
+                self.add_natural_data(data)
+            }
         }
     }
 
-    pub fn put_data_from_another_tychentropy(&mut self, other: Tychentropy) {
-        self.range = other.range;
-        self.full_bits_in_each_datum = other.full_bits_in_each_datum;
-        self.target_entropy_bits = other.target_entropy_bits;
-        self.target_entropy_bytes = other.target_entropy_bytes;
-        self.sequence = other.sequence.clone();
-        self.entropy_generating_sequence = other.entropy_generating_sequence.clone();
-        self.generated_entropy_bits = other.generated_entropy_bits;
-        self.is_entropy_ready = other.is_entropy_ready;
-        self.entropy_bit_string = other.entropy_bit_string.clone();
-        self.entropy_bit_vector = other.entropy_bit_vector.clone();
-        self.entropy_bytes_vector = other.entropy_bytes_vector.clone();
-        self.rng_entropy_bytes_vector = other.rng_entropy_bytes_vector.clone();
-        self.mixed_entropy_and_rng_bytes_vector = other.mixed_entropy_and_rng_bytes_vector.clone();
-        self.final_entropy_bytes_vector = other.final_entropy_bytes_vector.clone();
-        self.mnemonic_length = other.mnemonic_length.clone();
-        self.mnemonic = other.mnemonic.clone();
-        self.statistical_test_results = other.statistical_test_results.clone();
+    /// Creates a session and adds data to it as `add_natural_data` does.
+    pub fn from_natural_data<I, D>(
+        input: TychentropyNewInput,
+        data: I,
+    ) -> Result<(Self, IngestionReport), ProcessError>
+    where
+        I: IntoIterator<Item = D>,
+        D: Into<NaturalDatumGroup>,
+    {
+        let mut tychentropy = Tychentropy::new(input);
+        let report = tychentropy.add_natural_data(data)?;
+        Ok((tychentropy, report))
     }
 
-    pub fn reset_data(&mut self) {
-        let input = TychentropyNewInput::new(self.range, self.target_entropy_bytes).unwrap();
-        let new_tych_entropy = Tychentropy::new(input);
-        self.put_data_from_another_tychentropy(new_tych_entropy);
+    /// Creates a session and adds rolls to it as `add_rolls` does.
+    pub fn from_rolls<I: IntoIterator<Item = u64>>(
+        input: TychentropyNewInput,
+        rolls: I,
+    ) -> Result<(Self, IngestionReport), ProcessError> {
+        let mut tychentropy = Tychentropy::new(input);
+        let report = tychentropy.add_rolls(rolls)?;
+        Ok((tychentropy, report))
     }
 
-    pub fn generate_mnemonic(&mut self) {
-        if self.is_entropy_ready && self.mnemonic_length.is_some() {
-            self.mnemonic = Some(Mnemonic::from_entropy(&self.final_entropy_bytes_vector).unwrap())
+    /// Creates a session and adds rolls written as text to it as `add_rolls_from_text` does.
+    pub fn from_rolls_text(
+        input: TychentropyNewInput,
+        text: &str,
+    ) -> Result<(Self, IngestionReport), ProcessError> {
+        let mut tychentropy = Tychentropy::new(input);
+        let report = tychentropy.add_rolls_from_text(text)?;
+        Ok((tychentropy, report))
+    }
+
+    /// Describes the faces named in a datum error with the session's face alphabet.
+    fn label_datum_error(&self, error: ProcessError) -> ProcessError {
+        match error {
+            ProcessError::NaturalDatumOutOfRange { value, range, .. } => {
+                ProcessError::NaturalDatumOutOfRange {
+                    value,
+                    range,
+                    faces: self.face_alphabet.describe_faces(range),
+                }
+            }
+            error => error,
         }
     }
 
-    pub fn perform_selected_statistical_tests(&mut self) {
-        let data = self.final_entropy_bytes_vector.clone();
-        if !data.is_empty() {
-            let results = panic::catch_unwind(|| {
-                UniformRandomDistStatisticalTest::perform_selected_tests(&data)
-            });
-            match results {
-                Ok(res) => self.statistical_test_results = res,
-                Err(_) => {}
+    /// Checks that a datum fits the session and returns the range it is taken with.
+    fn check_natural_datum(&self, datum_group: &NaturalDatumGroup) -> Result<u64, ProcessError> {
+        let datum_range = *datum_group.get_range();
+        if !self.is_range_accepted(datum_range) {
+            Err(ProcessError::DatumRangeIsNotAcceptedInSession { range: datum_range })
+        } else {
+            Ok(datum_range)
+        }
+    }
+
+    fn apply_natural_datum(&mut self, datum_group: &NaturalDatumGroup) -> Result<(), ProcessError> {
+        let datum_values = datum_group.get_values();
+        if self.is_entropy_ready {
+            Err(ProcessError::EntropyAlreadyCreated)
+        } else {
+            self.check_health_test_failure_is_acknowledged()?;
+            let datum_range = self.check_natural_datum(datum_group)?;
+            let combined_range = combined_range(datum_range, datum_values.len())?;
+            if let Some(failure) = self.run_health_tests(datum_values, datum_range) {
+                let error = ProcessError::NaturalDatumFailedHealthTest {
+                    test: failure.get_test().get_name(),
+                    range: *failure.get_range(),
+                    face: self
+                        .face_alphabet
+                        .describe_face(*failure.get_face() - 1, *failure.get_range()),
+                };
+                self.health_test_failure = Some(failure);
+                return Err(error);
             }
-        };
+            self.sequence.extend(datum_values.iter());
+            self.sequence_ranges
+                .extend(datum_values.iter().map(|_| datum_range));
+            self.monitor_die_bias(datum_values, datum_range);
+            let zero_indexed_datum_value = combine_zero_indexed_values(datum_range, datum_values);
+            let remaining_entropy_bits = self.target_entropy_bits - self.generated_entropy_bits;
+            let step = if self.is_conditioned() {
+                ExtractionStep {
+                    entropy_generating_data: vec![zero_indexed_datum_value],
+                    bits: vec![],
+                }
+            } else if self.extractor.splits_datum_groups() {
+                self.absorb_faces(datum_values, datum_range)
+            } else {
+                self.extractor.absorb(
+                    zero_indexed_datum_value,
+                    combined_range,
+                    remaining_entropy_bits,
+                )
+            };
+            self.entropy_generating_sequence
+                .extend(step.entropy_generating_data.iter());
+            self.range_account_mut(datum_range).record(
+                datum_values,
+                step.entropy_generating_data.len() as u64,
+                step.bits.len() as u64,
+            );
+            self.data_since_last_extraction += datum_values.len() as u64;
+            if !step.bits.is_empty() {
+                self.extraction_costs.push(ExtractionCost::new(
+                    self.data_since_last_extraction,
+                    step.bits.len() as u64,
+                ));
+                self.data_since_last_extraction = 0;
+            }
+            self.push_entropy_bits(&step.bits);
+            if self.is_hashed_entropy_due() {
+                self.apply_hashed_entropy_finalization()?;
+            }
+            if self.is_conditioning_due() {
+                self.apply_conditioning();
+            }
+            Ok(())
+        }
     }
 
-    pub fn recover_original_entropy_bytes_after_mix(&mut self) {
-        self.final_entropy_bytes_vector = self.entropy_bytes_vector.clone();
-        self.mixed_entropy_and_rng_bytes_vector = vec![];
-        self.rng_entropy_bytes_vector = vec![];
-        self.perform_selected_statistical_tests();
-        self.generate_mnemonic();
+    /// Feeds the faces of a group to the extractor one at a time.
+    fn absorb_faces(&mut self, datum_values: &[u64], datum_range: u64) -> ExtractionStep {
+        let mut step = ExtractionStep::default();
+        for value in datum_values {
+            let remaining_entropy_bits = (self.target_entropy_bits - self.generated_entropy_bits)
+                .saturating_sub(step.bits.len() as u64);
+            let face_step = self
+                .extractor
+                .absorb(value - 1, datum_range, remaining_entropy_bits);
+            step.entropy_generating_data
+                .extend(face_step.entropy_generating_data.iter());
+            step.bits.extend(face_step.bits.iter());
+        }
+        step
     }
-}
 
-#[cfg(test)]
-mod test {
-    use crate::utils::test_utils::{
-        generate_pre_determined_series_of_data_till_entropy_is_full,
-        generate_random_data_sequence_till_entropy_is_full,
-    };
+    fn monitor_die_bias(&mut self, datum_values: &[u64], datum_range: u64) {
+        if let Some(monitor) = self.die_bias_monitor.as_mut() {
+            for value in datum_values {
+                let warning = monitor.absorb(*value, datum_range);
+                if self.die_bias_warning.is_none() {
+                    self.die_bias_warning = warning;
+                }
+            }
+        }
+    }
 
-    use super::*;
+    /// Runs the health tests on the faces of a datum, up to the first face that fails.
+    fn run_health_tests(
+        &mut self,
+        datum_values: &[u64],
+        datum_range: u64,
+    ) -> Option<HealthTestFailure> {
+        let monitor = self.health_test_monitor.as_mut()?;
+        datum_values
+            .iter()
+            .find_map(|value| monitor.absorb(*value, datum_range))
+    }
 
-    #[test]
-    fn default_works_01() {
-        let default = Tychentropy::default();
-        assert_eq!(*default.get_range(), 6);
-        assert_eq!(*default.get_target_entropy_bytes(), 32);
-        assert_eq!(*default.get_target_entropy_bits(), 256);
-        assert_eq!(*default.get_full_bits_in_each_datum(), 2);
+    fn check_health_test_failure_is_acknowledged(&self) -> Result<(), ProcessError> {
+        match &self.health_test_failure {
+            Some(failure) => Err(ProcessError::HealthTestFailureIsNotAcknowledged {
+                test: failure.get_test().get_name(),
+                range: *failure.get_range(),
+            }),
+            None => Ok(()),
+        }
     }
 
-    #[test]
-    fn new_works_in_normal_condition_01() {
-        let range = 3;
-        let num_bytes_of_target_entropy = 5;
-        let input = TychentropyNewInput::new(range, num_bytes_of_target_entropy).unwrap();
-        let tychentropy = Tychentropy::new(input);
-        assert_eq!(tychentropy.range, 3);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 1);
-        assert_eq!(tychentropy.target_entropy_bytes, 5);
-        assert_eq!(tychentropy.target_entropy_bits, 40);
-        assert_eq!(tychentropy.sequence, vec![]);
-        assert_eq!(tychentropy.entropy_generating_sequence, vec![]);
-        assert_eq!(tychentropy.generated_entropy_bits, 0);
-        assert_eq!(tychentropy.is_entropy_ready, false);
-        assert_eq!(tychentropy.entropy_bit_string, "");
-        assert_eq!(tychentropy.entropy_bit_vector, vec![]);
-        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    /// Clears the health test failure once the operator has seen it and, for example, swapped
+    /// the dice, so that data are accepted again.
+    pub fn acknowledge_health_test_failure(&mut self) -> Result<(), ProcessError> {
+        if self.health_test_failure.is_none() {
+            return Err(ProcessError::NoHealthTestFailureFound);
+        }
+        self.apply_health_test_failure_acknowledgement();
+        self.record_event(SessionEvent::HealthTestFailureAcknowledged);
+        Ok(())
     }
 
-    #[test]
-    fn add_datum_works_in_normal_condition_01() {
-        let range = 6;
-        let target_entropy_bytes = 1;
-        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
-        let mut tychentropy = Tychentropy::new(input);
-        let datum = NaturalDatum::new(range, 5).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    fn apply_health_test_failure_acknowledgement(&mut self) {
+        self.health_test_failure = None;
+    }
 
-        assert_eq!(tychentropy.range, 6);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
-        assert_eq!(tychentropy.target_entropy_bytes, 1);
-        assert_eq!(tychentropy.target_entropy_bits, 8);
-        assert_eq!(tychentropy.sequence, vec![5]);
-        assert_eq!(tychentropy.entropy_generating_sequence, vec![]);
-        assert_eq!(tychentropy.generated_entropy_bits, 0);
-        assert_eq!(tychentropy.is_entropy_ready, false);
-        assert_eq!(tychentropy.entropy_bit_string, "");
-        assert_eq!(tychentropy.entropy_bit_vector, vec![]);
-        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    /// Clears the die bias warning, once the operator has seen it and, for example, swapped the
+    /// dice. The bias monitor forgets the data added so far and starts again.
+    pub fn acknowledge_die_bias_warning(&mut self) -> Result<(), ProcessError> {
+        if self.die_bias_warning.is_none() {
+            return Err(ProcessError::NoDieBiasWarningFound);
+        }
+        self.apply_die_bias_warning_acknowledgement();
+        self.record_event(SessionEvent::DieBiasWarningAcknowledged);
+        Ok(())
     }
 
-    #[test]
-    fn add_datum_works_in_normal_condition_02() {
-        let range = 6;
-        let target_entropy_bytes = 1;
-        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
-        let mut tychentropy = Tychentropy::new(input);
-        let datum = NaturalDatum::new(range, 4).unwrap();
-        let _ = tychentropy.add_natural_datum(datum);
+    fn apply_die_bias_warning_acknowledgement(&mut self) {
+        self.die_bias_warning = None;
+        if let Some(monitor) = self.die_bias_monitor.as_mut() {
+            monitor.restart();
+        }
+    }
 
-        assert_eq!(tychentropy.range, 6);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
-        assert_eq!(tychentropy.target_entropy_bytes, 1);
-        assert_eq!(tychentropy.target_entropy_bits, 8);
-        assert_eq!(tychentropy.sequence, vec![4]);
-        assert_eq!(tychentropy.entropy_generating_sequence, vec![3]);
-        assert_eq!(tychentropy.generated_entropy_bits, 2);
-        assert_eq!(tychentropy.is_entropy_ready, false);
-        assert_eq!(tychentropy.entropy_bit_string, "11");
-        assert_eq!(tychentropy.entropy_bit_vector, vec![1, 1]);
-        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    /// Adds the order of a shuffled deck of cards. The Lehmer code of the permutation is turned
+    /// into bits with range coding whatever the session's extraction method is, since every
+    /// order of a well shuffled deck is equally likely.
+    pub fn add_deck_permutation(
+        &mut self,
+        deck_permutation: DeckPermutation,
+    ) -> Result<(), ProcessError> {
+        self.apply_deck_permutation(&deck_permutation)?;
+        self.record_event(SessionEvent::DeckPermutationAdded(deck_permutation));
+        Ok(())
     }
 
-    #[test]
-    fn add_datum_works_in_normal_condition_03() {
-        let range = 6;
-        let target_entropy_bytes = 1;
-        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
-        let mut tychentropy = Tychentropy::new(input);
-        let datum = NaturalDatum::new(range, 4).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    fn apply_deck_permutation(
+        &mut self,
+        deck_permutation: &DeckPermutation,
+    ) -> Result<(), ProcessError> {
+        if self.is_entropy_ready {
+            return Err(ProcessError::EntropyAlreadyCreated);
+        }
+        self.check_health_test_failure_is_acknowledged()?;
+        if self.extraction_method.is_hashed() || self.is_conditioned() {
+            return Err(ProcessError::ExtractionMethodDoesNotFitSession {
+                method: self.extraction_method.get_name(),
+                requirement: "rolls, not deck permutations".to_string(),
+            });
+        }
+        let remaining_entropy_bits = self.target_entropy_bits - self.generated_entropy_bits;
+        let mut state = RangeCodingState::new();
+        let mut bits = vec![];
+        let mut absorbed_digits = 0;
+        for digit in deck_permutation.get_lehmer_code().iter() {
+            let remaining_deck_bits = remaining_entropy_bits - bits.len() as u64;
+            if remaining_deck_bits == 0 {
+                break;
+            }
+            bits.extend(state.absorb(*digit.get_value(), *digit.get_range(), remaining_deck_bits));
+            absorbed_digits += 1;
+        }
+        bits.extend(state.flush());
+        bits.truncate(remaining_entropy_bits as usize);
+        self.deck_permutations.push(deck_permutation.clone());
+        self.data_since_last_extraction += absorbed_digits;
+        if !bits.is_empty() {
+            self.extraction_costs.push(ExtractionCost::new(
+                self.data_since_last_extraction,
+                bits.len() as u64,
+            ));
+            self.data_since_last_extraction = 0;
+        }
+        self.push_entropy_bits(&bits);
+        Ok(())
+    }
 
-        let datum = NaturalDatum::new(range, 3).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    /// Whether the session conditions its data. Hashed extraction methods leave conditioning
+    /// out, as they hash the data themselves.
+    fn is_conditioned(&self) -> bool {
+        self.conditioning.is_some() && !self.extraction_method.is_hashed()
+    }
 
-        assert_eq!(tychentropy.range, 6);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
-        assert_eq!(tychentropy.target_entropy_bytes, 1);
-        assert_eq!(tychentropy.target_entropy_bits, 8);
-        assert_eq!(tychentropy.sequence, vec![4, 3]);
-        assert_eq!(tychentropy.entropy_generating_sequence, vec![3, 2]);
-        assert_eq!(tychentropy.generated_entropy_bits, 4);
-        assert_eq!(tychentropy.is_entropy_ready, false);
-        assert_eq!(tychentropy.entropy_bit_string, "1110");
-        assert_eq!(tychentropy.entropy_bit_vector, vec![1, 1, 1, 0]);
-        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    /// Min-entropy of the rolls added so far if every die is fair, `log2(range)` for each roll.
+    pub fn get_credited_entropy_bits(&self) -> f64 {
+        self.sequence_ranges
+            .iter()
+            .map(|range| (*range as f64).log2())
+            .sum()
     }
 
-    #[test]
-    fn add_datum_works_in_normal_condition_04() {
-        let range = 6;
-        let target_entropy_bytes = 1;
-        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
-        let mut tychentropy = Tychentropy::new(input);
-        let datum = NaturalDatum::new(range, 4).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    fn is_conditioning_due(&self) -> bool {
+        match &self.conditioning {
+            Some(conditioning) if self.is_conditioned() && !self.is_entropy_ready => {
+                self.get_credited_entropy_bits()
+                    >= conditioning.get_required_entropy_bits(self.target_entropy_bits)
+            }
+            _ => false,
+        }
+    }
 
-        let datum = NaturalDatum::new(range, 5).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    /// Makes the entropy by conditioning the whole sequence, rejected rolls included.
+    fn apply_conditioning(&mut self) {
+        let conditioning = match &self.conditioning {
+            Some(conditioning) => conditioning.clone(),
+            None => return,
+        };
+        let mut entropy_bytes = conditioning.condition(
+            &self.sequence,
+            &self.sequence_ranges,
+            self.target_entropy_bits,
+        );
+        let mut bits = self
+            .bit_order
+            .unpack(&entropy_bytes, self.target_entropy_bits);
+        self.conditioning_record = Some(ConditioningRecord::new(
+            &conditioning,
+            self.sequence.len() as u64,
+            self.get_credited_entropy_bits(),
+        ));
+        self.extraction_costs.push(ExtractionCost::new(
+            self.data_since_last_extraction,
+            bits.len() as u64,
+        ));
+        self.data_since_last_extraction = 0;
+        self.push_entropy_bits(&bits);
+        entropy_bytes.zeroize();
+        bits.zeroize();
+    }
 
-        let datum = NaturalDatum::new(range, 3).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    /// Every roll added so far as raw bits, each written as its zero indexed value with
+    /// `ceil(log2(range))` bits, most significant bit first. Rejected rolls are included.
+    pub fn get_raw_bit_stream(&self) -> Vec<u8> {
+        self.sequence
+            .iter()
+            .zip(self.sequence_ranges.iter())
+            .flat_map(|(value, range)| {
+                u128_to_bit_vec((value - 1) as u128, (range - 1).ilog2() + 1)
+            })
+            .collect()
+    }
 
-        let datum = NaturalDatum::new(range, 6).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    /// Runs a Toeplitz extractor over the raw bit stream of the session, credited with
+    /// `min_entropy_bits` bits of min-entropy in total. The session itself is left as it is.
+    pub fn extract_with_toeplitz(
+        &self,
+        extractor: &ToeplitzExtractor,
+        min_entropy_bits: f64,
+    ) -> Result<ToeplitzExtraction, ProcessError> {
+        extractor.extract(&self.get_raw_bit_stream(), min_entropy_bits)
+    }
 
-        let datum = NaturalDatum::new(range, 2).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    /// Makes the entropy of a session with a hashed extraction method, such as
+    /// [`ExtractionMethod::Coldcard`], from all the data added so far.
+    /// [`ExtractionMethod::IanColemanRaw`] is finalized the same way, although it converts the
+    /// data instead of hashing them.
+    pub fn finalize_hashed_entropy(&mut self) -> Result<(), ProcessError> {
+        self.apply_hashed_entropy_finalization()?;
+        self.record_event(SessionEvent::HashedEntropyFinalized);
+        Ok(())
+    }
 
-        assert_eq!(tychentropy.range, 6);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
-        assert_eq!(tychentropy.target_entropy_bytes, 1);
-        assert_eq!(tychentropy.target_entropy_bits, 8);
-        assert_eq!(tychentropy.sequence, vec![4, 5, 3, 6, 2]);
-        assert_eq!(tychentropy.entropy_generating_sequence, vec![3, 2, 1]);
-        assert_eq!(tychentropy.generated_entropy_bits, 6);
-        assert_eq!(tychentropy.is_entropy_ready, false);
-        assert_eq!(tychentropy.entropy_bit_string, "111001");
-        assert_eq!(tychentropy.entropy_bit_vector, vec![1, 1, 1, 0, 0, 1]);
-        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    fn apply_hashed_entropy_finalization(&mut self) -> Result<(), ProcessError> {
+        if self.is_entropy_ready {
+            return Err(ProcessError::EntropyAlreadyCreated);
+        }
+        self.check_health_test_failure_is_acknowledged()?;
+        let entropy_bytes = match self.extraction_method {
+            ExtractionMethod::Coldcard => {
+                self.check_session_range(Some(6))?;
+                self.check_target_bits(&[256])?;
+                self.check_hashed_data_len(COLDCARD_MIN_ROLLS)?;
+                sha256_of_roll_digits(&self.sequence)
+            }
+            ExtractionMethod::SeedSigner => {
+                self.check_session_range(Some(6))?;
+                self.check_target_bits(&[128, 256])?;
+                let rolls = match self.target_entropy_bits {
+                    128 => SEEDSIGNER_12_WORD_ROLLS,
+                    _ => SEEDSIGNER_24_WORD_ROLLS,
+                };
+                self.check_hashed_data_len(rolls)?;
+                sha256_of_roll_digits(&self.sequence)
+            }
+            ExtractionMethod::IanColemanRaw | ExtractionMethod::IanColemanHashed => {
+                self.check_session_range(None)?;
+                self.check_target_bits(&[128, 160, 192, 224, 256])?;
+                self.get_ian_coleman_entropy_bytes()?
+            }
+            _ => {
+                return Err(ProcessError::ExtractionMethodIsNotHashed {
+                    method: self.extraction_method.get_name(),
+                })
+            }
+        };
+        // Unpacking with the session's bit order makes the final bytes equal to the tool's.
+        let bits = self
+            .bit_order
+            .unpack(&entropy_bytes, self.target_entropy_bits);
+        self.extraction_costs.push(ExtractionCost::new(
+            self.data_since_last_extraction,
+            bits.len() as u64,
+        ));
+        self.data_since_last_extraction = 0;
+        self.push_entropy_bits(&bits);
+        Ok(())
     }
 
-    #[test]
-    fn add_datum_works_in_normal_condition_05() {
-        let range = 6;
-        let target_entropy_bytes = 1;
-        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
-        let mut tychentropy = Tychentropy::new(input);
-        let datum = NaturalDatum::new(range, 4).unwrap();
+    /// Whether the last datum completed the data of a method that takes an exact number of
+    /// them, so the entropy can be made right away.
+    fn is_hashed_entropy_due(&self) -> bool {
+        let data_len = self.sequence.len() as u64;
+        self.extraction_method == ExtractionMethod::SeedSigner
+            && self.health_test_failure.is_none()
+            && self.check_session_range(Some(6)).is_ok()
+            && match self.target_entropy_bits {
+                128 => data_len == SEEDSIGNER_12_WORD_ROLLS,
+                256 => data_len == SEEDSIGNER_24_WORD_ROLLS,
+                _ => false,
+            }
+    }
+
+    /// Entropy the Ian Coleman BIP39 tool makes from the session's rolls, typed as their face
+    /// labels with nothing between them.
+    fn get_ian_coleman_entropy_bytes(&self) -> Result<Vec<u8>, ProcessError> {
+        let method = self.extraction_method.get_name();
+        let labels = self
+            .sequence
+            .iter()
+            .map(|value| self.face_alphabet.describe_face(value - 1, self.range))
+            .collect::<Vec<String>>();
+        let entropy = labels
+            .iter()
+            .all(|label| label.len() == 1)
+            .then(|| IanColemanEntropy::from_digit_string(&labels.concat()))
+            .flatten()
+            .ok_or_else(|| ProcessError::ExtractionMethodDoesNotFitSession {
+                method: method.clone(),
+                requirement: "faces labelled with single digits".to_string(),
+            })?;
+        if entropy.get_expected_bits() < self.target_entropy_bits {
+            let bits_per_datum = (entropy.get_base() as f64).log2();
+            let required_data_len = (1..)
+                .find(|data_len| {
+                    (*data_len as f64 * bits_per_datum).floor() as u64 >= self.target_entropy_bits
+                })
+                .unwrap();
+            return Err(ProcessError::NotEnoughDataForHashedEntropy {
+                data_len: self.sequence.len() as u64,
+                required_data_len,
+            });
+        }
+        if self.extraction_method == ExtractionMethod::IanColemanHashed {
+            return Ok(entropy.get_hashed_bytes(self.target_entropy_bits));
+        }
+        let bits = entropy.get_raw_mnemonic_bits();
+        if bits.len() as u64 != self.target_entropy_bits {
+            return Err(ProcessError::ExtractionMethodDoesNotFitSession {
+                method,
+                requirement: format!(
+                    "a target of {} bits to use all {} data",
+                    bits.len(),
+                    self.sequence.len()
+                ),
+            });
+        }
+        Ok(BitOrder::MsbFirst.pack(&bits))
+    }
+
+    /// Checks that the session does not mix dice types and, if given, uses dice of `range`.
+    fn check_session_range(&self, range: Option<u64>) -> Result<(), ProcessError> {
+        if self.accepted_ranges.is_some() || range.is_some_and(|range| range != self.range) {
+            let requirement = match range {
+                Some(range) => format!("a session of d{} rolls only", range),
+                None => "a session with a single die type".to_string(),
+            };
+            Err(ProcessError::ExtractionMethodDoesNotFitSession {
+                method: self.extraction_method.get_name(),
+                requirement,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_target_bits(&self, target_entropy_bits: &[u64]) -> Result<(), ProcessError> {
+        if target_entropy_bits.contains(&self.target_entropy_bits) {
+            Ok(())
+        } else {
+            let targets = target_entropy_bits
+                .iter()
+                .map(|bits| bits.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            Err(ProcessError::ExtractionMethodDoesNotFitSession {
+                method: self.extraction_method.get_name(),
+                requirement: format!("a target of {} bits", targets),
+            })
+        }
+    }
+
+    fn check_hashed_data_len(&self, required_data_len: u64) -> Result<(), ProcessError> {
+        let data_len = self.sequence.len() as u64;
+        if data_len < required_data_len {
+            Err(ProcessError::NotEnoughDataForHashedEntropy {
+                data_len,
+                required_data_len,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Rolls added so far written with the session's face labels and separated by spaces, so
+    /// that the log can be read back with `add_rolls_from_text`. In a session that mixes dice
+    /// types each roll is written as `d<range>:<label>`, such as `d20:13`.
+    pub fn get_roll_log(&self) -> String {
+        self.sequence
+            .iter()
+            .zip(self.sequence_ranges.iter())
+            .map(|(value, range)| {
+                let label = self.face_alphabet.describe_face(value - 1, *range);
+                match self.accepted_ranges {
+                    None => label,
+                    Some(_) => format!("d{}:{}", range, label),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    pub fn is_range_accepted(&self, range: u64) -> bool {
+        match &self.accepted_ranges {
+            None => range == self.range,
+            Some(accepted_ranges) => accepted_ranges.contains(&range),
+        }
+    }
+
+    /// Bookkeeping of the data of one die type, if any datum of that range has been added.
+    pub fn get_range_account(&self, range: u64) -> Option<&RangeAccount> {
+        self.range_accounts
+            .iter()
+            .find(|account| *account.get_range() == range)
+    }
+
+    fn range_account_mut(&mut self, range: u64) -> &mut RangeAccount {
+        let index = match self
+            .range_accounts
+            .iter()
+            .position(|account| *account.get_range() == range)
+        {
+            Some(index) => index,
+            None => {
+                self.range_accounts.push(RangeAccount::new(range));
+                self.range_accounts.len() - 1
+            }
+        };
+        &mut self.range_accounts[index]
+    }
+
+    fn push_entropy_bits(&mut self, bits: &[u8]) {
+        if bits.is_empty() {
+            return;
+        }
+        bits.iter().for_each(|bit| {
+            self.entropy_bit_string
+                .push(if *bit == 1 { '1' } else { '0' })
+        });
+        self.entropy_bit_vector.extend(bits);
+        self.generated_entropy_bits += bits.len() as u64;
+        if self.generated_entropy_bits >= self.target_entropy_bits {
+            self.is_entropy_ready = true;
+            self.entropy_bit_string
+                .truncate(self.target_entropy_bits as usize);
+            self.entropy_bit_vector
+                .truncate(self.target_entropy_bits as usize);
+
+            // The unused positions of a partial last byte are left as zero bits.
+            let entropy_bytes_vector = self.bit_order.pack(&self.entropy_bit_vector);
+            self.entropy_bytes_vector = entropy_bytes_vector.clone();
+            self.final_entropy_bytes_vector = entropy_bytes_vector;
+            self.perform_selected_statistical_tests();
+            self.generate_mnemonic();
+        }
+    }
+
+    /// Average number of raw data spent on each generated entropy bit, or `None` before the
+    /// first bit. Useful to see how much a debiasing extraction method costs on a given source.
+    pub fn get_average_data_cost_per_entropy_bit(&self) -> Option<f64> {
+        let (consumed_data, emitted_bits) =
+            self.extraction_costs
+                .iter()
+                .fold((0, 0), |(data, bits), cost| {
+                    (
+                        data + cost.get_consumed_data(),
+                        bits + cost.get_emitted_bits(),
+                    )
+                });
+        if emitted_bits == 0 {
+            None
+        } else {
+            Some(consumed_data as f64 / emitted_bits as f64)
+        }
+    }
+
+    /// Mixes the entropy with the XOR of `rand`'s thread rng and `ring`'s system rng.
+    pub fn mix_with_rng(&mut self) -> Result<(), ProcessError> {
+        let mut thread_rng = CryptoRngSource::new(rand::thread_rng());
+        let mut system_random = SecureRandomSource::new(SystemRandom::new());
+        self.mix_with_rng_sources(&mut [&mut thread_rng, &mut system_random])
+    }
+
+    /// Mixes the entropy with the XOR of the bytes of `sources`, using the session's mixing
+    /// strategy. Nothing changes if a source fails. A session committed to rng bytes can only
+    /// be mixed with them, by [`Tychentropy::mix_with_committed_rng`].
+    pub fn mix_with_rng_sources(
+        &mut self,
+        sources: &mut [&mut dyn RngSource],
+    ) -> Result<(), ProcessError> {
+        if self.rng_commitment.is_some() {
+            Err(ProcessError::RngIsCommittedAndMustBeRevealed)
+        } else if !self.is_entropy_ready {
+            Err(ProcessError::EntropyBitsAreNotReady {
+                required_entropy_bits: self.target_entropy_bits,
+                current_entropy_bits: self.generated_entropy_bits,
+            })
+        } else {
+            let mut mixed = fill_from_rng_sources(sources, self.target_entropy_bytes as usize)?;
+            self.clear_padding_bits(&mut mixed);
+            self.apply_rng_mix(&mixed)?;
+            self.record_event(SessionEvent::MixApplied {
+                rng_entropy_bytes: mixed,
+            });
+            Ok(())
+        }
+    }
+
+    /// Commits the session to rng bytes from `rand`'s thread rng and `ring`'s system rng, as
+    /// [`Tychentropy::commit_to_rng_sources`] does.
+    pub fn commit_to_rng(&mut self) -> Result<Vec<u8>, ProcessError> {
+        let mut thread_rng = CryptoRngSource::new(rand::thread_rng());
+        let mut system_random = SecureRandomSource::new(SystemRandom::new());
+        self.commit_to_rng_sources(&mut [&mut thread_rng, &mut system_random])
+    }
+
+    /// Draws the rng contribution from `sources` before any data are added and returns its
+    /// SHA-256 commitment, to be shown or written down before rolling. The bytes stay hidden
+    /// until [`Tychentropy::mix_with_committed_rng`] reveals them.
+    pub fn commit_to_rng_sources(
+        &mut self,
+        sources: &mut [&mut dyn RngSource],
+    ) -> Result<Vec<u8>, ProcessError> {
+        if self.rng_commitment.is_some() {
+            return Err(ProcessError::RngIsAlreadyCommitted);
+        }
+        if self.history[self.get_session_start()..]
+            .iter()
+            .any(|event| event.is_data_event())
+        {
+            return Err(ProcessError::RngCommitmentMustPrecedeData);
+        }
+        let mut rng_entropy_bytes =
+            fill_from_rng_sources(sources, self.target_entropy_bytes as usize)?;
+        self.clear_padding_bits(&mut rng_entropy_bytes);
+        let rng_commitment = RngCommitment::new(&rng_entropy_bytes);
+        let commitment = rng_commitment.get_commitment().clone();
+        self.rng_commitment = Some(rng_commitment);
+        self.committed_rng_entropy_bytes = CommittedRngBytes::new(rng_entropy_bytes);
+        self.record_event(SessionEvent::RngCommitted {
+            commitment: commitment.clone(),
+        });
+        Ok(commitment)
+    }
+
+    /// Reveals the committed rng bytes, checks them against `shown_commitment`, the commitment
+    /// the operator wrote down before rolling, and mixes the entropy with them using the
+    /// session's mixing strategy. The reveal and whether it matched are recorded either way;
+    /// bytes that do not match are not mixed in.
+    pub fn mix_with_committed_rng(&mut self, shown_commitment: &[u8]) -> Result<(), ProcessError> {
+        if !self.is_entropy_ready {
+            return Err(ProcessError::EntropyBitsAreNotReady {
+                required_entropy_bits: self.target_entropy_bits,
+                current_entropy_bits: self.generated_entropy_bits,
+            });
+        }
+        let rng_commitment = self
+            .rng_commitment
+            .as_mut()
+            .ok_or(ProcessError::NoRngCommitmentFound)?;
+        let rng_entropy_bytes = rng_commitment.reveal();
+        let matches_commitment = rng_commitment.check_reveal(shown_commitment);
+        if matches_commitment {
+            self.apply_rng_mix(&rng_entropy_bytes)?;
+        }
+        self.record_event(SessionEvent::RngRevealed {
+            rng_entropy_bytes,
+            shown_commitment: shown_commitment.to_vec(),
+            matches_commitment,
+        });
+        if matches_commitment {
+            Ok(())
+        } else {
+            Err(ProcessError::RngRevealDoesNotMatchCommitment)
+        }
+    }
+
+    fn apply_rng_reveal(&mut self, shown_commitment: &[u8]) -> Result<(), ProcessError> {
+        let rng_commitment = self
+            .rng_commitment
+            .as_mut()
+            .ok_or(ProcessError::NoRngCommitmentFound)?;
+        let rng_entropy_bytes = rng_commitment.reveal();
+        if rng_commitment.check_reveal(shown_commitment) {
+            self.apply_rng_mix(&rng_entropy_bytes)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Mixes the entropy with bytes from outside, such as a dump of an air gapped hardware rng,
+    /// using the session's mixing strategy. There must be exactly as many bytes as the target
+    /// entropy bytes, and the bytes must pass the selected statistical tests. Every test must
+    /// run, except block frequency and longest run of ones on less than 128 bits, so bytes are
+    /// rejected when the tests cannot be run. Unused bits of a partial last byte are zeroed.
+    /// Returns the test results of the bytes.
+    ///
+    /// The tests share a significance level of 1% through a Bonferroni correction, so bytes
+    /// drawn uniformly at random are rejected about 1% of the time rather than the 6% they
+    /// would be if every test had to pass on its own at 1%.
+    ///
+    /// A session committed to rng bytes can only be mixed with them, by
+    /// [`Tychentropy::mix_with_committed_rng`].
+    pub fn mix_with_external_entropy(
+        &mut self,
+        external_entropy_bytes: &[u8],
+    ) -> Result<StatisticalTestsResults, ProcessError> {
+        if self.rng_commitment.is_some() {
+            return Err(ProcessError::RngIsCommittedAndMustBeRevealed);
+        }
+        if !self.is_entropy_ready {
+            return Err(ProcessError::EntropyBitsAreNotReady {
+                required_entropy_bits: self.target_entropy_bits,
+                current_entropy_bits: self.generated_entropy_bits,
+            });
+        }
+        if external_entropy_bytes.len() as u64 != self.target_entropy_bytes {
+            return Err(ProcessError::ProvidedRngEntropyLengthDoesNotMatchTarget {
+                provided_len: external_entropy_bytes.len() as u64,
+                target_len: self.target_entropy_bytes,
+            });
+        }
+        let mut external_entropy_bytes = external_entropy_bytes.to_vec();
+        self.clear_padding_bits(&mut external_entropy_bytes);
+        let data = external_entropy_bytes.clone();
+        let results =
+            panic::catch_unwind(|| UniformRandomDistStatisticalTest::perform_selected_tests(&data))
+                .unwrap_or_default();
+        let overview = StatisticalTestsOverview::from(&results);
+        let missing_tests = overview.get_missing_tests(self.target_entropy_bits as usize);
+        if !missing_tests.is_empty() {
+            return Err(ProcessError::ExternalEntropyStatisticalTestsDidNotRun { missing_tests });
+        }
+        let failed_tests = results
+            .get_failed_tests_with_bonferroni_correction(EXTERNAL_ENTROPY_SIGNIFICANCE_LEVEL);
+        if !failed_tests.is_empty() {
+            return Err(ProcessError::ExternalEntropyFailedStatisticalTests { failed_tests });
+        }
+        self.apply_rng_mix(&external_entropy_bytes)?;
+        self.record_event(SessionEvent::MixApplied {
+            rng_entropy_bytes: external_entropy_bytes,
+        });
+        Ok(results)
+    }
+
+    /// Reads all the bytes of `reader` and mixes them as `mix_with_external_entropy` does.
+    pub fn mix_with_external_entropy_from_reader<R: Read>(
+        &mut self,
+        mut reader: R,
+    ) -> Result<StatisticalTestsResults, ProcessError> {
+        let mut external_entropy_bytes = vec![];
+        let read_result = reader.read_to_end(&mut external_entropy_bytes);
+        let result = match read_result {
+            Ok(_) => self.mix_with_external_entropy(&external_entropy_bytes),
+            Err(error) => Err(ProcessError::ExternalEntropyCouldNotBeRead {
+                error: error.to_string(),
+            }),
+        };
+        external_entropy_bytes.zeroize();
+        result
+    }
+
+    /// Reads the whole file at `path` and mixes its bytes as `mix_with_external_entropy` does.
+    pub fn mix_with_external_entropy_from_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<StatisticalTestsResults, ProcessError> {
+        let file =
+            File::open(path).map_err(|error| ProcessError::ExternalEntropyCouldNotBeRead {
+                error: error.to_string(),
+            })?;
+        self.mix_with_external_entropy_from_reader(file)
+    }
+
+    fn apply_rng_mix(&mut self, rng_entropy_bytes: &[u8]) -> Result<(), ProcessError> {
+        if !self.is_entropy_ready {
+            return Err(ProcessError::EntropyBitsAreNotReady {
+                required_entropy_bits: self.target_entropy_bits,
+                current_entropy_bits: self.generated_entropy_bits,
+            });
+        }
+        let mixing_record = MixingRecord::new(
+            &self.mixing_strategy,
+            self.target_entropy_bits,
+            rng_entropy_bytes,
+        );
+        let mut mixed = mixing_record.remix(&self.entropy_bytes_vector)?;
+        self.clear_padding_bits(&mut mixed);
+        self.rng_entropy_bytes_vector = rng_entropy_bytes.to_vec();
+        self.final_entropy_bytes_vector = mixed;
+        self.mixing_record = Some(mixing_record);
+        self.perform_selected_statistical_tests();
+        self.generate_mnemonic();
+        Ok(())
+    }
+
+    /// Zeroes the unused positions of a partial last byte, so mixing keeps the padding defined.
+    fn clear_padding_bits(&self, bytes: &mut [u8]) {
+        let used_bits_in_last_byte = (self.target_entropy_bits % 8) as u32;
+        if used_bits_in_last_byte != 0 {
+            if let Some(last_byte) = bytes.last_mut() {
+                *last_byte &= self.bit_order.get_used_bits_mask(used_bits_in_last_byte);
+            }
+        }
+    }
+
+    /// Final entropy as bits, exactly `target_entropy_bits` long once entropy is ready and
+    /// empty before. Unlike the bytes, it carries no padding.
+    pub fn get_final_entropy_bit_vector(&self) -> Vec<u8> {
+        if !self.is_entropy_ready {
+            return vec![];
+        }
+        self.bit_order
+            .unpack(&self.final_entropy_bytes_vector, self.target_entropy_bits)
+    }
+
+    /// Takes back the last datum or deck permutation added since the session was created or
+    /// reset. Mixes applied after it are taken back too. The state is rebuilt from the
+    /// remaining events, so it is exactly what it was before the datum was added.
+    ///
+    /// A health test failure must be acknowledged first, as taking back the data before it
+    /// would clear it unseen.
+    pub fn undo_last_datum(&mut self) -> Result<(), ProcessError> {
+        self.check_health_test_failure_is_acknowledged()?;
+        let session_start = self.get_session_start();
+        let last_data_event = self.history[session_start..]
+            .iter()
+            .rposition(|event| event.is_data_event())
+            .ok_or(ProcessError::NoSequenceAppendixFound)?;
+        let undone_events = self.history.split_off(session_start + last_data_event);
+        self.redo_stack.push(undone_events);
+        self.rebuild_from_history()
+    }
+
+    /// Applies again the events taken back by the last [`Tychentropy::undo_last_datum`]. If
+    /// any of them fails, the session and the events to redo are left as they were.
+    pub fn redo(&mut self) -> Result<(), ProcessError> {
+        let events = self
+            .redo_stack
+            .last()
+            .ok_or(ProcessError::NothingToRedo)?
+            .clone();
+        let history_len = self.history.len();
+        for event in events.iter() {
+            if let Err(error) = self.apply_event(event) {
+                self.history.truncate(history_len);
+                self.rebuild_from_history()?;
+                return Err(error);
+            }
+            self.history.push(event.clone());
+        }
+        self.redo_stack.pop();
+        Ok(())
+    }
+
+    fn record_event(&mut self, event: SessionEvent) {
+        self.history.push(event);
+        self.redo_stack.clear();
+    }
+
+    /// Index of the first event after the last reset.
+    fn get_session_start(&self) -> usize {
+        self.history
+            .iter()
+            .rposition(|event| matches!(event, SessionEvent::Reset))
+            .map_or(0, |index| index + 1)
+    }
+
+    fn apply_event(&mut self, event: &SessionEvent) -> Result<(), ProcessError> {
+        match event {
+            SessionEvent::DatumAdded(datum_group) => self.apply_natural_datum(datum_group),
+            SessionEvent::DatumRejected(datum_group) => {
+                match self.apply_natural_datum(datum_group) {
+                    Err(ProcessError::NaturalDatumFailedHealthTest { .. }) => Ok(()),
+                    result => result,
+                }
+            }
+            SessionEvent::DeckPermutationAdded(deck_permutation) => {
+                self.apply_deck_permutation(deck_permutation)
+            }
+            SessionEvent::MixApplied { rng_entropy_bytes } => self.apply_rng_mix(rng_entropy_bytes),
+            SessionEvent::MixReverted => {
+                self.apply_mix_revert();
+                Ok(())
+            }
+            SessionEvent::RngCommitted { commitment } => {
+                self.rng_commitment = Some(RngCommitment::with_commitment(
+                    commitment,
+                    self.committed_rng_entropy_bytes.get_bytes(),
+                ));
+                Ok(())
+            }
+            SessionEvent::RngRevealed {
+                shown_commitment, ..
+            } => self.apply_rng_reveal(shown_commitment),
+            SessionEvent::HashedEntropyFinalized => self.apply_hashed_entropy_finalization(),
+            SessionEvent::DieBiasWarningAcknowledged => {
+                self.apply_die_bias_warning_acknowledgement();
+                Ok(())
+            }
+            SessionEvent::HealthTestFailureAcknowledged => {
+                self.apply_health_test_failure_acknowledgement();
+                Ok(())
+            }
+            SessionEvent::Reset => {
+                self.clear_derived_state();
+                Ok(())
+            }
+        }
+    }
+
+    /// Derives the whole state again from the events recorded since the last reset.
+    fn rebuild_from_history(&mut self) -> Result<(), ProcessError> {
+        self.clear_derived_state();
+        let session_events = self.history[self.get_session_start()..].to_vec();
+        session_events
+            .iter()
+            .try_for_each(|event| self.apply_event(event))
+    }
+
+    /// Brings the state back to that of a new session, keeping the history.
+    fn clear_derived_state(&mut self) {
+        let history = std::mem::take(&mut self.history);
+        let redo_stack = std::mem::take(&mut self.redo_stack);
+        let committed_rng_entropy_bytes = std::mem::take(&mut self.committed_rng_entropy_bytes);
+        let new_tych_entropy = Tychentropy::new(self.to_new_input());
+        self.put_data_from_another_tychentropy(new_tych_entropy);
+        self.history = history;
+        self.redo_stack = redo_stack;
+        self.committed_rng_entropy_bytes = committed_rng_entropy_bytes;
+    }
+
+    pub fn put_data_from_another_tychentropy(&mut self, other: Tychentropy) {
+        self.range = other.range;
+        self.accepted_ranges = other.accepted_ranges.clone();
+        self.full_bits_in_each_datum = other.full_bits_in_each_datum;
+        self.extraction_method = other.extraction_method.clone();
+        self.face_alphabet = other.face_alphabet.clone();
+        self.bit_order = other.bit_order.clone();
+        self.mixing_strategy = other.mixing_strategy.clone();
+        self.mixing_record = other.mixing_record.clone();
+        self.die_bias_monitor = other.die_bias_monitor.clone();
+        self.die_bias_warning = other.die_bias_warning.clone();
+        self.health_test_monitor = other.health_test_monitor.clone();
+        self.health_test_failure = other.health_test_failure.clone();
+        self.rng_commitment = other.rng_commitment.clone();
+        self.committed_rng_entropy_bytes = other.committed_rng_entropy_bytes.clone();
+        self.conditioning = other.conditioning.clone();
+        self.conditioning_record = other.conditioning_record.clone();
+        self.extractor = other.extractor.clone();
+        self.target_entropy_bits = other.target_entropy_bits;
+        self.target_entropy_bytes = other.target_entropy_bytes;
+        self.sequence = other.sequence.clone();
+        self.sequence_ranges = other.sequence_ranges.clone();
+        self.entropy_generating_sequence = other.entropy_generating_sequence.clone();
+        self.range_accounts = other.range_accounts.clone();
+        self.deck_permutations = other.deck_permutations.clone();
+        self.generated_entropy_bits = other.generated_entropy_bits;
+        self.extraction_costs = other.extraction_costs.clone();
+        self.data_since_last_extraction = other.data_since_last_extraction;
+        self.is_entropy_ready = other.is_entropy_ready;
+        self.entropy_bit_string = other.entropy_bit_string.clone();
+        self.entropy_bit_vector = other.entropy_bit_vector.clone();
+        self.entropy_bytes_vector = other.entropy_bytes_vector.clone();
+        self.rng_entropy_bytes_vector = other.rng_entropy_bytes_vector.clone();
+        self.mixed_entropy_and_rng_bytes_vector = other.mixed_entropy_and_rng_bytes_vector.clone();
+        self.final_entropy_bytes_vector = other.final_entropy_bytes_vector.clone();
+        self.mnemonic_length = other.mnemonic_length.clone();
+        self.mnemonic = other.mnemonic.clone();
+        self.statistical_test_results = other.statistical_test_results.clone();
+        self.history = other.history.clone();
+        self.redo_stack = other.redo_stack.clone();
+    }
+
+    pub fn reset_data(&mut self) {
+        self.record_event(SessionEvent::Reset);
+        self.clear_derived_state();
+        self.committed_rng_entropy_bytes.zeroize();
+    }
+
+    fn to_new_input(&self) -> TychentropyNewInput {
+        let mut input = match &self.accepted_ranges {
+            None => TychentropyNewInput::new(self.range, self.target_entropy_bytes),
+            Some(accepted_ranges) => TychentropyNewInput::new_with_mixed_ranges(
+                accepted_ranges.clone(),
+                self.target_entropy_bytes,
+            ),
+        }
+        .unwrap();
+        input
+            .set_target_entropy_bits(self.target_entropy_bits)
+            .unwrap();
+        input.set_extraction_method(self.extraction_method.clone());
+        input.set_extractor(self.extractor.new_empty());
+        input.set_bit_order(self.bit_order.clone());
+        input.set_mixing_strategy(self.mixing_strategy.clone());
+        input
+            .set_sprt_config(
+                self.die_bias_monitor
+                    .as_ref()
+                    .map(|monitor| monitor.get_config().clone()),
+            )
+            .unwrap();
+        input.set_health_test_config(
+            self.health_test_monitor
+                .as_ref()
+                .map(|monitor| monitor.get_config().clone()),
+        );
+        input.set_conditioning(self.conditioning.clone()).unwrap();
+        input.set_face_alphabet(self.face_alphabet.clone()).unwrap();
+        input
+    }
+
+    pub fn generate_mnemonic(&mut self) {
+        if self.is_entropy_ready && self.mnemonic_length.is_some() {
+            self.mnemonic = Some(Mnemonic::from_entropy(&self.final_entropy_bytes_vector).unwrap())
+        }
+    }
+
+    /// Mnemonic the session would have given when bytes were always packed least significant
+    /// bit first, whatever the session's bit order is. Helps to check or recover a mnemonic
+    /// made with that packing. A mix with rng entropy is applied to it again with the recorded
+    /// strategy.
+    pub fn get_legacy_lsb_first_mnemonic(&self) -> Option<Mnemonic> {
+        if !self.is_entropy_ready || self.mnemonic_length.is_none() {
+            return None;
+        }
+        let legacy_entropy_bytes = BitOrder::LsbFirst.pack(&self.entropy_bit_vector);
+        let legacy_final_entropy_bytes = match &self.mixing_record {
+            None => legacy_entropy_bytes,
+            Some(mixing_record) => {
+                let mut mixed = mixing_record.remix(&legacy_entropy_bytes).ok()?;
+                self.clear_padding_bits(&mut mixed);
+                mixed
+            }
+        };
+        Mnemonic::from_entropy(&legacy_final_entropy_bytes).ok()
+    }
+
+    pub fn perform_selected_statistical_tests(&mut self) {
+        let data = self.final_entropy_bytes_vector.clone();
+        if !data.is_empty() {
+            let results = panic::catch_unwind(|| {
+                UniformRandomDistStatisticalTest::perform_selected_tests(&data)
+            });
+            if let Ok(res) = results {
+                self.statistical_test_results = res;
+            }
+        };
+    }
+
+    /// Tests whether the faces rolled with dice of the session's range fit a fair die.
+    pub fn perform_goodness_of_fit_test(
+        &self,
+        statistic_kind: GoodnessOfFitStatistic,
+    ) -> Result<GoodnessOfFitTestResult, StatisticsError> {
+        self.perform_goodness_of_fit_test_for_range(self.range, statistic_kind)
+    }
+
+    /// Tests whether the faces rolled with dice of `range` fit a fair die. In a session that
+    /// mixes dice types, each range is tested on its own.
+    pub fn perform_goodness_of_fit_test_for_range(
+        &self,
+        range: u64,
+        statistic_kind: GoodnessOfFitStatistic,
+    ) -> Result<GoodnessOfFitTestResult, StatisticsError> {
+        let observations = self.get_observations_for_range(range);
+        RawDatumStatisticalTest::goodness_of_fit_test(&observations, 1, range, statistic_kind)
+    }
+
+    /// Tests whether consecutive faces rolled with dice of the session's range depend on each
+    /// other, with autocorrelations up to `max_lag`. Bias is left to the goodness of fit test.
+    pub fn perform_serial_dependence_test(
+        &self,
+        max_lag: u64,
+        significance_level: f64,
+    ) -> Result<SerialDependenceTestResult, StatisticsError> {
+        self.perform_serial_dependence_test_for_range(self.range, max_lag, significance_level)
+    }
+
+    /// Tests whether consecutive faces rolled with dice of `range` depend on each other. In a
+    /// session that mixes dice types, rolls of other ranges are skipped.
+    pub fn perform_serial_dependence_test_for_range(
+        &self,
+        range: u64,
+        max_lag: u64,
+        significance_level: f64,
+    ) -> Result<SerialDependenceTestResult, StatisticsError> {
+        let observations = self.get_observations_for_range(range);
+        RawDatumStatisticalTest::serial_dependence_test(
+            &observations,
+            1,
+            range,
+            max_lag,
+            significance_level,
+        )
+    }
+
+    /// Estimates the min-entropy of each datum of the session's range with the non-IID
+    /// estimators of NIST SP 800-90B, to set against `full_bits_in_each_datum`.
+    pub fn assess_min_entropy(&self) -> Result<MinEntropyAssessment, StatisticsError> {
+        self.assess_min_entropy_for_range(self.range)
+    }
+
+    /// Estimates the min-entropy of each datum rolled with dice of `range`. In a session that
+    /// mixes dice types, rolls of other ranges are skipped.
+    pub fn assess_min_entropy_for_range(
+        &self,
+        range: u64,
+    ) -> Result<MinEntropyAssessment, StatisticsError> {
+        let observations = self.get_observations_for_range(range);
+        MinEntropyEstimation::assess(&observations, 1, range)
+    }
+
+    fn get_observations_for_range(&self, range: u64) -> Vec<u64> {
+        self.sequence
+            .iter()
+            .zip(self.sequence_ranges.iter())
+            .filter(|(_, datum_range)| **datum_range == range)
+            .map(|(value, _)| *value)
+            .collect()
+    }
+
+    pub fn recover_original_entropy_bytes_after_mix(&mut self) {
+        self.apply_mix_revert();
+        self.record_event(SessionEvent::MixReverted);
+    }
+
+    fn apply_mix_revert(&mut self) {
+        self.final_entropy_bytes_vector = self.entropy_bytes_vector.clone();
+        self.mixed_entropy_and_rng_bytes_vector = vec![];
+        self.rng_entropy_bytes_vector = vec![];
+        self.mixing_record = None;
+        self.perform_selected_statistical_tests();
+        self.generate_mnemonic();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::utils::test_utils::{
+        generate_pre_determined_series_of_data_till_entropy_is_full,
+        generate_random_data_sequence_till_entropy_is_full, generate_random_datum,
+    };
+
+    use rand::seq::SliceRandom;
+
+    use crate::utils::domain_utils::vec_u8_to_bit_string;
+
+    use super::*;
+    use crate::domain::{
+        extraction::ConditioningFunction,
+        process::{input::NaturalDatum, rng_source::DeterministicRngSource},
+        statistics::{
+            bias_monitor::{BiasDirection, SprtConfig},
+            health_test::{HealthTest, HealthTestConfig},
+        },
+    };
+
+    #[test]
+    fn session_is_send_and_sync_01() {
+        fn assert_send_and_sync<T: Send + Sync>() {}
+        assert_send_and_sync::<Tychentropy>();
+    }
+
+    #[test]
+    fn default_works_01() {
+        let default = Tychentropy::default();
+        assert_eq!(*default.get_range(), 6);
+        assert_eq!(*default.get_target_entropy_bytes(), 32);
+        assert_eq!(*default.get_target_entropy_bits(), 256);
+        assert_eq!(*default.get_full_bits_in_each_datum(), 2);
+    }
+
+    #[test]
+    fn new_works_in_normal_condition_01() {
+        let range = 3;
+        let num_bytes_of_target_entropy = 5;
+        let input = TychentropyNewInput::new(range, num_bytes_of_target_entropy).unwrap();
+        let tychentropy = Tychentropy::new(input);
+        assert_eq!(tychentropy.range, 3);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 1);
+        assert_eq!(tychentropy.target_entropy_bytes, 5);
+        assert_eq!(tychentropy.target_entropy_bits, 40);
+        assert_eq!(tychentropy.sequence, vec![]);
+        assert_eq!(tychentropy.entropy_generating_sequence, vec![]);
+        assert_eq!(tychentropy.generated_entropy_bits, 0);
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "");
+        assert_eq!(tychentropy.entropy_bit_vector, vec![]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    }
+
+    #[test]
+    fn add_datum_works_in_normal_condition_01() {
+        let range = 6;
+        let target_entropy_bytes = 1;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let datum = NaturalDatum::new(range, 5).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        assert_eq!(tychentropy.range, 6);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
+        assert_eq!(tychentropy.target_entropy_bytes, 1);
+        assert_eq!(tychentropy.target_entropy_bits, 8);
+        assert_eq!(tychentropy.sequence, vec![5]);
+        assert_eq!(tychentropy.entropy_generating_sequence, vec![]);
+        assert_eq!(tychentropy.generated_entropy_bits, 0);
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "");
+        assert_eq!(tychentropy.entropy_bit_vector, vec![]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    }
+
+    #[test]
+    fn add_datum_works_in_normal_condition_02() {
+        let range = 6;
+        let target_entropy_bytes = 1;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let datum = NaturalDatum::new(range, 4).unwrap();
+        let _ = tychentropy.add_natural_datum(datum);
+
+        assert_eq!(tychentropy.range, 6);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
+        assert_eq!(tychentropy.target_entropy_bytes, 1);
+        assert_eq!(tychentropy.target_entropy_bits, 8);
+        assert_eq!(tychentropy.sequence, vec![4]);
+        assert_eq!(tychentropy.entropy_generating_sequence, vec![3]);
+        assert_eq!(tychentropy.generated_entropy_bits, 2);
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "11");
+        assert_eq!(tychentropy.entropy_bit_vector, vec![1, 1]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    }
+
+    #[test]
+    fn add_datum_works_in_normal_condition_03() {
+        let range = 6;
+        let target_entropy_bytes = 1;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let datum = NaturalDatum::new(range, 4).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 3).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        assert_eq!(tychentropy.range, 6);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
+        assert_eq!(tychentropy.target_entropy_bytes, 1);
+        assert_eq!(tychentropy.target_entropy_bits, 8);
+        assert_eq!(tychentropy.sequence, vec![4, 3]);
+        assert_eq!(tychentropy.entropy_generating_sequence, vec![3, 2]);
+        assert_eq!(tychentropy.generated_entropy_bits, 4);
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "1110");
+        assert_eq!(tychentropy.entropy_bit_vector, vec![1, 1, 1, 0]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    }
+
+    #[test]
+    fn add_datum_works_in_normal_condition_04() {
+        let range = 6;
+        let target_entropy_bytes = 1;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let datum = NaturalDatum::new(range, 4).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 5).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 3).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 6).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 2).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        assert_eq!(tychentropy.range, 6);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
+        assert_eq!(tychentropy.target_entropy_bytes, 1);
+        assert_eq!(tychentropy.target_entropy_bits, 8);
+        assert_eq!(tychentropy.sequence, vec![4, 5, 3, 6, 2]);
+        assert_eq!(tychentropy.entropy_generating_sequence, vec![3, 2, 1]);
+        assert_eq!(tychentropy.generated_entropy_bits, 6);
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "111001");
+        assert_eq!(tychentropy.entropy_bit_vector, vec![1, 1, 1, 0, 0, 1]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+    }
+
+    #[test]
+    fn add_datum_works_in_normal_condition_05() {
+        let range = 6;
+        let target_entropy_bytes = 1;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let datum = NaturalDatum::new(range, 4).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 5).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 3).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 6).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 2).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        let datum = NaturalDatum::new(range, 1).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        assert_eq!(tychentropy.range, 6);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
+        assert_eq!(tychentropy.target_entropy_bytes, 1);
+        assert_eq!(tychentropy.target_entropy_bits, 8);
+        assert_eq!(tychentropy.sequence, vec![4, 5, 3, 6, 2, 1]);
+        assert_eq!(tychentropy.entropy_generating_sequence, vec![3, 2, 1, 0]);
+        assert_eq!(tychentropy.generated_entropy_bits, 8);
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "11100100");
+        assert_eq!(tychentropy.entropy_bit_vector, vec![1, 1, 1, 0, 0, 1, 0, 0]);
+        assert_eq!(tychentropy.entropy_bytes_vector.len(), 1);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1);
+    }
+
+    #[test]
+    fn add_datum_works_in_normal_condition_06() {
+        let range = 6;
+        let target_entropy_bytes = 1;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+
+        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+
+        assert_eq!(tychentropy.range, 6);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
+        assert_eq!(tychentropy.target_entropy_bytes, 1);
+        assert_eq!(tychentropy.target_entropy_bits, 8);
+        assert_eq!(tychentropy.entropy_generating_sequence.len(), 4);
+        assert_eq!(tychentropy.generated_entropy_bits, 8);
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string.len(), 8);
+        assert_eq!(tychentropy.entropy_bit_vector.len(), 8);
+        assert_eq!(tychentropy.entropy_bytes_vector.len(), 1);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1);
+    }
+
+    #[test]
+    fn add_datum_works_in_normal_condition_07() {
+        let range = 6;
+        let target_entropy_bytes = 1;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let sequence = vec![1, 4, 6, 2, 3, 4, 1, 4, 6, 4, 2];
+
+        generate_pre_determined_series_of_data_till_entropy_is_full(
+            range,
+            sequence,
+            &mut tychentropy,
+        );
+
+        assert_eq!(tychentropy.range, 6);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
+        assert_eq!(tychentropy.target_entropy_bytes, 1);
+        assert_eq!(tychentropy.target_entropy_bits, 8);
+        assert_eq!(tychentropy.sequence, vec![1, 4, 6, 2, 3]);
+        assert_eq!(tychentropy.entropy_generating_sequence, vec![0, 3, 1, 2]);
+        assert_eq!(tychentropy.generated_entropy_bits, 8);
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "00110110");
+        assert_eq!(tychentropy.entropy_bit_vector, vec![0, 0, 1, 1, 0, 1, 1, 0]);
+        assert_eq!(tychentropy.entropy_bytes_vector.len(), 1);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1);
+    }
+
+    #[test]
+    fn add_datum_works_in_normal_condition_08() {
+        let range = 2048;
+        let target_entropy_bytes = 1024;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+
+        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+
+        assert_eq!(tychentropy.range, 2048);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 11);
+        assert_eq!(tychentropy.target_entropy_bytes, 1024);
+        assert_eq!(tychentropy.target_entropy_bits, 8192);
+        assert_eq!(tychentropy.generated_entropy_bits, 8195); // every datum has 11 bits and that's not a multiple of 8. Hence a bit of over doing here.
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string.len(), 8192);
+        assert_eq!(tychentropy.entropy_bit_vector.len(), 8192);
+        assert_eq!(tychentropy.entropy_bytes_vector.len(), 1024);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1024);
+    }
+
+    #[test]
+    fn add_datum_works_in_normal_condition_09() {
+        let range = 632;
+        let target_entropy_bytes = 893;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+
+        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+
+        assert_eq!(tychentropy.range, 632);
+        assert_eq!(tychentropy.full_bits_in_each_datum, 9);
+        assert_eq!(tychentropy.target_entropy_bytes, 893);
+        assert_eq!(tychentropy.target_entropy_bits, 7144);
+        assert_eq!(tychentropy.generated_entropy_bits, 7146); // every datum has 11 bits and that's not a multiple of 8. Hence a bit of over doing here.
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string.len(), 7144);
+        assert_eq!(tychentropy.entropy_bit_vector.len(), 7144);
+        assert_eq!(tychentropy.entropy_bytes_vector.len(), 893);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 893);
+    }
+
+    #[test]
+    fn add_datum_works_with_range_coding_01() {
+        let range = 6;
+        let target_entropy_bytes = 1;
+        let mut input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        input.set_extraction_method(ExtractionMethod::RangeCoding);
+        let mut tychentropy = Tychentropy::new(input);
+        let sequence = vec![6, 3, 4, 2, 5, 1];
+
+        generate_pre_determined_series_of_data_till_entropy_is_full(
+            range,
+            sequence,
+            &mut tychentropy,
+        );
+
+        assert_eq!(tychentropy.sequence, vec![6, 3, 4, 2]);
+        assert_eq!(tychentropy.entropy_generating_sequence, vec![5, 2, 3, 1]);
+        assert_eq!(tychentropy.generated_entropy_bits, 8);
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "10010011");
+        assert_eq!(tychentropy.entropy_bytes_vector.len(), 1);
+    }
+
+    #[test]
+    fn add_datum_works_with_range_coding_02() {
+        let range = 6;
+        let target_entropy_bytes = 32;
+        let mut input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        input.set_extraction_method(ExtractionMethod::RangeCoding);
+        let mut tychentropy = Tychentropy::new(input);
+
+        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+
+        assert_eq!(tychentropy.generated_entropy_bits, 256);
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(
+            tychentropy.sequence.len(),
+            tychentropy.entropy_generating_sequence.len()
+        );
+        // log2(6) is about 2.585 bits, so 256 bits need 100 rolls plus a little slack.
+        assert!(tychentropy.sequence.len() < 110);
+        assert_eq!(tychentropy.entropy_bytes_vector.len(), 32);
+        assert!(tychentropy.mnemonic.is_some());
+    }
+
+    #[test]
+    fn add_datum_works_with_von_neumann_01() {
+        let range = 2;
+        let target_entropy_bytes = 1;
+        let mut input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        input.set_extraction_method(ExtractionMethod::VonNeumann);
+        let mut tychentropy = Tychentropy::new(input);
+        let sequence = vec![
+            2, 1, 1, 1, 1, 2, 2, 2, 2, 1, 2, 1, 1, 2, 2, 2, 1, 2, 2, 1, 1, 1, 2, 1,
+        ];
+
+        generate_pre_determined_series_of_data_till_entropy_is_full(
+            range,
+            sequence,
+            &mut tychentropy,
+        );
+
+        assert_eq!(tychentropy.sequence.len(), 24);
+        assert_eq!(
+            tychentropy.entropy_generating_sequence,
+            vec![1, 0, 0, 1, 1, 0, 1, 0, 0, 1, 0, 1, 1, 0, 1, 0]
+        );
+        assert_eq!(tychentropy.generated_entropy_bits, 8);
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "10110011");
+        assert_eq!(tychentropy.extraction_costs.len(), 8);
+        assert_eq!(
+            *tychentropy.extraction_costs[1].get_consumed_data(),
+            4,
+            "the equal pair before the second productive pair is part of its cost"
+        );
+        assert_eq!(
+            tychentropy.get_average_data_cost_per_entropy_bit(),
+            Some(3.0)
+        );
+    }
+
+    #[test]
+    fn add_datum_works_with_peres_01() {
+        let range = 2;
+        let target_entropy_bytes = 4;
+        let mut input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        input.set_extraction_method(ExtractionMethod::Peres);
+        let mut tychentropy = Tychentropy::new(input);
+
+        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+
+        assert_eq!(tychentropy.entropy_bit_vector.len(), 32);
+        assert!(tychentropy.generated_entropy_bits >= 32);
+        assert_eq!(tychentropy.sequence.len() % 16, 0);
+        let consumed_data = tychentropy
+            .extraction_costs
+            .iter()
+            .map(|cost| cost.get_consumed_data())
+            .sum::<u64>();
+        assert_eq!(consumed_data, tychentropy.sequence.len() as u64);
+    }
+
+    #[test]
+    fn add_datum_group_works_01() {
+        let range = 6;
+        let target_entropy_bytes = 3;
+        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+
+        let datum = NaturalDatumGroup::new(range, vec![1, 1, 1, 1, 2]).unwrap();
         tychentropy.add_natural_datum(datum).unwrap();
+        // Rejection takes the faces one at a time, so only the 5s and 6s are discarded.
+        let datum = NaturalDatumGroup::new(range, vec![6, 5, 6, 6, 6]).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+        let datum = NaturalDatumGroup::new(range, vec![3, 2, 1, 5, 4]).unwrap();
+        tychentropy.add_natural_datum(datum).unwrap();
+
+        assert_eq!(tychentropy.sequence.len(), 15);
+        assert_eq!(
+            tychentropy.entropy_generating_sequence,
+            vec![0, 0, 0, 0, 1, 2, 1, 0, 3]
+        );
+        assert_eq!(tychentropy.generated_entropy_bits, 18);
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bit_string, "000000000110010011");
+    }
+
+    #[test]
+    fn add_datum_group_uses_no_more_rolls_per_bit_01() {
+        let rolls = (0u32..64)
+            .flat_map(|counter| {
+                ring::digest::digest(&ring::digest::SHA256, &counter.to_be_bytes())
+                    .as_ref()
+                    .to_vec()
+            })
+            .filter(|byte| *byte < 252)
+            .map(|byte| byte as u64 % 6 + 1)
+            .collect::<Vec<u64>>();
+        let rolls_used = |extraction_method: ExtractionMethod, group_len: usize| {
+            let mut input = TychentropyNewInput::new(6, 32).unwrap();
+            input.set_extraction_method(extraction_method);
+            let mut tychentropy = Tychentropy::new(input);
+            for group in rolls.chunks_exact(group_len) {
+                if tychentropy.is_entropy_ready {
+                    break;
+                }
+                let datum = NaturalDatumGroup::new(6, group.to_vec()).unwrap();
+                tychentropy.add_natural_datum(datum).unwrap();
+            }
+            assert!(tychentropy.is_entropy_ready);
+            tychentropy.sequence.len()
+        };
+        let single_rolls = rolls_used(ExtractionMethod::Rejection, 1);
+        let grouped_rolls = rolls_used(ExtractionMethod::Rejection, 5);
+        // Groups cost at most the rest of the group that finishes the entropy.
+        assert!(grouped_rolls >= single_rolls && grouped_rolls < single_rolls + 5);
+        // About 1.33 bits a roll with rejection, and 2.58 with range coding.
+        assert!(single_rolls >= 180);
+        assert!(rolls_used(ExtractionMethod::RangeCoding, 5) <= 105);
+    }
+
+    #[test]
+    fn add_datum_group_works_with_range_coding_01() {
+        let range = 6;
+        let target_entropy_bytes = 32;
+        let mut input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        input.set_extraction_method(ExtractionMethod::RangeCoding);
+        let mut tychentropy = Tychentropy::new(input);
+
+        while !tychentropy.is_entropy_ready {
+            let values = (0..5)
+                .map(|_| *generate_random_datum(range).get_value())
+                .collect();
+            let datum = NaturalDatumGroup::new(range, values).unwrap();
+            tychentropy.add_natural_datum(datum).unwrap();
+        }
+
+        assert_eq!(tychentropy.generated_entropy_bits, 256);
+        assert_eq!(tychentropy.sequence.len() % 5, 0);
+        assert_eq!(
+            tychentropy.entropy_generating_sequence.len() * 5,
+            tychentropy.sequence.len()
+        );
+        // 256 bits need at least 100 d6 rolls, that is 20 groups of five.
+        assert!(tychentropy.entropy_generating_sequence.len() >= 20);
+    }
+
+    #[test]
+    fn add_datum_group_emits_error_for_out_of_range_face_01() {
+        let input = TychentropyNewInput::new(6, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let datum = NaturalDatumGroup::new(8, vec![1, 8, 2]).unwrap();
+        let result = tychentropy.add_natural_datum(datum);
+        assert_eq!(
+            result.err().unwrap(),
+            ProcessError::DatumRangeIsNotAcceptedInSession { range: 8 }
+        );
+        assert_eq!(tychentropy.sequence, vec![]);
+        // A d8 datum is not taken for a d6 roll, even when its face is on a d6.
+        let result = tychentropy.add_natural_datum(NaturalDatum::new(8, 3).unwrap());
+        assert_eq!(
+            result.err().unwrap(),
+            ProcessError::DatumRangeIsNotAcceptedInSession { range: 8 }
+        );
+        assert_eq!(tychentropy.sequence, vec![]);
+    }
+
+    #[test]
+    fn add_datum_works_with_mixed_ranges_01() {
+        let input = TychentropyNewInput::new_with_mixed_ranges(vec![6, 8, 20], 2).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(6, 5).unwrap())
+            .unwrap();
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(8, 8).unwrap())
+            .unwrap();
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(20, 13).unwrap())
+            .unwrap();
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(6, 2).unwrap())
+            .unwrap();
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(20, 1).unwrap())
+            .unwrap();
+
+        assert_eq!(tychentropy.sequence, vec![5, 8, 13, 2, 1]);
+        assert_eq!(tychentropy.sequence_ranges, vec![6, 8, 20, 6, 20]);
+        assert_eq!(tychentropy.entropy_generating_sequence, vec![7, 12, 1, 0]);
+        assert_eq!(tychentropy.generated_entropy_bits, 13);
+        assert_eq!(tychentropy.entropy_bit_string, "1111100010000");
+
+        let d6 = tychentropy.get_range_account(6).unwrap();
+        assert_eq!(*d6.get_sequence(), vec![5, 2]);
+        assert_eq!(*d6.get_entropy_generating_data(), 1);
+        assert_eq!(*d6.get_generated_entropy_bits(), 2);
+        let d20 = tychentropy.get_range_account(20).unwrap();
+        assert_eq!(*d20.get_sequence(), vec![13, 1]);
+        assert_eq!(*d20.get_generated_entropy_bits(), 8);
+        assert_eq!(d20.get_face_counts()[12], 1);
+        assert!(tychentropy.get_range_account(12).is_none());
+    }
+
+    #[test]
+    fn add_datum_emits_error_for_range_not_accepted_01() {
+        let input = TychentropyNewInput::new_with_mixed_ranges(vec![6, 8], 2).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let result = tychentropy.add_natural_datum(NaturalDatum::new(12, 3).unwrap());
+        assert_eq!(
+            result.err().unwrap(),
+            ProcessError::DatumRangeIsNotAcceptedInSession { range: 12 }
+        );
+        assert_eq!(tychentropy.sequence, vec![]);
+    }
+
+    #[test]
+    fn reset_keeps_mixed_ranges_01() {
+        let input = TychentropyNewInput::new_with_mixed_ranges(vec![6, 8], 2).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(8, 3).unwrap())
+            .unwrap();
+
+        tychentropy.reset_data();
+
+        assert_eq!(tychentropy.accepted_ranges, Some(vec![6, 8]));
+        assert_eq!(tychentropy.range_accounts, vec![]);
+        assert_eq!(tychentropy.sequence_ranges, vec![]);
+    }
+
+    #[test]
+    fn add_deck_permutation_works_01() {
+        let input = TychentropyNewInput::new(6, 28).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let deck_permutation = DeckPermutation::new(52, (1..=52).collect()).unwrap();
+
+        tychentropy.add_deck_permutation(deck_permutation).unwrap();
+
+        assert_eq!(tychentropy.generated_entropy_bits, 224);
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![0; 28]);
+        assert_eq!(tychentropy.deck_permutations.len(), 1);
+        assert_eq!(tychentropy.sequence, vec![]);
+        assert!(tychentropy.mnemonic.is_some());
+        assert_eq!(tychentropy.extraction_costs.len(), 1);
+        assert_eq!(*tychentropy.extraction_costs[0].get_emitted_bits(), 224);
+        assert_eq!(*tychentropy.extraction_costs[0].get_consumed_data(), 50);
+    }
+
+    #[test]
+    fn add_deck_permutation_works_02() {
+        let input = TychentropyNewInput::new(6, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let mut cards = (1..=52).collect::<Vec<u64>>();
+        cards.shuffle(&mut rand::thread_rng());
+        let deck_permutation = DeckPermutation::new(52, cards).unwrap();
+
+        tychentropy.add_deck_permutation(deck_permutation).unwrap();
+
+        // A single deck holds about 225.58 bits, so 256 bits need more data.
+        assert!(!tychentropy.is_entropy_ready);
+        assert!(tychentropy.generated_entropy_bits <= 225);
+        assert!(tychentropy.generated_entropy_bits > 200);
+        assert_eq!(*tychentropy.extraction_costs[0].get_consumed_data(), 51);
+        tychentropy
+            .add_deck_permutation(DeckPermutation::new(52, (1..=52).collect()).unwrap())
+            .unwrap();
+        assert!(tychentropy.is_entropy_ready);
+    }
+
+    #[test]
+    fn undo_last_datum_works_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input.clone());
+        let mut expected = Tychentropy::new(input);
+        generate_pre_determined_series_of_data_till_entropy_is_full(
+            range,
+            vec![1, 4, 6, 2, 3],
+            &mut tychentropy,
+        );
+        generate_pre_determined_series_of_data_till_entropy_is_full(
+            range,
+            vec![1, 4, 6, 2],
+            &mut expected,
+        );
+        assert!(tychentropy.is_entropy_ready);
+
+        tychentropy.undo_last_datum().unwrap();
+
+        assert_eq!(tychentropy.sequence, expected.sequence);
+        assert_eq!(
+            tychentropy.entropy_generating_sequence,
+            expected.entropy_generating_sequence
+        );
+        assert_eq!(tychentropy.generated_entropy_bits, 6);
+        assert_eq!(tychentropy.entropy_bit_string, expected.entropy_bit_string);
+        assert_eq!(tychentropy.entropy_bit_vector, expected.entropy_bit_vector);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![]);
+        assert!(!tychentropy.is_entropy_ready);
+        assert!(tychentropy.mnemonic.is_none());
+    }
+
+    #[test]
+    fn undo_last_datum_works_with_range_coding_01() {
+        let range = 6;
+        let mut input = TychentropyNewInput::new(range, 1).unwrap();
+        input.set_extraction_method(ExtractionMethod::RangeCoding);
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(range, 6).unwrap())
+            .unwrap();
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(range, 3).unwrap())
+            .unwrap();
+        let state_before = format!("{:?}", tychentropy.extractor);
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(range, 4).unwrap())
+            .unwrap();
+
+        tychentropy.undo_last_datum().unwrap();
+
+        assert_eq!(tychentropy.sequence, vec![6, 3]);
+        assert_eq!(format!("{:?}", tychentropy.extractor), state_before);
+    }
+
+    #[test]
+    fn undo_last_datum_takes_back_later_mix_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        generate_pre_determined_series_of_data_till_entropy_is_full(
+            range,
+            vec![1, 4, 6, 2, 3],
+            &mut tychentropy,
+        );
+        tychentropy.mix_with_rng().unwrap();
+        let mixed_final_entropy = tychentropy.final_entropy_bytes_vector.clone();
+
+        tychentropy.undo_last_datum().unwrap();
+
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
+        assert_eq!(tychentropy.history.len(), 4);
+
+        tychentropy.redo().unwrap();
+
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.sequence, vec![1, 4, 6, 2, 3]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![54]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector, mixed_final_entropy);
+        assert_eq!(tychentropy.history.len(), 6);
+    }
+
+    #[test]
+    fn undo_last_datum_emits_error_when_no_datum_since_reset_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        assert_eq!(
+            tychentropy.undo_last_datum().err().unwrap(),
+            ProcessError::NoSequenceAppendixFound
+        );
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(range, 2).unwrap())
+            .unwrap();
+        tychentropy.reset_data();
+        assert_eq!(
+            tychentropy.undo_last_datum().err().unwrap(),
+            ProcessError::NoSequenceAppendixFound
+        );
+    }
+
+    #[test]
+    fn redo_emits_error_when_nothing_was_undone_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(range, 2).unwrap())
+            .unwrap();
+        tychentropy.undo_last_datum().unwrap();
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(range, 3).unwrap())
+            .unwrap();
+        assert_eq!(
+            tychentropy.redo().err().unwrap(),
+            ProcessError::NothingToRedo
+        );
+        assert_eq!(tychentropy.sequence, vec![3]);
+    }
+
+    #[test]
+    fn redo_keeps_events_when_it_fails_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(range, 2).unwrap())
+            .unwrap();
+        let events = vec![
+            SessionEvent::DatumAdded(NaturalDatumGroup::new(range, vec![4]).unwrap()),
+            SessionEvent::DatumAdded(NaturalDatumGroup::new(8, vec![7]).unwrap()),
+        ];
+        tychentropy.redo_stack.push(events);
+        assert_eq!(
+            tychentropy.redo().err().unwrap(),
+            ProcessError::DatumRangeIsNotAcceptedInSession { range: 8 }
+        );
+        assert_eq!(tychentropy.redo_stack.len(), 1);
+        assert_eq!(tychentropy.redo_stack[0].len(), 2);
+        assert_eq!(tychentropy.history.len(), 1);
+        assert_eq!(tychentropy.sequence, vec![2]);
+    }
+
+    #[test]
+    fn add_rolls_works_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let report = tychentropy.add_rolls(vec![1, 4, 6, 2, 3, 4, 1]).unwrap();
+        assert_eq!(report, IngestionReport::new(5, 2));
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.sequence, vec![1, 4, 6, 2, 3]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![54]);
+        assert_eq!(tychentropy.history.len(), 5);
+    }
+
+    #[test]
+    fn add_rolls_works_02() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let report = tychentropy.add_rolls(vec![1, 4]).unwrap();
+        assert_eq!(report, IngestionReport::new(2, 0));
+        assert!(!tychentropy.is_entropy_ready);
+        let report = tychentropy.add_rolls(vec![6, 2, 3]).unwrap();
+        assert_eq!(report, IngestionReport::new(3, 0));
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![54]);
+        assert_eq!(
+            tychentropy.add_rolls(vec![1]).err().unwrap(),
+            ProcessError::EntropyAlreadyCreated
+        );
+    }
+
+    #[test]
+    fn add_rolls_emits_error_with_position_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let test = tychentropy.add_rolls(vec![1, 4, 6, 7, 3]);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::InvalidDatumAtPosition {
+                index: 3,
+                error: Box::new(ProcessError::NaturalDatumOutOfRange {
+                    value: 7,
+                    range: 6,
+                    faces: "1 to 6".to_string()
+                })
+            }
+        );
+        assert_eq!(tychentropy.sequence, vec![]);
+        assert_eq!(tychentropy.history.len(), 0);
+    }
+
+    #[test]
+    fn add_rolls_emits_error_with_faces_of_face_alphabet_01() {
+        let mut input = TychentropyNewInput::new(6, 1).unwrap();
+        input.set_face_alphabet(FaceAlphabet::Lettered).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let test = tychentropy.add_rolls(vec![1, 7]).err().unwrap();
+        assert_eq!(
+            test,
+            ProcessError::InvalidDatumAtPosition {
+                index: 1,
+                error: Box::new(ProcessError::NaturalDatumOutOfRange {
+                    value: 7,
+                    range: 6,
+                    faces: "A to F".to_string()
+                })
+            }
+        );
+        assert!(test.to_string().contains("for the faces A to F"));
+    }
+
+    #[test]
+    fn add_rolls_from_text_works_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let (tychentropy, report) = Tychentropy::from_rolls_text(input, "1 4 6 2, 3 4 1").unwrap();
+        assert_eq!(report, IngestionReport::new(5, 2));
+        assert_eq!(tychentropy.sequence, vec![1, 4, 6, 2, 3]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![54]);
+    }
+
+    #[test]
+    fn add_rolls_from_text_emits_error_with_position_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let test = tychentropy.add_rolls_from_text("1 4 6 2, three 4 1");
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::InvalidRollToken {
+                index: 4,
+                offset: 9,
+                token: "three".to_string()
+            }
+        );
+        assert_eq!(tychentropy.sequence, vec![]);
+    }
+
+    #[test]
+    fn add_natural_data_works_with_mixed_ranges_01() {
+        let input = TychentropyNewInput::new_with_mixed_ranges(vec![6, 8, 20], 2).unwrap();
+        let data = vec![
+            NaturalDatum::new(6, 5).unwrap(),
+            NaturalDatum::new(8, 8).unwrap(),
+            NaturalDatum::new(20, 13).unwrap(),
+            NaturalDatum::new(6, 2).unwrap(),
+            NaturalDatum::new(20, 1).unwrap(),
+        ];
+        let (tychentropy, report) = Tychentropy::from_natural_data(input, data).unwrap();
+        assert_eq!(report, IngestionReport::new(5, 0));
+        assert_eq!(tychentropy.sequence_ranges, vec![6, 8, 20, 6, 20]);
+        assert_eq!(tychentropy.entropy_bit_string, "1111100010000");
+    }
+
+    #[test]
+    fn add_natural_data_emits_error_with_position_01() {
+        let input = TychentropyNewInput::new_with_mixed_ranges(vec![6, 8, 20], 2).unwrap();
+        let data = vec![
+            NaturalDatum::new(6, 5).unwrap(),
+            NaturalDatum::new(12, 8).unwrap(),
+        ];
+        let test = Tychentropy::from_natural_data(input, data);
+        assert_eq!(
+            test.err().unwrap(),
+            ProcessError::InvalidDatumAtPosition {
+                index: 1,
+                error: Box::new(ProcessError::DatumRangeIsNotAcceptedInSession { range: 12 })
+            }
+        );
+    }
+
+    #[test]
+    fn add_rolls_emits_error_for_mixed_ranges_01() {
+        let input = TychentropyNewInput::new_with_mixed_ranges(vec![6, 20], 4).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        assert_eq!(
+            tychentropy.add_rolls(vec![5, 13]).err().unwrap(),
+            ProcessError::RollsNeedRangeInMixedRangeSession
+        );
+        assert_eq!(
+            tychentropy.add_rolls_from_text("d6:5 13").err().unwrap(),
+            ProcessError::InvalidRollToken {
+                index: 1,
+                offset: 5,
+                token: "13".to_string()
+            }
+        );
+        assert_eq!(tychentropy.sequence, vec![]);
+    }
+
+    #[test]
+    fn add_rolls_from_text_works_with_face_alphabet_01() {
+        let range = 10;
+        let mut input = TychentropyNewInput::new(range, 4).unwrap();
+        input.set_face_alphabet(FaceAlphabet::ZeroBased).unwrap();
+        let (tychentropy, report) = Tychentropy::from_rolls_text(input, "0 3 5 1 2 3 9").unwrap();
+        assert_eq!(*report.get_accepted_data(), 7);
+        assert_eq!(tychentropy.sequence, vec![1, 4, 6, 2, 3, 4, 10]);
+        assert_eq!(tychentropy.get_roll_log(), "0 3 5 1 2 3 9");
+    }
+
+    #[test]
+    fn add_rolls_from_text_works_with_face_alphabet_02() {
+        let range = 10;
+        let mut input = TychentropyNewInput::new(range, 4).unwrap();
+        input.set_face_alphabet(FaceAlphabet::Percentile).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls_from_text("00, 30, 90").unwrap();
+        assert_eq!(tychentropy.sequence, vec![1, 4, 10]);
+        assert_eq!(tychentropy.get_roll_log(), "00 30 90");
+        assert_eq!(
+            tychentropy.add_rolls_from_text("40 7").err().unwrap(),
+            ProcessError::InvalidRollToken {
+                index: 1,
+                offset: 3,
+                token: "7".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn get_roll_log_works_with_mixed_ranges_01() {
+        let input = TychentropyNewInput::new_with_mixed_ranges(vec![6, 20], 4).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(6, 5).unwrap())
+            .unwrap();
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(20, 13).unwrap())
+            .unwrap();
+        assert_eq!(tychentropy.get_roll_log(), "d6:5 d20:13");
+    }
+
+    #[test]
+    fn get_roll_log_round_trips_through_add_rolls_from_text_01() {
+        let mut input = TychentropyNewInput::new_with_mixed_ranges(vec![6, 20], 4).unwrap();
+        input.set_face_alphabet(FaceAlphabet::ZeroBased).unwrap();
+        let mut tychentropy = Tychentropy::new(input.clone());
+        tychentropy
+            .add_rolls_from_text("d6:4 d20:12 d20:0 d6:5")
+            .unwrap();
+        let mut copy = Tychentropy::new(input);
+        copy.add_rolls_from_text(&tychentropy.get_roll_log())
+            .unwrap();
+        assert_eq!(copy.sequence, tychentropy.sequence);
+        assert_eq!(copy.sequence_ranges, tychentropy.sequence_ranges);
+        assert_eq!(copy.get_roll_log(), "d6:4 d20:12 d20:0 d6:5");
+
+        let mut input = TychentropyNewInput::new(6, 4).unwrap();
+        input.set_face_alphabet(FaceAlphabet::Lettered).unwrap();
+        let mut tychentropy = Tychentropy::new(input.clone());
+        tychentropy.add_rolls_from_text("a f c").unwrap();
+        let mut copy = Tychentropy::new(input);
+        copy.add_rolls_from_text(&tychentropy.get_roll_log())
+            .unwrap();
+        assert_eq!(copy.sequence, vec![1, 6, 3]);
+    }
+
+    #[test]
+    fn reset_keeps_face_alphabet_01() {
+        let mut input = TychentropyNewInput::new(6, 4).unwrap();
+        input.set_face_alphabet(FaceAlphabet::Lettered).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls_from_text("a b").unwrap();
+        tychentropy.reset_data();
+        assert_eq!(tychentropy.face_alphabet, FaceAlphabet::Lettered);
+        assert_eq!(tychentropy.get_roll_log(), "");
+    }
+
+    #[test]
+    fn add_datum_works_with_target_in_bits_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new_with_target_bits(range, 11).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let report = tychentropy.add_rolls(vec![1, 4, 6, 2, 3, 4, 1, 2]).unwrap();
+        assert_eq!(report, IngestionReport::new(7, 1));
+        assert_eq!(tychentropy.target_entropy_bytes, 2);
+        assert_eq!(tychentropy.entropy_bit_string, "00110110110");
+        // The last three bits fill the highest positions of the second byte.
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![54, 192]);
+        assert_eq!(
+            tychentropy.get_final_entropy_bit_vector(),
+            vec![0, 0, 1, 1, 0, 1, 1, 0, 1, 1, 0]
+        );
+        assert!(tychentropy.mnemonic_length.is_none());
+    }
+
+    #[test]
+    fn mix_with_rng_keeps_padding_with_target_in_bits_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new_with_target_bits(range, 11).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+        tychentropy.mix_with_rng().unwrap();
+        assert_eq!(tychentropy.rng_entropy_bytes_vector[1] & 0b0001_1111, 0);
+        assert_eq!(tychentropy.final_entropy_bytes_vector[1] & 0b0001_1111, 0);
+        assert_eq!(tychentropy.get_final_entropy_bit_vector().len(), 11);
+    }
+
+    #[test]
+    fn reset_keeps_target_in_bits_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new_with_target_bits(range, 130).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(vec![1, 2, 3]).unwrap();
+        tychentropy.reset_data();
+        assert_eq!(tychentropy.target_entropy_bits, 130);
+        assert_eq!(tychentropy.target_entropy_bytes, 17);
+        assert!(tychentropy.get_final_entropy_bit_vector().is_empty());
+    }
+
+    #[test]
+    fn add_datum_works_with_lsb_first_bit_order_01() {
+        let range = 6;
+        let mut input = TychentropyNewInput::new(range, 1).unwrap();
+        input.set_bit_order(BitOrder::LsbFirst);
+        let (tychentropy, _) = Tychentropy::from_rolls(input, vec![1, 4, 6, 2, 3]).unwrap();
+        assert_eq!(tychentropy.entropy_bit_string, "00110110");
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![108]);
+        assert_eq!(
+            tychentropy.get_final_entropy_bit_vector(),
+            vec![0, 0, 1, 1, 0, 1, 1, 0]
+        );
+    }
+
+    #[test]
+    fn entropy_bytes_match_bit_string_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+        assert_eq!(
+            vec_u8_to_bit_string(&tychentropy.entropy_bytes_vector),
+            tychentropy.entropy_bit_string
+        );
+    }
+
+    #[test]
+    fn get_legacy_lsb_first_mnemonic_works_01() {
+        let range = 6;
+        let input = TychentropyNewInput::new(range, 16).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+        let mut legacy_input = TychentropyNewInput::new(range, 16).unwrap();
+        legacy_input.set_bit_order(BitOrder::LsbFirst);
+        let mut legacy = Tychentropy::new(legacy_input);
+        legacy.add_rolls(tychentropy.sequence.clone()).unwrap();
+        assert_eq!(
+            tychentropy.get_legacy_lsb_first_mnemonic(),
+            legacy.mnemonic.clone()
+        );
+        assert_eq!(legacy.get_legacy_lsb_first_mnemonic(), legacy.mnemonic);
+        assert_ne!(tychentropy.mnemonic, legacy.mnemonic);
+    }
+
+    #[test]
+    fn get_legacy_lsb_first_mnemonic_works_with_hkdf_mix_01() {
+        let range = 6;
+        let mut input = TychentropyNewInput::new(range, 16).unwrap();
+        input.set_mixing_strategy(MixingStrategy::HkdfSha256);
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+        let mut legacy_input = TychentropyNewInput::new(range, 16).unwrap();
+        legacy_input.set_mixing_strategy(MixingStrategy::HkdfSha256);
+        legacy_input.set_bit_order(BitOrder::LsbFirst);
+        let mut legacy = Tychentropy::new(legacy_input);
+        legacy.add_rolls(tychentropy.sequence.clone()).unwrap();
+
+        tychentropy.mix_with_rng().unwrap();
+        legacy
+            .apply_rng_mix(&tychentropy.rng_entropy_bytes_vector.clone())
+            .unwrap();
+
+        assert_eq!(
+            tychentropy.get_legacy_lsb_first_mnemonic(),
+            legacy.mnemonic.clone()
+        );
+        let xor = MixingStrategy::Xor
+            .mix(
+                &BitOrder::LsbFirst.pack(&tychentropy.entropy_bit_vector),
+                &tychentropy.rng_entropy_bytes_vector,
+                128,
+            )
+            .unwrap();
+        assert_ne!(legacy.mnemonic, Mnemonic::from_entropy(&xor).ok());
+    }
+
+    fn coldcard_session() -> Tychentropy {
+        let mut input = TychentropyNewInput::new(6, 32).unwrap();
+        input.set_extraction_method(ExtractionMethod::Coldcard);
+        Tychentropy::new(input)
+    }
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    // The expected bytes below are SHA-256 of the roll digits as ASCII, computed separately
+    // with Python's hashlib, as in `dice_hashing`. Vectors from Coldcard's firmware tests still
+    // have to be added, see the note there.
+    #[test]
+    fn finalize_hashed_entropy_works_with_coldcard_01() {
+        let mut tychentropy = coldcard_session();
+        let rolls = (0..99).map(|index| index % 6 + 1).collect::<Vec<u64>>();
+        tychentropy.add_rolls(rolls).unwrap();
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.generated_entropy_bits, 0);
+
+        tychentropy.finalize_hashed_entropy().unwrap();
+
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(
+            to_hex(&tychentropy.final_entropy_bytes_vector),
+            "5588d3630bd19f6375b7bd922457af34ea9c74f00807566a1cf808e445dc8c20"
+        );
+        assert_eq!(tychentropy.entropy_generating_sequence.len(), 99);
+        assert_eq!(
+            tychentropy.mnemonic,
+            Some(Mnemonic::from_entropy(&tychentropy.final_entropy_bytes_vector).unwrap())
+        );
+    }
+
+    #[test]
+    fn finalize_hashed_entropy_works_with_coldcard_02() {
+        let mut tychentropy = coldcard_session();
+        tychentropy.add_rolls(vec![6; 120]).unwrap();
+        tychentropy.finalize_hashed_entropy().unwrap();
+        let mut expected = coldcard_session();
+        expected.add_rolls(vec![6; 99]).unwrap();
+        expected.finalize_hashed_entropy().unwrap();
+        assert_ne!(
+            tychentropy.final_entropy_bytes_vector,
+            expected.final_entropy_bytes_vector
+        );
+        assert_eq!(
+            to_hex(&expected.final_entropy_bytes_vector),
+            "7efb8e5d1353a90137755f711e1763fd7301a033fbb854889e127ff79c389131"
+        );
+    }
+
+    #[test]
+    fn finalize_hashed_entropy_works_with_lsb_first_bit_order_01() {
+        let mut input = TychentropyNewInput::new(6, 32).unwrap();
+        input.set_extraction_method(ExtractionMethod::Coldcard);
+        input.set_bit_order(BitOrder::LsbFirst);
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(vec![1; 99]).unwrap();
+        tychentropy.finalize_hashed_entropy().unwrap();
+        assert_eq!(
+            to_hex(&tychentropy.final_entropy_bytes_vector),
+            "fa098eb852b2660348b21bb00ad03a49cc177ea07ebe34f46b40baa85313525e"
+        );
+    }
+
+    #[test]
+    fn finalize_hashed_entropy_emits_error_for_too_few_rolls_01() {
+        let mut tychentropy = coldcard_session();
+        tychentropy.add_rolls(vec![3; 98]).unwrap();
+        assert_eq!(
+            tychentropy.finalize_hashed_entropy().err().unwrap(),
+            ProcessError::NotEnoughDataForHashedEntropy {
+                data_len: 98,
+                required_data_len: 99
+            }
+        );
+        assert!(!tychentropy.is_entropy_ready);
+    }
+
+    #[test]
+    fn finalize_hashed_entropy_emits_error_for_session_not_fitting_01() {
+        let mut input = TychentropyNewInput::new(6, 16).unwrap();
+        input.set_extraction_method(ExtractionMethod::Coldcard);
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(vec![3; 99]).unwrap();
+        assert_eq!(
+            tychentropy.finalize_hashed_entropy().err().unwrap(),
+            ProcessError::ExtractionMethodDoesNotFitSession {
+                method: "Coldcard".to_string(),
+                requirement: "a target of 256 bits".to_string()
+            }
+        );
+        let mut tychentropy = Tychentropy::default();
+        assert_eq!(
+            tychentropy.finalize_hashed_entropy().err().unwrap(),
+            ProcessError::ExtractionMethodIsNotHashed {
+                method: "rejection".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn undo_last_datum_takes_back_hashed_entropy_01() {
+        let mut tychentropy = coldcard_session();
+        tychentropy.add_rolls(vec![2; 100]).unwrap();
+        tychentropy.finalize_hashed_entropy().unwrap();
+        tychentropy.undo_last_datum().unwrap();
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.sequence.len(), 99);
+        tychentropy.finalize_hashed_entropy().unwrap();
+        let mut expected = coldcard_session();
+        expected.add_rolls(vec![2; 99]).unwrap();
+        expected.finalize_hashed_entropy().unwrap();
+        assert_eq!(
+            tychentropy.final_entropy_bytes_vector,
+            expected.final_entropy_bytes_vector
+        );
+    }
+
+    fn interop_session(
+        range: u64,
+        target_entropy_bytes: u64,
+        extraction_method: ExtractionMethod,
+    ) -> Tychentropy {
+        let mut input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+        input.set_extraction_method(extraction_method);
+        if range == 10 {
+            input.set_face_alphabet(FaceAlphabet::ZeroBased).unwrap();
+        }
+        Tychentropy::new(input)
+    }
+
+    // The expected bytes of the interoperability tests were computed separately with a short
+    // Python script following each tool's algorithm, using hashlib for SHA-256 and Python
+    // integers for the base conversion.
+    #[test]
+    fn add_datum_works_with_seedsigner_01() {
+        let mut tychentropy = interop_session(6, 16, ExtractionMethod::SeedSigner);
+        let rolls = (0..52).map(|index| index % 6 + 1).collect::<Vec<u64>>();
+        let report = tychentropy.add_rolls(rolls).unwrap();
+        assert_eq!(report, IngestionReport::new(50, 2));
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(
+            to_hex(&tychentropy.final_entropy_bytes_vector),
+            "ee72ae915a4e6ea7ccbeb8e5e5eecef2"
+        );
+        assert!(tychentropy.mnemonic.is_some());
+    }
+
+    #[test]
+    fn add_datum_works_with_seedsigner_02() {
+        let mut tychentropy = interop_session(6, 32, ExtractionMethod::SeedSigner);
+        let rolls = (0..99).map(|index| index % 6 + 1).collect::<Vec<u64>>();
+        tychentropy.add_rolls(rolls.clone()).unwrap();
+        let mut coldcard = coldcard_session();
+        coldcard.add_rolls(rolls).unwrap();
+        coldcard.finalize_hashed_entropy().unwrap();
+        assert_eq!(
+            to_hex(&tychentropy.final_entropy_bytes_vector),
+            "5588d3630bd19f6375b7bd922457af34ea9c74f00807566a1cf808e445dc8c20"
+        );
+        assert_eq!(tychentropy.mnemonic, coldcard.mnemonic);
+    }
+
+    #[test]
+    fn add_datum_emits_error_for_seedsigner_target_01() {
+        let mut tychentropy = interop_session(6, 24, ExtractionMethod::SeedSigner);
+        tychentropy.add_rolls(vec![4; 99]).unwrap();
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(
+            tychentropy.finalize_hashed_entropy().err().unwrap(),
+            ProcessError::ExtractionMethodDoesNotFitSession {
+                method: "SeedSigner".to_string(),
+                requirement: "a target of 128, 256 bits".to_string()
+            }
+        );
+    }
+
+    // The Ian Coleman vectors below were computed from the tool's conversion rules, not
+    // taken from the tool's page, and should be checked against it.
+    #[test]
+    fn finalize_hashed_entropy_works_with_ian_coleman_raw_01() {
+        let mut tychentropy = interop_session(6, 16, ExtractionMethod::IanColemanRaw);
+        let rolls = (0..50).map(|index| index % 6 + 1).collect::<Vec<u64>>();
+        tychentropy.add_rolls(rolls).unwrap();
+        tychentropy.finalize_hashed_entropy().unwrap();
+        assert_eq!(
+            to_hex(&tychentropy.final_entropy_bytes_vector),
+            "91d89c79009852800bffccdc406b1ba0"
+        );
+    }
+
+    #[test]
+    fn finalize_hashed_entropy_works_with_ian_coleman_raw_02() {
+        let mut tychentropy = interop_session(10, 16, ExtractionMethod::IanColemanRaw);
+        let rolls = (0..39)
+            .map(|index| (index * 7 + 3) % 10 + 1)
+            .collect::<Vec<u64>>();
+        tychentropy.add_rolls(rolls).unwrap();
+        assert_eq!(
+            tychentropy.get_roll_log().replace(' ', ""),
+            "307418529630741852963074185296307418529"
+        );
+        tychentropy.finalize_hashed_entropy().unwrap();
+        assert_eq!(
+            to_hex(&tychentropy.final_entropy_bytes_vector),
+            "e746a7b90f3c22754845f4c218a1f5a1"
+        );
+    }
+
+    #[test]
+    fn finalize_hashed_entropy_emits_error_for_ian_coleman_raw_01() {
+        let mut tychentropy = interop_session(6, 16, ExtractionMethod::IanColemanRaw);
+        tychentropy.add_rolls(vec![6; 49]).unwrap();
+        assert_eq!(
+            tychentropy.finalize_hashed_entropy().err().unwrap(),
+            ProcessError::NotEnoughDataForHashedEntropy {
+                data_len: 49,
+                required_data_len: 50
+            }
+        );
+        tychentropy.add_rolls(vec![6; 13]).unwrap();
+        assert_eq!(
+            tychentropy.finalize_hashed_entropy().err().unwrap(),
+            ProcessError::ExtractionMethodDoesNotFitSession {
+                method: "Ian Coleman raw".to_string(),
+                requirement: "a target of 160 bits to use all 62 data".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn finalize_hashed_entropy_works_with_ian_coleman_hashed_01() {
+        let mut tychentropy = interop_session(6, 16, ExtractionMethod::IanColemanHashed);
+        let rolls = (0..50).map(|index| index % 6 + 1).collect::<Vec<u64>>();
+        tychentropy.add_rolls(rolls).unwrap();
+        tychentropy.finalize_hashed_entropy().unwrap();
+        assert_eq!(
+            to_hex(&tychentropy.final_entropy_bytes_vector),
+            "ee72ae915a4e6ea7ccbeb8e5e5eecef2"
+        );
+    }
+
+    #[test]
+    fn add_deck_permutation_emits_error_in_ian_coleman_session_01() {
+        for extraction_method in [
+            ExtractionMethod::IanColemanRaw,
+            ExtractionMethod::IanColemanHashed,
+        ] {
+            let mut tychentropy = interop_session(6, 16, extraction_method.clone());
+            let deck = DeckPermutation::new(52, (1..=52).rev().collect()).unwrap();
+            assert_eq!(
+                tychentropy.add_deck_permutation(deck).err().unwrap(),
+                ProcessError::ExtractionMethodDoesNotFitSession {
+                    method: extraction_method.get_name(),
+                    requirement: "rolls, not deck permutations".to_string()
+                }
+            );
+            assert!(tychentropy.get_deck_permutations().is_empty());
+        }
+    }
+
+    #[test]
+    fn add_deck_permutation_emits_error_in_hashed_session_01() {
+        let mut tychentropy = coldcard_session();
+        let deck = DeckPermutation::new(52, (1..=52).collect()).unwrap();
+        assert_eq!(
+            tychentropy.add_deck_permutation(deck).err().unwrap(),
+            ProcessError::ExtractionMethodDoesNotFitSession {
+                method: "Coldcard".to_string(),
+                requirement: "rolls, not deck permutations".to_string()
+            }
+        );
+    }
+
+    fn conditioned_session(
+        target_entropy_bytes: u64,
+        function: ConditioningFunction,
+        safety_factor: f64,
+    ) -> Tychentropy {
+        let mut input = TychentropyNewInput::new(6, target_entropy_bytes).unwrap();
+        let conditioning = Conditioning::new(function, safety_factor).unwrap();
+        input.set_conditioning(Some(conditioning)).unwrap();
+        Tychentropy::new(input)
+    }
+
+    #[test]
+    fn add_datum_works_with_conditioning_01() {
+        let mut tychentropy = conditioned_session(1, ConditioningFunction::Sha256, 2.0);
+        // Each d6 roll is credited log2(6) bits, so 16 bits need 7 rolls.
+        let report = tychentropy.add_rolls(vec![6, 5, 6, 1, 2, 6, 5, 3]).unwrap();
+        assert_eq!(report, IngestionReport::new(7, 1));
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(
+            tychentropy.entropy_generating_sequence,
+            vec![5, 4, 5, 0, 1, 5, 4]
+        );
+        let expected = Conditioning::new(ConditioningFunction::Sha256, 2.0)
+            .unwrap()
+            .condition(&[6, 5, 6, 1, 2, 6, 5], &[6; 7], 8);
+        assert_eq!(tychentropy.entropy_bytes_vector, expected);
+        let record = tychentropy.conditioning_record.clone().unwrap();
+        assert_eq!(*record.get_function(), ConditioningFunction::Sha256);
+        assert_eq!(*record.get_conditioned_data(), 7);
+        assert_eq!(record.get_domain(), "tychentropy/conditioning/v1");
+        assert!((*record.get_credited_entropy_bits() - 7.0 * 6f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn add_datum_works_with_conditioning_02() {
+        let mut tychentropy = conditioned_session(32, ConditioningFunction::HmacSha512, 1.5);
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
+        assert_eq!(tychentropy.sequence.len(), 149);
+        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 32);
+        assert!(tychentropy.mnemonic.is_some());
+        assert_eq!(
+            vec_u8_to_bit_string(&tychentropy.entropy_bytes_vector),
+            tychentropy.entropy_bit_string
+        );
+    }
+
+    #[test]
+    fn undo_last_datum_takes_back_conditioning_01() {
+        let mut tychentropy = conditioned_session(1, ConditioningFunction::Sha512, 2.0);
+        tychentropy.add_rolls(vec![6, 5, 6, 1, 2, 6, 5]).unwrap();
+        tychentropy.undo_last_datum().unwrap();
+        assert!(!tychentropy.is_entropy_ready);
+        assert!(tychentropy.conditioning_record.is_none());
+        assert_eq!(tychentropy.generated_entropy_bits, 0);
+        tychentropy.add_rolls(vec![4]).unwrap();
+        let expected = Conditioning::new(ConditioningFunction::Sha512, 2.0)
+            .unwrap()
+            .condition(&[6, 5, 6, 1, 2, 6, 4], &[6; 7], 8);
+        assert_eq!(tychentropy.entropy_bytes_vector, expected);
+    }
+
+    #[test]
+    fn conditioning_is_ignored_by_hashed_methods_01() {
+        let mut input = TychentropyNewInput::new(6, 32).unwrap();
+        input.set_extraction_method(ExtractionMethod::Coldcard);
+        input
+            .set_conditioning(Some(Conditioning::default()))
+            .unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(vec![1; 99]).unwrap();
+        tychentropy.finalize_hashed_entropy().unwrap();
+        assert_eq!(
+            to_hex(&tychentropy.final_entropy_bytes_vector),
+            "fa098eb852b2660348b21bb00ad03a49cc177ea07ebe34f46b40baa85313525e"
+        );
+        assert!(tychentropy.conditioning_record.is_none());
+    }
+
+    #[test]
+    fn get_raw_bit_stream_works_01() {
+        let input = TychentropyNewInput::new_with_mixed_ranges(vec![2, 6, 20], 4).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(6, 6).unwrap())
+            .unwrap();
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(2, 2).unwrap())
+            .unwrap();
+        tychentropy
+            .add_natural_datum(NaturalDatum::new(20, 13).unwrap())
+            .unwrap();
+        assert_eq!(
+            tychentropy.get_raw_bit_stream(),
+            vec![1, 0, 1, 1, 0, 1, 1, 0, 0]
+        );
+    }
+
+    #[test]
+    fn extract_with_toeplitz_works_01() {
+        let input = TychentropyNewInput::new(6, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_rolls((0..100).map(|index| index % 5 + 1))
+            .unwrap();
+        // Credited with 2 bits of min-entropy a roll.
+        let extractor = ToeplitzExtractor::new(vec![0xa5; 64], 32);
+        let test = tychentropy
+            .extract_with_toeplitz(&extractor, 200.0)
+            .unwrap();
+        assert_eq!(*test.get_input_bits(), 300);
+        assert_eq!(test.get_bits().len(), 136);
+        assert_eq!(*test.get_statistical_distance_bound(), 2f64.powi(-32));
+        assert_eq!(tychentropy.sequence.len(), 100);
+    }
+
+    /// Emits the lowest bit of every datum, to test sessions with an extractor of their own.
+    #[derive(Debug, Clone, Zeroize)]
+    struct LowestBitExtractor {
+        absorbed_data: u64,
+    }
+
+    impl Extractor for LowestBitExtractor {
+        fn absorb(
+            &mut self,
+            zero_indexed_datum_value: u64,
+            _range: u64,
+            _remaining_bits: u64,
+        ) -> ExtractionStep {
+            self.absorbed_data += 1;
+            ExtractionStep {
+                entropy_generating_data: vec![zero_indexed_datum_value],
+                bits: vec![(zero_indexed_datum_value & 1) as u8],
+            }
+        }
+
+        fn get_name(&self) -> String {
+            "lowest bit".to_string()
+        }
+
+        fn new_empty(&self) -> Box<dyn Extractor> {
+            Box::new(LowestBitExtractor { absorbed_data: 0 })
+        }
+
+        fn clone_box(&self) -> Box<dyn Extractor> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn custom_extractor_works_01() {
+        let mut input = TychentropyNewInput::new(6, 1).unwrap();
+        input.set_extractor(Box::new(LowestBitExtractor { absorbed_data: 0 }));
+        let mut tychentropy = Tychentropy::new(input);
+        assert_eq!(tychentropy.get_extractor().get_name(), "lowest bit");
+        tychentropy.add_rolls(vec![6, 5, 2, 1, 4, 3, 6, 2]).unwrap();
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.generated_entropy_bits, 8);
+        assert_eq!(tychentropy.entropy_bit_string, "10101011");
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![0b1010_1011]);
+    }
+
+    #[test]
+    fn custom_extractor_survives_undo_and_reset_01() {
+        let mut input = TychentropyNewInput::new(6, 1).unwrap();
+        input.set_extractor(Box::new(LowestBitExtractor { absorbed_data: 0 }));
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(vec![6, 5, 2]).unwrap();
+        tychentropy.undo_last_datum().unwrap();
+        assert_eq!(tychentropy.entropy_bit_string, "10");
+        assert_eq!(
+            format!("{:?}", tychentropy.extractor),
+            "LowestBitExtractor { absorbed_data: 2 }"
+        );
+        tychentropy.reset_data();
+        tychentropy.add_rolls(vec![2]).unwrap();
+        assert_eq!(tychentropy.get_extractor().get_name(), "lowest bit");
+        assert_eq!(tychentropy.entropy_bit_string, "1");
+    }
+
+    #[test]
+    fn perform_goodness_of_fit_test_works_01() {
+        let input = TychentropyNewInput::new(4, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_rolls((0..40).map(|index| index % 4 + 1))
+            .unwrap();
+        let test = tychentropy
+            .perform_goodness_of_fit_test(GoodnessOfFitStatistic::ChiSquared)
+            .unwrap();
+        assert_eq!(*test.get_num_samples(), 40);
+        assert_eq!(*test.get_statistic(), 0.0);
+        assert!(test
+            .get_face_counts()
+            .iter()
+            .all(|face_count| *face_count.get_observed() == 10));
+    }
+
+    #[test]
+    fn perform_goodness_of_fit_test_for_range_works_01() {
+        let input = TychentropyNewInput::new_with_mixed_ranges(vec![2, 6], 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        for value in 0..30 {
+            tychentropy
+                .add_natural_datum(NaturalDatum::new(6, value % 6 + 1).unwrap())
+                .unwrap();
+        }
+        for _ in 0..9 {
+            tychentropy
+                .add_natural_datum(NaturalDatum::new(2, 2).unwrap())
+                .unwrap();
+        }
+        let test = tychentropy
+            .perform_goodness_of_fit_test(GoodnessOfFitStatistic::G)
+            .unwrap();
+        assert_eq!(*test.get_num_samples(), 30);
+        assert_eq!(
+            tychentropy
+                .perform_goodness_of_fit_test_for_range(2, GoodnessOfFitStatistic::G)
+                .err()
+                .unwrap(),
+            StatisticsError::NotEnoughSamplesForChiSquaredTest {
+                num_given_samples: 9,
+                num_required_samples: 10
+            }
+        );
+    }
+
+    #[test]
+    fn perform_serial_dependence_test_works_01() {
+        let input = TychentropyNewInput::new(4, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_rolls((0..81).map(|index| index / 2 % 4 + 1))
+            .unwrap();
+        let test = tychentropy.perform_serial_dependence_test(2, 0.01).unwrap();
+        assert!(*test.get_is_dependence_detected());
+        assert_eq!(*test.get_transition_test().get_num_transitions(), 80);
+        assert_eq!(
+            tychentropy
+                .perform_serial_dependence_test_for_range(6, 2, 0.01)
+                .err()
+                .unwrap(),
+            StatisticsError::NotEnoughSamplesForChiSquaredTest {
+                num_given_samples: 0,
+                num_required_samples: 180
+            }
+        );
+    }
+
+    #[test]
+    fn assess_min_entropy_works_01() {
+        let input = TychentropyNewInput::new(6, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy
+            .add_rolls((0..200).map(|index| index % 6 + 1))
+            .unwrap();
+        let test = tychentropy.assess_min_entropy().unwrap();
+        assert_eq!(
+            *test.get_num_samples(),
+            tychentropy.get_sequence().len() as u64
+        );
+        // The faces are even, but each one gives the next away.
+        assert!(*test.get_min_entropy_bits_per_datum() < 0.5);
+        assert!(tychentropy.assess_min_entropy_for_range(20).is_err());
+    }
+
+    #[test]
+    fn die_bias_monitor_works_01() {
+        let mut input = TychentropyNewInput::new(6, 32).unwrap();
+        input.set_sprt_config(Some(SprtConfig::default())).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(vec![6; 17]).unwrap();
+        assert!(tychentropy.get_die_bias_warning().is_none());
+        tychentropy.add_rolls(vec![6]).unwrap();
+        let warning = tychentropy.get_die_bias_warning().clone().unwrap();
+        assert_eq!(*warning.get_face(), 6);
+        assert_eq!(*warning.get_direction(), BiasDirection::Overrepresented);
+
+        tychentropy.undo_last_datum().unwrap();
+        assert!(tychentropy.get_die_bias_warning().is_none());
+        tychentropy.redo().unwrap();
+        assert!(tychentropy.get_die_bias_warning().is_some());
+
+        tychentropy.acknowledge_die_bias_warning().unwrap();
+        assert!(tychentropy.get_die_bias_warning().is_none());
+        tychentropy.add_rolls(vec![6; 17]).unwrap();
+        assert!(tychentropy.get_die_bias_warning().is_none());
+        assert_eq!(
+            tychentropy.acknowledge_die_bias_warning().err().unwrap(),
+            ProcessError::NoDieBiasWarningFound
+        );
+    }
+
+    #[test]
+    fn health_tests_work_01() {
+        let mut input = TychentropyNewInput::new(6, 32).unwrap();
+        input.set_health_test_config(Some(HealthTestConfig::default()));
+        let mut tychentropy = Tychentropy::new(input);
+        // The ninth 6 in a row fails and is not added, and the rest of the rolls are dropped.
+        assert_eq!(
+            tychentropy.add_rolls(vec![6; 20]).err().unwrap(),
+            ProcessError::IngestionStoppedAtPosition {
+                index: 8,
+                report: IngestionReport::new(8, 0),
+                error: Box::new(ProcessError::NaturalDatumFailedHealthTest {
+                    test: "repetition count".to_string(),
+                    range: 6,
+                    face: "6".to_string()
+                })
+            }
+        );
+        assert_eq!(tychentropy.get_sequence().len(), 8);
+        let failure = tychentropy.get_health_test_failure().clone().unwrap();
+        assert_eq!(*failure.get_test(), HealthTest::RepetitionCount);
+        assert_eq!(*failure.get_count(), 9);
+        assert_eq!(
+            tychentropy.add_natural_datum(NaturalDatum::new(6, 1).unwrap()),
+            Err(ProcessError::HealthTestFailureIsNotAcknowledged {
+                test: "repetition count".to_string(),
+                range: 6
+            })
+        );
 
-        let datum = NaturalDatum::new(range, 5).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+        tychentropy.acknowledge_health_test_failure().unwrap();
+        tychentropy.add_rolls(vec![6; 8]).unwrap();
+        assert_eq!(tychentropy.get_sequence().len(), 16);
+        assert_eq!(
+            tychentropy.acknowledge_health_test_failure().err().unwrap(),
+            ProcessError::NoHealthTestFailureFound
+        );
+        tychentropy.reset_data();
+        assert!(tychentropy.get_health_test_monitor().is_some());
+    }
 
-        let datum = NaturalDatum::new(range, 3).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    #[test]
+    fn health_tests_block_undo_until_acknowledged_01() {
+        let mut input = TychentropyNewInput::new(6, 32).unwrap();
+        input.set_health_test_config(Some(HealthTestConfig::default()));
+        let mut tychentropy = Tychentropy::new(input);
+        assert!(tychentropy.add_rolls(vec![6; 9]).is_err());
 
-        let datum = NaturalDatum::new(range, 6).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+        assert_eq!(
+            tychentropy.undo_last_datum().err().unwrap(),
+            ProcessError::HealthTestFailureIsNotAcknowledged {
+                test: "repetition count".to_string(),
+                range: 6
+            }
+        );
+        assert!(tychentropy.get_health_test_failure().is_some());
+        assert_eq!(tychentropy.get_sequence().len(), 8);
+
+        tychentropy.acknowledge_health_test_failure().unwrap();
+        tychentropy.undo_last_datum().unwrap();
+        assert!(tychentropy.get_health_test_failure().is_none());
+        assert_eq!(tychentropy.get_sequence().len(), 7);
+        tychentropy.redo().unwrap();
+        assert!(tychentropy.get_health_test_failure().is_none());
+        assert_eq!(tychentropy.get_sequence().len(), 8);
+    }
 
-        let datum = NaturalDatum::new(range, 2).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+    #[test]
+    fn health_tests_block_deck_permutations_01() {
+        let mut input = TychentropyNewInput::new(6, 32).unwrap();
+        input.set_health_test_config(Some(HealthTestConfig::default()));
+        let mut tychentropy = Tychentropy::new(input);
+        assert!(tychentropy.add_rolls(vec![6; 9]).is_err());
+        let deck = DeckPermutation::new(52, (1..=52).collect()).unwrap();
 
-        let datum = NaturalDatum::new(range, 1).unwrap();
-        tychentropy.add_natural_datum(datum).unwrap();
+        assert_eq!(
+            tychentropy
+                .add_deck_permutation(deck.clone())
+                .err()
+                .unwrap(),
+            ProcessError::HealthTestFailureIsNotAcknowledged {
+                test: "repetition count".to_string(),
+                range: 6
+            }
+        );
+        assert!(tychentropy.deck_permutations.is_empty());
+        tychentropy.acknowledge_health_test_failure().unwrap();
+        tychentropy.add_deck_permutation(deck).unwrap();
+        assert_eq!(tychentropy.deck_permutations.len(), 1);
+    }
 
-        assert_eq!(tychentropy.range, 6);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
-        assert_eq!(tychentropy.target_entropy_bytes, 1);
-        assert_eq!(tychentropy.target_entropy_bits, 8);
-        assert_eq!(tychentropy.sequence, vec![4, 5, 3, 6, 2, 1]);
-        assert_eq!(tychentropy.entropy_generating_sequence, vec![3, 2, 1, 0]);
-        assert_eq!(tychentropy.generated_entropy_bits, 8);
-        assert_eq!(tychentropy.is_entropy_ready, true);
-        assert_eq!(tychentropy.entropy_bit_string, "11100100");
-        assert_eq!(tychentropy.entropy_bit_vector, vec![1, 1, 1, 0, 0, 1, 0, 0]);
-        assert_eq!(tychentropy.entropy_bytes_vector.len(), 1);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1);
+    #[test]
+    fn health_tests_name_failed_face_with_face_alphabet_01() {
+        let mut input = TychentropyNewInput::new(6, 32).unwrap();
+        input.set_face_alphabet(FaceAlphabet::Lettered).unwrap();
+        input.set_health_test_config(Some(HealthTestConfig::default()));
+        let mut tychentropy = Tychentropy::new(input);
+        let test = tychentropy
+            .add_rolls_from_text("c c c c c c c c c")
+            .err()
+            .unwrap();
+        assert_eq!(
+            test,
+            ProcessError::IngestionStoppedAtPosition {
+                index: 8,
+                report: IngestionReport::new(8, 0),
+                error: Box::new(ProcessError::NaturalDatumFailedHealthTest {
+                    test: "repetition count".to_string(),
+                    range: 6,
+                    face: "C".to_string()
+                })
+            }
+        );
     }
 
     #[test]
-    fn add_datum_works_in_normal_condition_06() {
-        let range = 6;
-        let target_entropy_bytes = 1;
-        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+    fn health_tests_reject_final_roll_01() {
+        let rolls = (0..50)
+            .map(|index| if index < 41 { index % 5 + 1 } else { 6 })
+            .collect::<Vec<u64>>();
+        let mut input = TychentropyNewInput::new(6, 16).unwrap();
+        input.set_extraction_method(ExtractionMethod::SeedSigner);
+        input.set_health_test_config(Some(HealthTestConfig::default()));
         let mut tychentropy = Tychentropy::new(input);
 
-        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+        // The 50th roll would complete a SeedSigner seed, but it is the ninth 6 in a row.
+        let result = tychentropy.add_rolls(rolls);
 
-        assert_eq!(tychentropy.range, 6);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
-        assert_eq!(tychentropy.target_entropy_bytes, 1);
-        assert_eq!(tychentropy.target_entropy_bits, 8);
-        assert_eq!(tychentropy.entropy_generating_sequence.len(), 4);
-        assert_eq!(tychentropy.generated_entropy_bits, 8);
-        assert_eq!(tychentropy.is_entropy_ready, true);
-        assert_eq!(tychentropy.entropy_bit_string.len(), 8);
-        assert_eq!(tychentropy.entropy_bit_vector.len(), 8);
-        assert_eq!(tychentropy.entropy_bytes_vector.len(), 1);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1);
+        assert!(matches!(
+            result.err().unwrap(),
+            ProcessError::IngestionStoppedAtPosition { index: 49, .. }
+        ));
+        assert!(!tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.get_sequence().len(), 49);
+        assert!(tychentropy.mnemonic.is_none());
+        assert!(tychentropy.finalize_hashed_entropy().is_err());
+
+        tychentropy.acknowledge_health_test_failure().unwrap();
+        tychentropy.add_rolls(vec![2]).unwrap();
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(*tychentropy.get_sequence().last().unwrap(), 2);
     }
 
     #[test]
-    fn add_datum_works_in_normal_condition_07() {
-        let range = 6;
-        let target_entropy_bytes = 1;
-        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+    fn health_tests_block_hashed_entropy_finalization_01() {
+        let mut input = TychentropyNewInput::new(6, 32).unwrap();
+        input.set_extraction_method(ExtractionMethod::Coldcard);
+        input.set_health_test_config(Some(HealthTestConfig::default()));
         let mut tychentropy = Tychentropy::new(input);
-        let sequence = vec![1, 4, 6, 2, 3, 4, 1, 4, 6, 4, 2];
+        let rolls = (0..100).map(|index| index % 5 + 1).collect::<Vec<u64>>();
+        tychentropy.add_rolls(rolls).unwrap();
+        assert!(tychentropy.add_rolls(vec![6; 9]).is_err());
 
-        generate_pre_determined_series_of_data_till_entropy_is_full(
-            range,
-            sequence,
-            &mut tychentropy,
+        assert_eq!(
+            tychentropy.finalize_hashed_entropy().err().unwrap(),
+            ProcessError::HealthTestFailureIsNotAcknowledged {
+                test: "repetition count".to_string(),
+                range: 6
+            }
         );
-
-        assert_eq!(tychentropy.range, 6);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 2);
-        assert_eq!(tychentropy.target_entropy_bytes, 1);
-        assert_eq!(tychentropy.target_entropy_bits, 8);
-        assert_eq!(tychentropy.sequence, vec![1, 4, 6, 2, 3]);
-        assert_eq!(tychentropy.entropy_generating_sequence, vec![0, 3, 1, 2]);
-        assert_eq!(tychentropy.generated_entropy_bits, 8);
-        assert_eq!(tychentropy.is_entropy_ready, true);
-        assert_eq!(tychentropy.entropy_bit_string, "00110110");
-        assert_eq!(tychentropy.entropy_bit_vector, vec![0, 0, 1, 1, 0, 1, 1, 0]);
-        assert_eq!(tychentropy.entropy_bytes_vector.len(), 1);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1);
+        assert!(!tychentropy.is_entropy_ready);
+        tychentropy.acknowledge_health_test_failure().unwrap();
+        tychentropy.finalize_hashed_entropy().unwrap();
+        assert!(tychentropy.is_entropy_ready);
+        assert_eq!(tychentropy.get_sequence().len(), 108);
     }
 
     #[test]
-    fn add_datum_works_in_normal_condition_08() {
-        let range = 2048;
-        let target_entropy_bytes = 1024;
-        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+    fn die_bias_monitor_is_off_by_default_01() {
+        let input = TychentropyNewInput::new(6, 32).unwrap();
         let mut tychentropy = Tychentropy::new(input);
-
-        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
-
-        assert_eq!(tychentropy.range, 2048);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 11);
-        assert_eq!(tychentropy.target_entropy_bytes, 1024);
-        assert_eq!(tychentropy.target_entropy_bits, 8192);
-        assert_eq!(tychentropy.generated_entropy_bits, 8195); // every datum has 11 bits and that's not a multiple of 8. Hence a bit of over doing here.
-        assert_eq!(tychentropy.is_entropy_ready, true);
-        assert_eq!(tychentropy.entropy_bit_string.len(), 8192);
-        assert_eq!(tychentropy.entropy_bit_vector.len(), 8192);
-        assert_eq!(tychentropy.entropy_bytes_vector.len(), 1024);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1024);
+        tychentropy.add_rolls(vec![6; 40]).unwrap();
+        assert!(tychentropy.get_die_bias_monitor().is_none());
+        assert!(tychentropy.get_die_bias_warning().is_none());
     }
 
     #[test]
-    fn add_datum_works_in_normal_condition_09() {
-        let range = 632;
-        let target_entropy_bytes = 893;
-        let input = TychentropyNewInput::new(range, target_entropy_bytes).unwrap();
+    fn reset_keeps_extraction_method_01() {
+        let mut input = TychentropyNewInput::new(6, 1).unwrap();
+        input.set_extraction_method(ExtractionMethod::RangeCoding);
         let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
 
-        generate_random_data_sequence_till_entropy_is_full(range, &mut tychentropy);
+        tychentropy.reset_data();
 
-        assert_eq!(tychentropy.range, 632);
-        assert_eq!(tychentropy.full_bits_in_each_datum, 9);
-        assert_eq!(tychentropy.target_entropy_bytes, 893);
-        assert_eq!(tychentropy.target_entropy_bits, 7144);
-        assert_eq!(tychentropy.generated_entropy_bits, 7146); // every datum has 11 bits and that's not a multiple of 8. Hence a bit of over doing here.
-        assert_eq!(tychentropy.is_entropy_ready, true);
-        assert_eq!(tychentropy.entropy_bit_string.len(), 7144);
-        assert_eq!(tychentropy.entropy_bit_vector.len(), 7144);
-        assert_eq!(tychentropy.entropy_bytes_vector.len(), 893);
-        assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 893);
+        assert_eq!(tychentropy.extraction_method, ExtractionMethod::RangeCoding);
+        assert_eq!(tychentropy.sequence, vec![]);
     }
 
     #[test]
@@ -534,7 +3350,7 @@ mod test {
         assert!(result.is_err());
         assert_eq!(
             result.err().unwrap(),
-            ProcessError::NaturalDatumOutOfRange { value: 7, range: 6 }
+            ProcessError::DatumRangeIsNotAcceptedInSession { range: 8 }
         )
     }
 
@@ -559,12 +3375,12 @@ mod test {
         assert_eq!(tychentropy.sequence, vec![1, 4, 6, 2, 3]);
         assert_eq!(tychentropy.entropy_generating_sequence, vec![0, 3, 1, 2]);
         assert_eq!(tychentropy.generated_entropy_bits, 8);
-        assert_eq!(tychentropy.is_entropy_ready, true);
+        assert!(tychentropy.is_entropy_ready);
         assert_eq!(tychentropy.entropy_bit_string, "00110110");
         assert_eq!(tychentropy.entropy_bit_vector, vec![0, 0, 1, 1, 0, 1, 1, 0]);
-        assert_eq!(tychentropy.entropy_bytes_vector, vec![108]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![54]);
         assert_eq!(tychentropy.rng_entropy_bytes_vector, vec![]);
-        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![108]);
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![54]);
     }
 
     #[test]
@@ -590,10 +3406,10 @@ mod test {
         assert_eq!(tychentropy.sequence, vec![1, 4, 6, 2, 3]);
         assert_eq!(tychentropy.entropy_generating_sequence, vec![0, 3, 1, 2]);
         assert_eq!(tychentropy.generated_entropy_bits, 8);
-        assert_eq!(tychentropy.is_entropy_ready, true);
+        assert!(tychentropy.is_entropy_ready);
         assert_eq!(tychentropy.entropy_bit_string, "00110110");
         assert_eq!(tychentropy.entropy_bit_vector, vec![0, 0, 1, 1, 0, 1, 1, 0]);
-        assert_eq!(tychentropy.entropy_bytes_vector, vec![108]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![54]);
         assert_eq!(tychentropy.rng_entropy_bytes_vector.len(), 1);
         assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1);
         assert_ne!(
@@ -638,6 +3454,388 @@ mod test {
         );
     }
 
+    #[test]
+    fn mix_with_rng_records_keyed_mix_01() {
+        let mut input = TychentropyNewInput::new(6, 16).unwrap();
+        input.set_mixing_strategy(MixingStrategy::HkdfSha256);
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
+
+        tychentropy.mix_with_rng().unwrap();
+
+        let record = tychentropy.get_mixing_record().clone().unwrap();
+        assert_eq!(*record.get_strategy(), MixingStrategy::HkdfSha256);
+        assert_eq!(*record.get_target_entropy_bits(), 128);
+        assert_eq!(
+            *record.get_rng_entropy_bytes(),
+            tychentropy.rng_entropy_bytes_vector
+        );
+        assert_eq!(
+            record.remix(&tychentropy.entropy_bytes_vector).unwrap(),
+            tychentropy.final_entropy_bytes_vector
+        );
+        let xor = MixingStrategy::Xor
+            .mix(
+                &tychentropy.entropy_bytes_vector,
+                &tychentropy.rng_entropy_bytes_vector,
+                128,
+            )
+            .unwrap();
+        assert_ne!(xor, tychentropy.final_entropy_bytes_vector);
+
+        tychentropy.recover_original_entropy_bytes_after_mix();
+        assert!(tychentropy.get_mixing_record().is_none());
+        assert_eq!(
+            tychentropy.final_entropy_bytes_vector,
+            tychentropy.entropy_bytes_vector
+        );
+    }
+
+    #[test]
+    fn mix_with_rng_keeps_padding_with_keyed_mix_01() {
+        let mut input = TychentropyNewInput::new_with_target_bits(6, 11).unwrap();
+        input.set_mixing_strategy(MixingStrategy::HmacSha256);
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
+        tychentropy.mix_with_rng().unwrap();
+        assert_eq!(tychentropy.final_entropy_bytes_vector[1] & 0b0001_1111, 0);
+        tychentropy.reset_data();
+        assert_eq!(tychentropy.mixing_strategy, MixingStrategy::HmacSha256);
+    }
+
+    fn external_entropy_bytes() -> Vec<u8> {
+        ring::digest::digest(&ring::digest::SHA256, b"air gapped hardware rng")
+            .as_ref()
+            .to_vec()
+    }
+
+    #[test]
+    fn mix_with_external_entropy_works_01() {
+        let input = TychentropyNewInput::new(6, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
+        let external = external_entropy_bytes();
+
+        let results = tychentropy.mix_with_external_entropy(&external).unwrap();
+
+        assert!(StatisticalTestsOverview::from(&results)
+            .get_failed_tests()
+            .is_empty());
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, external);
+        let expected = tychentropy
+            .entropy_bytes_vector
+            .iter()
+            .zip(external.iter())
+            .map(|(data, rng)| data ^ rng)
+            .collect::<Vec<u8>>();
+        assert_eq!(tychentropy.final_entropy_bytes_vector, expected);
+        tychentropy.undo_last_datum().unwrap();
+        tychentropy.redo().unwrap();
+        assert_eq!(tychentropy.final_entropy_bytes_vector, expected);
+    }
+
+    #[test]
+    fn mix_with_external_entropy_emits_error_for_wrong_length_01() {
+        let input = TychentropyNewInput::new(6, 16).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
+        assert_eq!(
+            tychentropy
+                .mix_with_external_entropy(&external_entropy_bytes())
+                .err()
+                .unwrap(),
+            ProcessError::ProvidedRngEntropyLengthDoesNotMatchTarget {
+                provided_len: 32,
+                target_len: 16
+            }
+        );
+        assert!(tychentropy.rng_entropy_bytes_vector.is_empty());
+    }
+
+    #[test]
+    fn mix_with_external_entropy_emits_error_for_failed_tests_01() {
+        let input = TychentropyNewInput::new(6, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
+        let result = tychentropy.mix_with_external_entropy(&[0; 32]);
+        match result.err().unwrap() {
+            ProcessError::ExternalEntropyFailedStatisticalTests { failed_tests } => {
+                assert!(failed_tests.contains(&"frequency".to_string()))
+            }
+            error => panic!("unexpected error: {}", error),
+        }
+        assert_eq!(
+            tychentropy.final_entropy_bytes_vector,
+            tychentropy.entropy_bytes_vector
+        );
+    }
+
+    #[test]
+    fn mix_with_external_entropy_from_reader_works_01() {
+        let input = TychentropyNewInput::new(6, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
+        let mut long_dump = external_entropy_bytes();
+        long_dump.push(0);
+        assert_eq!(
+            tychentropy
+                .mix_with_external_entropy_from_reader(long_dump.as_slice())
+                .err()
+                .unwrap(),
+            ProcessError::ProvidedRngEntropyLengthDoesNotMatchTarget {
+                provided_len: 33,
+                target_len: 32
+            }
+        );
+        let external = external_entropy_bytes();
+        tychentropy
+            .mix_with_external_entropy_from_reader(external.as_slice())
+            .unwrap();
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, external);
+    }
+
+    #[test]
+    fn mix_with_external_entropy_from_file_works_01() {
+        let input = TychentropyNewInput::new(6, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
+        let path = std::env::temp_dir().join("tychentropy_external_entropy_test.bin");
+        std::fs::write(&path, external_entropy_bytes()).unwrap();
+        let result = tychentropy.mix_with_external_entropy_from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_ok());
+        assert_eq!(
+            tychentropy.rng_entropy_bytes_vector,
+            external_entropy_bytes()
+        );
+        assert!(matches!(
+            tychentropy.mix_with_external_entropy_from_file(&path),
+            Err(ProcessError::ExternalEntropyCouldNotBeRead { .. })
+        ));
+    }
+
+    #[test]
+    fn mix_with_rng_sources_works_01() {
+        let input = TychentropyNewInput::new(6, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(vec![1, 4, 6, 2, 3]).unwrap();
+
+        let mut source = DeterministicRngSource::new(b"seed");
+        tychentropy
+            .mix_with_rng_sources(&mut [&mut source])
+            .unwrap();
+
+        let mut expected_rng_bytes = [0u8; 1];
+        DeterministicRngSource::new(b"seed")
+            .fill_bytes(&mut expected_rng_bytes)
+            .unwrap();
+        assert_eq!(tychentropy.rng_entropy_bytes_vector, expected_rng_bytes);
+        assert_eq!(
+            tychentropy.final_entropy_bytes_vector,
+            vec![54 ^ expected_rng_bytes[0]]
+        );
+    }
+
+    /// Fails every time, to test sessions with a broken rng.
+    struct FailingRngSource;
+
+    impl RngSource for FailingRngSource {
+        fn get_name(&self) -> String {
+            "failing".to_string()
+        }
+
+        fn fill_bytes(&mut self, _bytes: &mut [u8]) -> Result<(), ProcessError> {
+            Err(ProcessError::RngSourceFailed {
+                source: self.get_name(),
+                error: "no entropy".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn mix_with_rng_sources_emits_error_for_failing_source_01() {
+        let input = TychentropyNewInput::new(6, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(vec![1, 4, 6, 2, 3]).unwrap();
+        let mut source = DeterministicRngSource::new(b"seed");
+        let result = tychentropy.mix_with_rng_sources(&mut [&mut source, &mut FailingRngSource]);
+        assert_eq!(
+            result.err().unwrap(),
+            ProcessError::RngSourceFailed {
+                source: "failing".to_string(),
+                error: "no entropy".to_string()
+            }
+        );
+        assert!(tychentropy.rng_entropy_bytes_vector.is_empty());
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![54]);
+        assert_eq!(tychentropy.history.len(), 5);
+    }
+
+    #[test]
+    fn mix_with_committed_rng_works_01() {
+        let input = TychentropyNewInput::new(6, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let mut source = DeterministicRngSource::new(b"seed");
+        let commitment = tychentropy
+            .commit_to_rng_sources(&mut [&mut source])
+            .unwrap();
+        let mut rng_entropy_bytes = [0u8; 1];
+        DeterministicRngSource::new(b"seed")
+            .fill_bytes(&mut rng_entropy_bytes)
+            .unwrap();
+        assert_eq!(
+            commitment,
+            ring::digest::digest(&ring::digest::SHA256, &rng_entropy_bytes)
+                .as_ref()
+                .to_vec()
+        );
+        tychentropy.add_rolls(vec![1, 4, 6, 2, 3]).unwrap();
+        assert_eq!(
+            tychentropy.mix_with_rng().err().unwrap(),
+            ProcessError::RngIsCommittedAndMustBeRevealed
+        );
+
+        assert!(tychentropy.history.iter().all(|event| match event {
+            SessionEvent::RngCommitted { commitment: shown } => *shown == commitment,
+            _ => true,
+        }));
+
+        tychentropy.mix_with_committed_rng(&commitment).unwrap();
+
+        assert_eq!(
+            tychentropy.final_entropy_bytes_vector,
+            vec![54 ^ rng_entropy_bytes[0]]
+        );
+        let rng_commitment = tychentropy.get_rng_commitment().clone().unwrap();
+        assert_eq!(
+            *rng_commitment.get_revealed_bytes().unwrap(),
+            rng_entropy_bytes
+        );
+        assert!(matches!(
+            tychentropy.history.last().unwrap(),
+            SessionEvent::RngRevealed {
+                matches_commitment: true,
+                ..
+            }
+        ));
+        tychentropy.undo_last_datum().unwrap();
+        assert!(tychentropy.get_rng_commitment().is_some());
+        tychentropy.redo().unwrap();
+        assert_eq!(
+            tychentropy.final_entropy_bytes_vector,
+            vec![54 ^ rng_entropy_bytes[0]]
+        );
+    }
+
+    #[test]
+    fn mix_with_committed_rng_emits_error_for_other_commitment_01() {
+        let input = TychentropyNewInput::new(6, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let mut source = DeterministicRngSource::new(b"seed");
+        let commitment = tychentropy
+            .commit_to_rng_sources(&mut [&mut source])
+            .unwrap();
+        tychentropy.add_rolls(vec![1, 4, 6, 2, 3]).unwrap();
+        // The operator wrote down a commitment to other bytes.
+        let shown_commitment = ring::digest::digest(&ring::digest::SHA256, &[0x42])
+            .as_ref()
+            .to_vec();
+        assert_ne!(shown_commitment, commitment);
+
+        assert_eq!(
+            tychentropy
+                .mix_with_committed_rng(&shown_commitment)
+                .err()
+                .unwrap(),
+            ProcessError::RngRevealDoesNotMatchCommitment
+        );
+
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![54]);
+        assert!(tychentropy.rng_entropy_bytes_vector.is_empty());
+        assert!(matches!(
+            tychentropy.history.last().unwrap(),
+            SessionEvent::RngRevealed {
+                matches_commitment: false,
+                ..
+            }
+        ));
+        tychentropy.undo_last_datum().unwrap();
+        tychentropy.redo().unwrap();
+        assert_eq!(tychentropy.final_entropy_bytes_vector, vec![54]);
+    }
+
+    #[test]
+    fn mix_with_external_entropy_emits_error_when_rng_is_committed_01() {
+        let input = TychentropyNewInput::new(6, 32).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        let mut source = DeterministicRngSource::new(b"seed");
+        let commitment = tychentropy
+            .commit_to_rng_sources(&mut [&mut source])
+            .unwrap();
+        generate_random_data_sequence_till_entropy_is_full(6, &mut tychentropy);
+        let final_entropy_bytes = tychentropy.final_entropy_bytes_vector.clone();
+
+        assert_eq!(
+            tychentropy
+                .mix_with_external_entropy(&external_entropy_bytes())
+                .err()
+                .unwrap(),
+            ProcessError::RngIsCommittedAndMustBeRevealed
+        );
+        assert_eq!(
+            tychentropy
+                .mix_with_external_entropy_from_reader(&external_entropy_bytes()[..])
+                .err()
+                .unwrap(),
+            ProcessError::RngIsCommittedAndMustBeRevealed
+        );
+        assert_eq!(tychentropy.final_entropy_bytes_vector, final_entropy_bytes);
+
+        tychentropy.mix_with_committed_rng(&commitment).unwrap();
+        assert_eq!(
+            tychentropy
+                .mix_with_external_entropy(&external_entropy_bytes())
+                .err()
+                .unwrap(),
+            ProcessError::RngIsCommittedAndMustBeRevealed
+        );
+    }
+
+    #[test]
+    fn debug_leaves_out_committed_rng_bytes_01() {
+        let input = TychentropyNewInput::new(6, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.commit_to_rng().unwrap();
+
+        let debug = format!("{:?}", tychentropy);
+
+        assert!(debug.contains("committed_rng_entropy_bytes: CommittedRngBytes(1 bytes)"));
+        assert!(debug.contains("rng_entropy_bytes: None"));
+    }
+
+    #[test]
+    fn commit_to_rng_emits_error_out_of_order_01() {
+        let input = TychentropyNewInput::new(6, 1).unwrap();
+        let mut tychentropy = Tychentropy::new(input);
+        tychentropy.add_rolls(vec![1, 4, 6, 2, 3]).unwrap();
+        assert_eq!(
+            tychentropy.mix_with_committed_rng(&[0; 32]).err().unwrap(),
+            ProcessError::NoRngCommitmentFound
+        );
+        assert_eq!(
+            tychentropy.commit_to_rng().err().unwrap(),
+            ProcessError::RngCommitmentMustPrecedeData
+        );
+        tychentropy.reset_data();
+        tychentropy.commit_to_rng().unwrap();
+        assert_eq!(
+            tychentropy.commit_to_rng().err().unwrap(),
+            ProcessError::RngIsAlreadyCommitted
+        );
+        tychentropy.reset_data();
+        assert!(tychentropy.get_rng_commitment().is_none());
+    }
+
     #[test]
     fn mix_rng_and_data_entropy_emits_error_when_entropy_is_not_ready_01() {
         let range = 1024;
@@ -715,10 +3913,10 @@ mod test {
         assert_eq!(tychentropy.sequence, vec![1, 4, 6, 2, 3]);
         assert_eq!(tychentropy.entropy_generating_sequence, vec![0, 3, 1, 2]);
         assert_eq!(tychentropy.generated_entropy_bits, 8);
-        assert_eq!(tychentropy.is_entropy_ready, true);
+        assert!(tychentropy.is_entropy_ready);
         assert_eq!(tychentropy.entropy_bit_string, "00110110");
         assert_eq!(tychentropy.entropy_bit_vector, vec![0, 0, 1, 1, 0, 1, 1, 0]);
-        assert_eq!(tychentropy.entropy_bytes_vector, vec![108]);
+        assert_eq!(tychentropy.entropy_bytes_vector, vec![54]);
         assert_eq!(tychentropy.rng_entropy_bytes_vector.len(), 1);
         assert_eq!(tychentropy.final_entropy_bytes_vector.len(), 1);
         assert_ne!(
@@ -760,7 +3958,7 @@ mod test {
         assert_eq!(tychentropy.sequence, vec![]);
         assert_eq!(tychentropy.entropy_generating_sequence, vec![]);
         assert_eq!(tychentropy.generated_entropy_bits, 0);
-        assert_eq!(tychentropy.is_entropy_ready, false);
+        assert!(!tychentropy.is_entropy_ready);
         assert_eq!(tychentropy.entropy_bit_string, "");
         assert_eq!(tychentropy.entropy_bit_vector, vec![]);
         assert_eq!(tychentropy.entropy_bytes_vector, vec![]);
@@ -20,6 +20,13 @@ pub fn u64_to_binary(data: u64, bit_space_len: u32) -> Result<String, UtilsError
     }
 }
 
+pub fn u128_to_bit_vec(data: u128, bit_space_len: u32) -> Vec<u8> {
+    (0..bit_space_len)
+        .rev()
+        .map(|index| ((data >> index) & 1) as u8)
+        .collect()
+}
+
 pub fn vec_u8_to_bit_string(data: &[u8]) -> String {
     let mut buff = String::new();
     data.iter()
@@ -55,6 +62,19 @@ mod test {
         )
     }
 
+    #[test]
+    fn u128_to_bit_vec_works_01() {
+        let result = u128_to_bit_vec(42, 8);
+        assert_eq!(result, vec![0, 0, 1, 0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn u128_to_bit_vec_works_02() {
+        let result = u128_to_bit_vec(u128::MAX, 128);
+        assert_eq!(result.len(), 128);
+        assert!(result.iter().all(|bit| *bit == 1));
+    }
+
     #[test]
     fn vec_u8_to_bit_string_works_01() {
         let data = vec![255];